@@ -0,0 +1,28 @@
+//! Full-screen background pattern: the old String-per-row Paragraph against `SubtlePattern`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use scoundrel::theme::BackgroundPattern;
+use scoundrel::ui::{paragraph_pattern, SubtlePattern};
+
+const WIDTH: u16 = 200;
+const HEIGHT: u16 = 60;
+
+fn background_pattern(c: &mut Criterion) {
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).expect("test backend");
+    let area = Rect::new(0, 0, WIDTH, HEIGHT);
+    let mut group = c.benchmark_group("background_pattern");
+    group.bench_function("paragraph", |b| b.iter(|| {
+        terminal.draw(|f| paragraph_pattern(f, area, BackgroundPattern::Dots, Color::DarkGray)).expect("draw");
+    }));
+    group.bench_function("widget", |b| b.iter(|| {
+        terminal.draw(|f| f.render_widget(SubtlePattern { pattern: BackgroundPattern::Dots, color: Color::DarkGray }, area)).expect("draw");
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, background_pattern);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+//! One-off unlocks for finishing challenge runs, kept across sessions.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use crate::persist::PersistHandle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Achievement {
+    /// Cleared the dungeon with the no-weapons rule.
+    NoWeaponWin,
+    /// Cleared the dungeon with the no-potions rule.
+    NoPotionWin,
+}
+
+impl Achievement {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Achievement::NoWeaponWin => "Bare Knuckles (win without weapons)",
+            Achievement::NoPotionWin => "Iron Stomach (win without potions)",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Achievements {
+    unlocked: BTreeSet<Achievement>,
+}
+
+impl Achievements {
+    fn path() -> &'static str { "scoundrel_achievements.json" }
+
+    pub fn load() -> Self {
+        fs::read_to_string(Path::new(Self::path()))
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    /// Records `a`; returns true the first time it is unlocked.
+    pub fn unlock(&mut self, a: Achievement, persist: &PersistHandle) -> bool {
+        if !self.unlocked.insert(a) { return false; }
+        if let Ok(text) = serde_json::to_string(self) { persist.write("achievements", Self::path(), text); }
+        true
+    }
+}
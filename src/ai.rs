@@ -0,0 +1,40 @@
+//! A computer player that plays whole runs through the same `PlayerAction`s as the keyboard,
+//! so full games can be exercised without a human.
+
+use crate::cards::{Card, Suit};
+use crate::game::{AvoidStatus, Game, PlayerAction, UseMode};
+
+/// Takes whatever helps most right now: avoids rooms that would kill it, drinks when hurt,
+/// picks up stronger weapons, then fights the monster that hurts least.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GreedyAi;
+
+impl GreedyAi {
+    /// The next move for `game`, or `None` when the room is empty.
+    pub fn choose_action(&self, game: &Game) -> Option<PlayerAction> {
+        let cards: Vec<(usize, Card)> = game.room.iter().enumerate().filter_map(|(i, c)| c.map(|c| (i, c))).collect();
+        let take = |slot: usize| PlayerAction::TakeCard { slot, mode: UseMode::Default };
+        let damage = |slot: usize| game.preview_fight(slot, UseMode::Default).unwrap_or(0);
+
+        let room_damage: i32 = cards.iter().map(|&(slot, _)| damage(slot)).sum();
+        if game.avoid_status() == AvoidStatus::Ready && room_damage >= game.player.hp {
+            return Some(PlayerAction::AvoidRoom);
+        }
+        let best_of = |suit: Suit| cards.iter().filter(|(_, c)| c.suit == suit).max_by_key(|(_, c)| c.monster_value());
+        if !game.potion_used_this_turn
+            && game.player.hp < game.player.max_hp
+            && let Some(&(slot, _)) = best_of(Suit::Hearts)
+        {
+            return Some(take(slot));
+        }
+        let current = game.player.weapon.as_ref().map_or(0, |w| w.value);
+        if let Some(&(slot, card)) = best_of(Suit::Diamonds)
+            && card.monster_value() > current
+        {
+            return Some(take(slot));
+        }
+        let cheapest = cards.iter().filter(|(_, c)| c.is_monster()).min_by_key(|&&(slot, _)| damage(slot));
+        cheapest.or(cards.first()).map(|&(slot, _)| take(slot))
+    }
+}
+
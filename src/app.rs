@@ -1,32 +1,145 @@
+use std::fs::File;
 use std::io::{self, Write};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
-use anyhow::Result;
-use crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseEventKind};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use anyhow::{Context, Result};
+use crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+use crossterm::terminal::{self, disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
 use crossterm::{execute, queue};
 use ratatui::backend::CrosstermBackend;
-use ratatui::Terminal;
+use ratatui::{Terminal, TerminalOptions, Viewport};
 
-use crate::game::{Game, GamePhase};
-use crate::ui::draw;
+use crate::attract::IdleWatch;
+use crate::deck::DeckSpec;
+use crate::export;
+use crate::headless;
+use crate::persist::{write_atomic, Persister};
+use crate::rules::Rules;
+use crate::spectator::StateWriter;
+use crate::theme::ColorLevel;
+use crate::runs::RunRecord;
+use crate::session::SessionSummary;
+use crate::shutdown::Shutdown;
+use crate::game::{now_ts, parse_scores, Game, FRAME_MS, GamePhase, PlayerAction, UseMode};
+use crate::simulate::{self, Strategy};
+use crate::ui::{draw, draw_inline, draw_plain, inline_summary, strip_colors, LayoutCapture, INLINE_HEIGHT};
+
+/// Switches for a session, filled in from the command line by `cli::Cli`.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    pub inline: bool,
+    pub plain: bool,
+    pub no_color: bool,
+    pub colorblind: bool, // on top of the `colorblind` setting
+    pub debug: bool, // state dump on quit, like SCOUNDREL_DEBUG=1
+    pub deck: Option<PathBuf>,
+    pub seed: Option<u64>,
+    pub name: Option<String>,
+    pub export_history: Option<PathBuf>,
+    pub dump_run: Option<PathBuf>,
+    pub rules: Rules,
+    pub state_output: Option<PathBuf>,
+    pub script: Option<PathBuf>,
+    pub export_leaderboard: Option<PathBuf>,
+    pub import_leaderboard: Option<PathBuf>,
+    pub export_scores: Option<PathBuf>,
+    pub import_scores: Option<PathBuf>,
+    pub color: Option<ColorLevel>, // detected from the environment when None
+    pub simulate: Option<u32>,     // games per strategy for `--simulate`
+    pub strategies: Vec<Strategy>, // `--strategies`; only `first` when empty
+    pub csv: Option<PathBuf>,      // per-game rows from `--simulate`
+}
+
+// `seed` deals the first run; random when None
+fn new_game(deck_spec: Option<&DeckSpec>, rules: &Rules, seed: Option<u64>) -> Result<Game> {
+    let mut game = Game::new_with_seed_and_rules(seed.unwrap_or_else(rand::random), rules.clone());
+    if let Some(spec) = deck_spec {
+        game.set_deck_spec(spec.clone()).with_context(|| format!("invalid deck '{}'", spec.name))?;
+    }
+    Ok(game)
+}
+
+/// Play the session `opts` describes; what was played comes back for the quit summary (empty without the TUI).
+pub fn run(opts: RunOptions) -> Result<SessionSummary> {
+    if let Some(path) = &opts.dump_run {
+        let record = RunRecord::load(path).with_context(|| format!("reading run {}", path.display()))?;
+        return export::write_run_text(&record, &mut io::stdout().lock()).map(|()| SessionSummary::default());
+    }
+    // Load and validate the deck before touching the terminal so errors print normally
+    let deck_spec = match &opts.deck {
+        Some(path) => Some(DeckSpec::load(path).with_context(|| format!("loading deck {}", path.display()))?),
+        None => None,
+    };
+    let mut game = new_game(deck_spec.as_ref(), &opts.rules, opts.seed)?;
+    game.theme.set_color_level(opts.color.unwrap_or_else(ColorLevel::detect));
+    game.theme.plain = opts.plain;
+    game.theme.colorblind |= opts.colorblind;
+    if opts.export_leaderboard.is_some() || opts.import_leaderboard.is_some() {
+        return leaderboard_csv(&mut game, &opts).map(|()| SessionSummary::default());
+    }
+    if opts.export_scores.is_some() || opts.import_scores.is_some() {
+        return transfer_scores(&mut game, &opts).map(|()| SessionSummary::default());
+    }
+    if let Some(games) = opts.simulate {
+        return simulate::run(game, games, &opts).map(|()| SessionSummary::default());
+    }
+    let debug = opts.debug || std::env::var("SCOUNDREL_DEBUG").is_ok_and(|v| v == "1");
+    if opts.export_history.is_some() || opts.script.is_some() {
+        return headless::run(game, &opts).map(|()| SessionSummary::default());
+    }
+
+    // Score and history files are written off the render loop from here on
+    let persister = Persister::spawn();
+    game.persist = persister.handle();
+
+    let mut spectator = opts.state_output.clone().map(StateWriter::spawn);
+
+    // Inline mode needs room below the prompt; fall back to the full UI on tiny terminals
+    let inline = opts.inline && terminal::size().map(|(_, h)| h > INLINE_HEIGHT).unwrap_or(false);
+
+    // SIGTERM/SIGINT/SIGHUP end the loop like `q`, so the run is saved, the terminal restored and queued
+    // writes land; in raw mode Ctrl+C is a key press, so these only come from outside (`kill`, a closing terminal)
+    let shutdown = Shutdown::install().context("installing signal handlers")?;
 
-pub fn run() -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let viewport = if inline {
+        Viewport::Inline(INLINE_HEIGHT)
+    } else {
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        Viewport::Fullscreen
+    };
     let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    // Inline setup queries the cursor position, which can fail; never leave raw mode behind
+    let mut terminal = match Terminal::with_options(backend, TerminalOptions { viewport }) {
+        Ok(t) => t,
+        Err(e) => {
+            cleanup_terminal(inline)?;
+            return Err(e.into());
+        }
+    };
     terminal.hide_cursor()?;
 
-    let tick_rate = Duration::from_millis(1000 / 30);
+    let tick_rate = Duration::from_millis(FRAME_MS);
     let mut last_tick = Instant::now();
-
-    let mut game = Game::new();
+    let mut idle = IdleWatch::new();
+    let mut session = SessionSummary::default();
 
     let res = loop {
-        terminal.draw(|f| draw(f, &game)).ok();
+        if let Some(signal) = shutdown.requested() {
+            session.signal = Some(signal);
+            break Ok(());
+        }
+        let mut capture = LayoutCapture::default();
+        terminal.draw(|f| {
+            if inline { draw_inline(f, &game) } else if game.theme.plain { draw_plain(f, &game) } else { draw(f, &game, idle.demo.as_ref().map(|d| &d.game), &mut capture) }
+            if opts.no_color { strip_colors(f.buffer_mut()); }
+        }).ok();
+        game.last_layout = Some(capture.layout);
+        // Same point as the redraw: whatever is on screen is what spectators see
+        if let Some(writer) = spectator.as_mut() { writer.publish(game.public_state()); }
 
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
@@ -39,80 +152,77 @@ pub fn run() -> Result<()> {
                     if key.kind != KeyEventKind::Press {
                         continue;
                     }
-                    let in_name = matches!(game.phase, GamePhase::NameEntry);
-                    match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => break Ok(()),
-                    // While entering name, any Char is treated as input
-                    KeyCode::Char(c) if in_name => { game.name_input_char(c); }
-                    KeyCode::Char('n') if !in_name => {
-                        match game.phase {
-                            GamePhase::Menu => { game.phase = GamePhase::NameEntry; game.name_input.clear(); }
-                            GamePhase::GameOver | GamePhase::Leaderboard => { game.phase = GamePhase::NameEntry; game.name_input.clear(); }
-                            _ => game.new_run(),
-                        }
+                    // Ctrl+C quits like `q`, even from a text prompt or the idle demo; mid-run both ask first
+                    if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                        let _ = game.apply_action(PlayerAction::Quit);
+                        if game.quit_requested { break Ok(()); }
+                        continue;
                     }
-                    KeyCode::Char('?') => game.toggle_help(),
-                    // Menu navigation and Game Over scroll
-                    KeyCode::Down => {
-                        match game.phase {
-                            GamePhase::Menu => game.select_menu_down(),
-                            GamePhase::GameOver => { game.game_over_scroll = game.game_over_scroll.saturating_add(1); }
-                            _ => {}
-                        }
+                    // A key that ends the idle demo does nothing else
+                    if idle.touch() { continue; }
+                    // Ctrl+H: phase changes to stderr (redirect it, e.g. `2>phases.log`); many terminals send Backspace instead
+                    if key.code == KeyCode::Char('h') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                        eprint!("phase history:\r\n{}", game.phase_history_text().replace('\n', "\r\n"));
+                        terminal.clear()?;
+                        continue;
                     }
-                    KeyCode::Up => {
-                        match game.phase {
-                            GamePhase::Menu => game.select_menu_up(),
-                            GamePhase::GameOver => { game.game_over_scroll = game.game_over_scroll.saturating_sub(1); }
-                            _ => {}
-                        }
+                    // Ctrl+R: start over from the menu; a run in progress keeps its save slot and is not scored
+                    if key.code == KeyCode::Char('r') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                        let (level, plain, colorblind) = (game.theme.color_level, game.theme.plain, game.theme.colorblind);
+                        game = new_game(deck_spec.as_ref(), &opts.rules, opts.seed)?;
+                        game.persist = persister.handle();
+                        game.theme.set_color_level(level);
+                        game.theme.plain = plain;
+                        game.theme.colorblind = colorblind;
+                        session.leave_run();
+                        continue;
                     }
-                    KeyCode::Enter => {
-                        match game.phase {
-                            GamePhase::Menu => game.menu_activate(),
-                            GamePhase::NameEntry => game.name_input_submit(),
-                            GamePhase::Running => game.take_selected_default(),
-                            GamePhase::Leaderboard | GamePhase::GameOver => { /* no-op */ }
+                    if key.code == KeyCode::Char('d') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                        match write_debug_dump(&game) {
+                            Ok(path) => game.notify(format!("Debug snapshot saved to {}", path)),
+                            Err(e) => game.notify(format!("Debug snapshot failed: {}", e)),
                         }
+                        continue;
                     }
-                    KeyCode::Backspace => {
-                        if matches!(game.phase, GamePhase::NameEntry) { game.name_input_backspace(); }
+                    // Any key closes the first-run welcome, q and Esc included
+                    if game.show_welcome {
+                        let _ = game.apply_action(PlayerAction::DismissWelcome);
+                        continue;
                     }
-                    KeyCode::Char(' ') if !in_name => { if matches!(game.phase, GamePhase::Running) { game.take_selected_default(); } }
-                    KeyCode::Char('v') if !in_name => if matches!(game.phase, GamePhase::Running) { game.avoid_room() },
-                    KeyCode::Right => if matches!(game.phase, GamePhase::Running) { game.move_selection(1, 0) },
-                    KeyCode::Left => if matches!(game.phase, GamePhase::Running) { game.move_selection(-1, 0) },
-                    KeyCode::Char('b') if !in_name => if matches!(game.phase, GamePhase::Running) { game.take_selected_barehand() },
-                    KeyCode::Char('w') if !in_name => if matches!(game.phase, GamePhase::Running) { game.take_selected_weapon() },
-                    // Quick pick shortcuts: 1-4 select slot and take default action
-                    KeyCode::Char('1') if !in_name => { if matches!(game.phase, GamePhase::Running) { game.selected = 0; game.take_selected_default(); } },
-                    KeyCode::Char('2') if !in_name => { if matches!(game.phase, GamePhase::Running) { game.selected = 1; game.take_selected_default(); } },
-                    KeyCode::Char('3') if !in_name => { if matches!(game.phase, GamePhase::Running) { game.selected = 2; game.take_selected_default(); } },
-                    KeyCode::Char('4') if !in_name => { if matches!(game.phase, GamePhase::Running) { game.selected = 3; game.take_selected_default(); } },
-                    KeyCode::Char('l') if !in_name => { game.phase = GamePhase::Leaderboard; },
-                    KeyCode::Char('m') if !in_name => { game.phase = GamePhase::Menu; },
-                    KeyCode::Char('r') if !in_name => game = Game::new(),
-                    KeyCode::PageUp => { if matches!(game.phase, GamePhase::GameOver) { game.game_over_scroll = game.game_over_scroll.saturating_sub(10); } },
-                    KeyCode::PageDown => { if matches!(game.phase, GamePhase::GameOver) { game.game_over_scroll = game.game_over_scroll.saturating_add(10); } },
-                    KeyCode::Home => { if matches!(game.phase, GamePhase::GameOver) { game.game_over_scroll = 0; } },
-                    KeyCode::End => { if matches!(game.phase, GamePhase::GameOver) { game.game_over_scroll = u16::MAX; } },
-                    _ => {}
-                }
+                    let typing = game.text_input_active();
+                    let action = match key.code {
+                        KeyCode::Char('q') if !typing => Some(PlayerAction::Quit),
+                        // Esc closes an open popup (or leaves seed entry, the changelog or the save slots) first, otherwise quits
+                        KeyCode::Esc if !game.overlay_open() && !matches!(game.phase, GamePhase::SeedEntry | GamePhase::Changelog | GamePhase::SaveSlots | GamePhase::Shop) => Some(PlayerAction::Quit),
+                        _ => key_action(&game, key.code),
+                    };
+                    if let Some(action) = action {
+                        // Rejected actions explain themselves in the game log; during a run they are also flagged
+                        if let Err(e) = game.apply_action(action) && game.flag_rejection(e) { ring_bell(&mut terminal)?; }
+                        session.observe(&game);
+                    }
+                    if game.quit_requested { break Ok(()); }
                 }
                 Event::Mouse(me) => {
-                    match me.kind {
-                        MouseEventKind::ScrollUp => {
-                            if matches!(game.phase, GamePhase::GameOver) {
-                                game.game_over_scroll = game.game_over_scroll.saturating_sub(3);
-                            }
-                        }
-                        MouseEventKind::ScrollDown => {
-                            if matches!(game.phase, GamePhase::GameOver) {
-                                game.game_over_scroll = game.game_over_scroll.saturating_add(3);
-                            }
-                        }
-                        _ => {}
+                    if idle.touch() { continue; }
+                    let action = match me.kind {
+                        MouseEventKind::ScrollUp => Some(PlayerAction::ScrollUp(3)),
+                        MouseEventKind::ScrollDown => Some(PlayerAction::ScrollDown(3)),
+                        // Clicking a room card takes it, like its quick-pick number
+                        MouseEventKind::Down(MouseButton::Left) if !game.overlay_open() => game
+                            .last_layout
+                            .as_ref()
+                            .and_then(|l| l.slot_at(me.column, me.row))
+                            .map(|slot| PlayerAction::TakeCard { slot, mode: UseMode::Default }),
+                        _ => None,
+                    };
+                    if let Some(action) = action
+                        && let Err(e) = game.apply_action(action)
+                        && game.flag_rejection(e)
+                    {
+                        ring_bell(&mut terminal)?;
                     }
+                    session.observe(&game);
                 }
                 _ => {}
             }
@@ -121,6 +231,13 @@ pub fn run() -> Result<()> {
         if last_tick.elapsed() >= tick_rate {
             last_tick = Instant::now();
             game.tick();
+            session.observe(&game);
+            let demo_allowed = game.phase == GamePhase::Menu && !game.overlay_open() && !game.config.reduced_motion && !game.theme.plain && !inline;
+            idle.tick(demo_allowed);
+            for err in persister.take_errors() {
+                game.notify(err.clone());
+                game.log.push(err);
+            }
             if matches!(game.phase, GamePhase::GameOver) {
                 // keep running until user presses 'n' or 'q'
             }
@@ -128,14 +245,245 @@ pub fn run() -> Result<()> {
     };
 
     // Restore terminal
-    cleanup_terminal()
-        .and(res)
+    if inline {
+        terminal.clear()?;
+        terminal.show_cursor()?;
+    }
+    cleanup_terminal(inline)?;
+    // Save the run in progress once more so its slot has the time played up to the quit
+    if matches!(game.phase, GamePhase::Running | GamePhase::Shop) { game.autosave(); }
+    // Nothing queued is lost on quit: wait for the writer to drain
+    for err in persister.shutdown() { eprintln!("{}", err); }
+    if debug { eprint!("{}", game.debug_dump()); }
+    // Leave the finished run in the scrollback when playing inline
+    if inline && matches!(game.phase, GamePhase::GameOver) {
+        for line in inline_summary(&game) { println!("{}", line); }
+    }
+    res.map(|()| session)
+}
+
+// --import-leaderboard merges first, so both together export the merged board
+fn leaderboard_csv(game: &mut Game, opts: &RunOptions) -> Result<()> {
+    if let Some(path) = &opts.import_leaderboard {
+        let mut file = File::open(path).with_context(|| format!("opening {}", path.display()))?;
+        let entries = export::import_leaderboard_csv(&mut file).with_context(|| format!("reading {}", path.display()))?;
+        let total = entries.len();
+        let added = game.merge_scores(entries);
+        println!("Imported {} of {} scores from {} ({} already present)", added, total, path.display(), total - added);
+    }
+    if let Some(path) = &opts.export_leaderboard {
+        let mut file = File::create(path).with_context(|| format!("creating {}", path.display()))?;
+        export::export_leaderboard_csv(&game.leaderboard, &mut file)?;
+        println!("Exported {} scores to {}", game.leaderboard.len(), path.display());
+    }
+    Ok(())
+}
+
+// Same as the CSV flags, in the scores file's own JSON format; any version of that file imports
+fn transfer_scores(game: &mut Game, opts: &RunOptions) -> Result<()> {
+    if let Some(path) = &opts.import_scores {
+        let text = std::fs::read_to_string(path).with_context(|| format!("opening {}", path.display()))?;
+        let entries = parse_scores(&text).with_context(|| format!("reading {}", path.display()))?;
+        let total = entries.len();
+        let added = game.merge_scores(entries);
+        println!("Imported {} of {} scores from {} ({} already present)", added, total, path.display(), total - added);
+    }
+    if let Some(path) = &opts.export_scores {
+        write_atomic(path, &game.scores_json()).with_context(|| format!("writing {}", path.display()))?;
+        println!("Exported {} scores to {}", game.leaderboard.len(), path.display());
+    }
+    Ok(())
+}
+
+// Ctrl+D: snapshot for bug reports, timestamped so repeated presses never overwrite
+fn write_debug_dump(game: &Game) -> Result<String> {
+    let path = format!("scoundrel_debug_{}.txt", now_ts());
+    std::fs::write(&path, game.debug_dump())?;
+    Ok(path)
+}
+
+/// Translate a key press into a game action for the current screen.
+fn key_action(game: &Game, code: KeyCode) -> Option<PlayerAction> {
+    use PlayerAction::*;
+    // A pending confirmation takes every key until answered
+    if game.pending_confirm.is_some() {
+        return match code {
+            KeyCode::Char('y') | KeyCode::Enter => Some(ConfirmAccept),
+            KeyCode::Char('n') | KeyCode::Esc => Some(ConfirmCancel),
+            _ => None,
+        };
+    }
+    if code == KeyCode::F(1) { return Some(ToggleFullHelp); }
+    // While the help popup is open it owns scrolling keys; nothing leaks to the screen below
+    if game.show_help {
+        return match code {
+            KeyCode::Char('?') | KeyCode::Esc => Some(ToggleHelp),
+            KeyCode::Right | KeyCode::Tab if game.help_full => Some(HelpTab(1)),
+            KeyCode::Left | KeyCode::BackTab if game.help_full => Some(HelpTab(-1)),
+            KeyCode::Down => Some(ScrollDown(1)),
+            KeyCode::Up => Some(ScrollUp(1)),
+            KeyCode::PageDown => Some(ScrollDown(10)),
+            KeyCode::PageUp => Some(ScrollUp(10)),
+            KeyCode::Home => Some(ScrollUp(u16::MAX)),
+            KeyCode::End => Some(ScrollDown(u16::MAX)),
+            _ => None,
+        };
+    }
+    // Capital V opens the changelog from any screen that isn't taking typed text
+    if code == KeyCode::Char('V') && !game.text_input_active() { return Some(ShowChangelog); }
+    let take = |slot: usize, mode: UseMode| Some(TakeCard { slot, mode });
+    match game.phase {
+        // While entering name, any Char is treated as input
+        GamePhase::NameEntry => match code {
+            KeyCode::Char(c) => Some(NameInputChar(c)),
+            KeyCode::Backspace => Some(NameInputBackspace),
+            KeyCode::Enter => Some(NameInputSubmit),
+            KeyCode::Tab => Some(NameInputToggleMode),
+            KeyCode::BackTab => Some(NameInputToggleHardcore),
+            KeyCode::Down => Some(NameInputSuggest { forward: true }),
+            KeyCode::Up => Some(NameInputSuggest { forward: false }),
+            _ => None,
+        },
+        GamePhase::SeedEntry => match code {
+            KeyCode::Char(c) => Some(SeedInputChar(c)),
+            KeyCode::Backspace => Some(SeedInputBackspace),
+            KeyCode::Enter => Some(SeedInputSubmit),
+            KeyCode::Esc => Some(SeedInputCancel),
+            _ => None,
+        },
+        GamePhase::Menu => match code {
+            KeyCode::Up => Some(MenuUp),
+            KeyCode::Down => Some(MenuDown),
+            KeyCode::Enter => Some(MenuActivate),
+            KeyCode::Char('w') => Some(PlayWeeklySeed),
+            _ => global_key_action(code),
+        },
+        GamePhase::Running => match code {
+            KeyCode::Enter | KeyCode::Char(' ') => take(game.selected, UseMode::Default),
+            KeyCode::Char('w') => take(game.selected, UseMode::Weapon),
+            KeyCode::Char('b') => take(game.selected, UseMode::Barehand),
+            // Quick pick shortcuts: 1-4 select slot and take default action
+            KeyCode::Char(c @ '1'..='4') => take(c as usize - '1' as usize, UseMode::Default),
+            KeyCode::Char('v') => Some(AvoidRoom),
+            KeyCode::Char('r') => Some(AbandonRun),
+            KeyCode::Right => Some(MoveSelection { dx: 1, dy: 0 }),
+            KeyCode::Left => Some(MoveSelection { dx: -1, dy: 0 }),
+            // Rows only exist in the 2x2 layout
+            KeyCode::Up if game.room_is_grid() => Some(MoveSelection { dx: 0, dy: -1 }),
+            KeyCode::Down if game.room_is_grid() => Some(MoveSelection { dx: 0, dy: 1 }),
+            KeyCode::Home => Some(SelectSlot(0)),
+            KeyCode::End => Some(SelectSlot(3)),
+            _ => global_key_action(code),
+        },
+        GamePhase::GameOver => match code {
+            KeyCode::Down => Some(ScrollDown(1)),
+            KeyCode::Up => Some(ScrollUp(1)),
+            KeyCode::PageDown => Some(ScrollDown(10)),
+            KeyCode::PageUp => Some(ScrollUp(10)),
+            KeyCode::Home => Some(ScrollUp(u16::MAX)),
+            KeyCode::End => Some(ScrollDown(u16::MAX)),
+            _ => global_key_action(code),
+        },
+        GamePhase::Shop => match code {
+            KeyCode::Up => Some(ShopMove(-1)),
+            KeyCode::Down => Some(ShopMove(1)),
+            KeyCode::Enter | KeyCode::Char(' ') => Some(ShopConfirm),
+            KeyCode::Esc => Some(ShopLeave),
+            KeyCode::Char('r') => Some(AbandonRun),
+            _ => global_key_action(code),
+        },
+        GamePhase::SaveSlots => match code {
+            KeyCode::Up => Some(SaveSlotUp),
+            KeyCode::Down => Some(SaveSlotDown),
+            KeyCode::Enter => Some(SaveSlotActivate),
+            KeyCode::Delete | KeyCode::Backspace => Some(SaveSlotDelete),
+            KeyCode::Esc => Some(ShowMenu),
+            _ => global_key_action(code),
+        },
+        GamePhase::Changelog => match code {
+            KeyCode::Esc => Some(CloseChangelog),
+            KeyCode::Down => Some(ScrollDown(1)),
+            KeyCode::Up => Some(ScrollUp(1)),
+            KeyCode::PageDown => Some(ScrollDown(10)),
+            KeyCode::PageUp => Some(ScrollUp(10)),
+            KeyCode::Home => Some(ScrollUp(u16::MAX)),
+            KeyCode::End => Some(ScrollDown(u16::MAX)),
+            _ => global_key_action(code),
+        },
+        GamePhase::RunHistory if game.run_open => match code {
+            KeyCode::Enter | KeyCode::Esc => Some(RunHistoryToggle),
+            KeyCode::Down => Some(ScrollDown(1)),
+            KeyCode::Up => Some(ScrollUp(1)),
+            KeyCode::PageDown => Some(ScrollDown(10)),
+            KeyCode::PageUp => Some(ScrollUp(10)),
+            KeyCode::Home => Some(ScrollUp(u16::MAX)),
+            KeyCode::End => Some(ScrollDown(u16::MAX)),
+            _ => None,
+        },
+        GamePhase::RunHistory => match code {
+            KeyCode::Up => Some(RunHistoryUp),
+            KeyCode::Down => Some(RunHistoryDown),
+            KeyCode::Enter => Some(RunHistoryToggle),
+            _ => global_key_action(code),
+        },
+        GamePhase::Leaderboard if game.leaderboard_detail => match code {
+            KeyCode::Enter | KeyCode::Esc => Some(LeaderboardToggleDetail),
+            _ => None,
+        },
+        // The name filter has focus like name entry, so every letter (q included) is typed
+        GamePhase::Leaderboard if game.leaderboard_filter.editing => match code {
+            KeyCode::Char(c) => Some(LeaderboardFilterChar(c)),
+            KeyCode::Backspace => Some(LeaderboardFilterBackspace),
+            KeyCode::Enter => Some(LeaderboardFilterDone),
+            KeyCode::Esc => Some(LeaderboardFilterCancel),
+            KeyCode::Up => Some(LeaderboardUp),
+            KeyCode::Down => Some(LeaderboardDown),
+            _ => None,
+        },
+        GamePhase::Leaderboard => match code {
+            KeyCode::Tab => Some(LeaderboardSwitchMode),
+            KeyCode::Char('w') => Some(LeaderboardToggleWins),
+            KeyCode::Char('f') => Some(LeaderboardToggleFastest),
+            KeyCode::Char('g') => Some(LeaderboardToggleSeed),
+            KeyCode::Char('s') => Some(LeaderboardCycleSort),
+            KeyCode::Char('e') => Some(LeaderboardExport),
+            KeyCode::Char('r') => Some(LeaderboardReverseSort),
+            KeyCode::Char('/') => Some(LeaderboardFilterStart),
+            KeyCode::Up => Some(LeaderboardUp),
+            KeyCode::Down => Some(LeaderboardDown),
+            KeyCode::Enter => Some(LeaderboardToggleDetail),
+            KeyCode::Delete | KeyCode::Backspace => Some(LeaderboardDelete),
+            _ => global_key_action(code),
+        },
+    }
+}
+
+// Keys shared by every screen except name entry
+fn global_key_action(code: KeyCode) -> Option<PlayerAction> {
+    match code {
+        KeyCode::Char('?') => Some(PlayerAction::ToggleHelp),
+        KeyCode::Char('n') => Some(PlayerAction::NewRun),
+        KeyCode::Char('l') => Some(PlayerAction::ShowLeaderboard),
+        KeyCode::Char('h') => Some(PlayerAction::ShowRunHistory),
+        KeyCode::Char('m') => Some(PlayerAction::ShowMenu),
+        _ => None,
+    }
+}
+
+// BEL through the backend's own stdout handle, so it is ordered with the frames drawn there
+fn ring_bell(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    let backend = terminal.backend_mut();
+    backend.write_all(b"\x07")?;
+    backend.flush()?;
+    Ok(())
 }
 
-fn cleanup_terminal() -> Result<()> {
+fn cleanup_terminal(inline: bool) -> Result<()> {
     disable_raw_mode()?;
     let mut stdout = io::stdout();
-    queue!(stdout, LeaveAlternateScreen, DisableMouseCapture)?;
+    if !inline {
+        queue!(stdout, LeaveAlternateScreen, DisableMouseCapture)?;
+    }
     stdout.flush()?;
     Ok(())
 }
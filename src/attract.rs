@@ -0,0 +1,82 @@
+//! Idle attract mode: after the menu sits untouched for a while, a bot plays a demo run behind
+//! it. The demo is its own `Game`, owned by the app and never saved.
+
+use std::time::{Duration, Instant};
+
+use crate::game::{Game, GamePhase, PlayerAction};
+use crate::simulate::{self, Strategy};
+
+/// Menu idle time before the demo starts.
+pub const IDLE_BEFORE: Duration = Duration::from_secs(30);
+/// Frames between the bot's moves.
+const MOVE_EVERY: u32 = 20;
+/// Frames a finished demo stays on screen before the next one is dealt.
+const PAUSE_AFTER_RUN: u32 = 90;
+
+pub struct Attract {
+    pub game: Game,
+    frames: u32,
+}
+
+impl Attract {
+    // No `Default`, like `Game::new` this reads the player's files
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        let mut game = Game::new();
+        simulate::make_unattended(&mut game);
+        game.new_run();
+        Attract { game, frames: 0 }
+    }
+
+    /// One frame: animations advance every time, the bot moves every `MOVE_EVERY` frames.
+    pub fn tick(&mut self) {
+        self.game.tick();
+        self.frames += 1;
+        if !matches!(self.game.phase, GamePhase::Running | GamePhase::Shop) {
+            if self.frames >= PAUSE_AFTER_RUN { *self = Self::new(); }
+            return;
+        }
+        if self.frames < MOVE_EVERY { return; }
+        self.frames = 0;
+        // Shops are walked past; a rejected move just waits for the next turn
+        let action = if self.game.phase == GamePhase::Shop { Some(PlayerAction::ShopLeave) } else { Strategy::Cautious.choose(&self.game) };
+        if let Some(action) = action { let _ = self.game.apply_action(action); }
+    }
+}
+
+/// Tracks how long the menu has gone untouched and runs the demo once it has.
+pub struct IdleWatch {
+    since: Instant,
+    pub demo: Option<Attract>,
+}
+
+impl Default for IdleWatch {
+    fn default() -> Self { Self::new() }
+}
+
+impl IdleWatch {
+    pub fn new() -> Self {
+        IdleWatch { since: Instant::now(), demo: None }
+    }
+
+    /// Input of any kind: the clock restarts. Returns whether a demo was showing, in which
+    /// case the input only dismissed it.
+    pub fn touch(&mut self) -> bool {
+        self.since = Instant::now();
+        self.demo.take().is_some()
+    }
+
+    /// Per frame. `allowed` is false off the menu, under a popup, or with reduced motion.
+    pub fn tick(&mut self, allowed: bool) {
+        if !allowed {
+            self.since = Instant::now();
+            self.demo = None;
+            return;
+        }
+        match self.demo.as_mut() {
+            Some(demo) => demo.tick(),
+            None if self.since.elapsed() >= IDLE_BEFORE => self.demo = Some(Attract::new()),
+            None => {}
+        }
+    }
+}
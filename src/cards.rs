@@ -1,8 +1,22 @@
 use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
 use strum::EnumIter;
+use thiserror::Error;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+/// Why a card written as text ("K♠", "Td") could not be read.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CardParseError {
+    #[error("unknown suit '{0}'")]
+    UnknownSuit(String),
+    #[error("unknown rank '{0}'")]
+    UnknownRank(String),
+    #[error("'{0}' needs a rank followed by a suit")]
+    Incomplete(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
 pub enum Suit {
     Clubs,
     Diamonds,
@@ -11,6 +25,27 @@ pub enum Suit {
 }
 
 impl Suit {
+    /// Reads a suit symbol (`♠`) or letter (`s`, either case).
+    pub fn from_char(c: char) -> Option<Suit> {
+        match c.to_ascii_lowercase() {
+            'c' | '♣' => Some(Suit::Clubs),
+            'd' | '♦' => Some(Suit::Diamonds),
+            'h' | '♥' => Some(Suit::Hearts),
+            's' | '♠' => Some(Suit::Spades),
+            _ => None,
+        }
+    }
+
+    /// Lowercase ASCII letter used by `Card::notation`.
+    pub fn letter(self) -> char {
+        match self {
+            Suit::Clubs => 'c',
+            Suit::Diamonds => 'd',
+            Suit::Hearts => 'h',
+            Suit::Spades => 's',
+        }
+    }
+
     pub fn symbol(self) -> char {
         match self {
             Suit::Clubs => '♣',
@@ -20,6 +55,10 @@ impl Suit {
         }
     }
 
+    pub fn is_red(self) -> bool {
+        matches!(self, Suit::Diamonds | Suit::Hearts)
+    }
+
     pub fn color(self) -> Color {
         match self {
             Suit::Diamonds | Suit::Hearts => Color::Red,
@@ -34,12 +73,15 @@ impl Display for Suit {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Rank(pub u8);
 
 impl Rank {
     pub fn new(v: u8) -> Self { Self(v) }
     pub fn value(self) -> u8 { self.0 }
+    /// Jack, queen or king.
+    pub fn is_face(self) -> bool { (11..=13).contains(&self.0) }
+    pub fn is_ace(self) -> bool { self.0 == 1 }
     pub fn label(self) -> &'static str {
         match self.0 {
             1 => "A",
@@ -66,13 +108,30 @@ impl Rank {
     }
 }
 
+/// Accepts `A`, `2`-`10`, `T` for ten, `J`, `Q` and `K`, in either case.
+impl FromStr for Rank {
+    type Err = CardParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let v = match s.to_ascii_uppercase().as_str() {
+            "A" => 1,
+            "T" => 10,
+            "J" => 11,
+            "Q" => 12,
+            "K" => 13,
+            n => n.parse().ok().filter(|n| (2..=10).contains(n)).ok_or_else(|| CardParseError::UnknownRank(s.to_string()))?,
+        };
+        Ok(Rank(v))
+    }
+}
+
 impl Display for Rank {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(f, "{}", self.label())
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Card {
     pub suit: Suit,
     pub rank: Rank,
@@ -80,6 +139,28 @@ pub struct Card {
 
 impl Card {
     pub fn new(suit: Suit, rank: Rank) -> Self { Self { suit, rank } }
+
+    /// Two-character ASCII form for files and scripts, e.g. "Ks" or "Td"; parses back with `from_str`.
+    pub fn notation(&self) -> String {
+        let rank = if self.rank.value() == 10 { "T" } else { self.rank.label() };
+        format!("{}{}", rank, self.suit.letter())
+    }
+}
+
+/// Reads the rank then the suit: "K♠", "Ks", "10♦" and "Td" all work.
+impl FromStr for Card {
+    type Err = CardParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let mut chars = s.chars();
+        let (Some(last), rank) = (chars.next_back(), chars.as_str()) else {
+            return Err(CardParseError::Incomplete(s.to_string()));
+        };
+        if rank.is_empty() { return Err(CardParseError::Incomplete(s.to_string())); }
+        let suit = Suit::from_char(last).ok_or_else(|| CardParseError::UnknownSuit(last.to_string()))?;
+        Ok(Card::new(suit, rank.parse()?))
+    }
 }
 
 impl Display for Card {
@@ -103,4 +184,74 @@ impl Card {
             _ => 0,
         }
     }
+    /// The card one monster value weaker (an ace becomes a king); twos and non-monsters stay as they are.
+    pub fn weakened(self) -> Card {
+        if !self.is_monster() || self.monster_value() <= 2 { return self; }
+        Card::new(self.suit, Rank::new(self.monster_value() - 1))
+    }
+    /// What the card is worth in score arithmetic: monsters count their threat, weapons
+    /// count against it since they absorb damage, potions count their healing.
+    pub fn point_value(&self) -> i32 {
+        match self.suit {
+            Suit::Clubs | Suit::Spades => self.monster_value() as i32,
+            Suit::Diamonds => -(self.rank.value() as i32),
+            Suit::Hearts => self.rank.value() as i32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::Deck;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn every_dungeon_card_round_trips_through_its_notation() {
+        let deck = Deck::scoundrel_deck();
+        assert!(deck.iter().count() > 0);
+        for card in deck.iter() {
+            let notation = card.notation();
+            assert_eq!(notation.len(), 2, "{notation}");
+            assert_eq!(notation.parse::<Card>(), Ok(*card));
+            // The on-screen form reads back too
+            assert_eq!(card.to_string().parse::<Card>(), Ok(*card));
+        }
+    }
+
+    #[test]
+    fn every_rank_of_every_suit_round_trips() {
+        for suit in Suit::iter() {
+            for v in 1..=13 {
+                let card = Card::new(suit, Rank::new(v));
+                assert_eq!(card.notation().parse::<Card>(), Ok(card));
+                assert_eq!(card.notation().to_lowercase().parse::<Card>(), Ok(card));
+            }
+        }
+    }
+
+    #[test]
+    fn errors_name_the_part_that_failed() {
+        assert_eq!("Kx".parse::<Card>().unwrap_err().to_string(), "unknown suit 'x'");
+        assert_eq!("1s".parse::<Card>().unwrap_err().to_string(), "unknown rank '1'");
+        assert_eq!("s".parse::<Card>(), Err(CardParseError::Incomplete("s".into())));
+        assert_eq!("".parse::<Card>(), Err(CardParseError::Incomplete(String::new())));
+    }
+
+    #[test]
+    fn point_value_by_suit() {
+        let value = |s: &str| s.parse::<Card>().unwrap().point_value();
+        // Monsters are worth their fighting strength, aces high
+        assert_eq!([value("2C"), value("JC"), value("KC"), value("AC")], [2, 11, 13, 14]);
+        assert_eq!([value("2S"), value("QS"), value("AS")], [2, 12, 14]);
+        // Weapons count against the threat, potions for the player
+        assert_eq!([value("2D"), value("7D"), value("TD")], [-2, -7, -10]);
+        assert_eq!([value("2H"), value("5H"), value("TH")], [2, 5, 10]);
+        for suit in [Suit::Clubs, Suit::Spades] {
+            for v in 2..=13 {
+                let card = Card::new(suit, Rank::new(v));
+                assert_eq!(card.point_value(), card.monster_value() as i32);
+            }
+        }
+    }
 }
@@ -0,0 +1,191 @@
+//! Command-line arguments, parsed with clap so `--help` lists every switch.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use clap::Parser;
+
+use crate::app::{self, RunOptions};
+use crate::config::Config;
+use crate::game::Game;
+use crate::rules::Rules;
+use crate::seed_codec;
+use crate::shutdown;
+use crate::simulate::Strategy;
+use crate::theme::ColorLevel;
+
+#[derive(Debug, Parser)]
+#[command(name = "scoundrel", version, about = "Scoundrel, the single-player dungeon crawl with a deck of cards, in the terminal")]
+pub struct Cli {
+    /// Play in a compact 12-line view below the prompt
+    #[arg(long)]
+    inline: bool,
+    /// Screen-reader layout: sentences, no borders or animations
+    #[arg(long)]
+    plain: bool,
+    /// Force the color support: 16, 256 or truecolor
+    #[arg(long, value_name = "LEVEL", value_parser = parse_color)]
+    color: Option<ColorLevel>,
+    /// Draw without any color (bold and reverse video are kept)
+    #[arg(long, conflicts_with = "color")]
+    no_color: bool,
+    /// Mark card kinds by shape and use a blue/yellow HP scale (also a setting)
+    #[arg(long)]
+    colorblind: bool,
+    /// Read and write settings at this path instead of scoundrel_config.json
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+    /// Print the state dump to stderr on quit (same as SCOUNDREL_DEBUG=1)
+    #[arg(long)]
+    debug: bool,
+    /// Don't print the session summary on quit
+    #[arg(long)]
+    quiet: bool,
+    /// Play with a homebrew deck (TOML, or JSON by extension)
+    #[arg(long, value_name = "FILE")]
+    deck: Option<PathBuf>,
+    /// Dungeon seed, as a decimal number or a seed code
+    #[arg(long, value_name = "CODE", value_parser = parse_seed)]
+    seed: Option<u64>,
+    /// Player name for exported, scripted or simulated runs
+    #[arg(long)]
+    name: Option<String>,
+
+    /// Challenge run: diamonds are discarded instead of equipped
+    #[arg(long, help_heading = "Variants")]
+    no_weapons: bool,
+    /// Challenge run: hearts are discarded instead of drunk
+    #[arg(long, help_heading = "Variants")]
+    no_potions: bool,
+    /// Rooms can't be avoided and fights aren't previewed
+    #[arg(long, help_heading = "Variants")]
+    hardcore: bool,
+    /// Newly dealt cards per room that stay face down until selected
+    #[arg(long, value_name = "N", help_heading = "Variants", value_parser = clap::value_parser!(u8).range(0..=3))]
+    hidden_cards: Option<u8>,
+    /// Show the suits of the next room's cards
+    #[arg(long, help_heading = "Variants")]
+    peek: bool,
+    /// Show the next room's cards in full
+    #[arg(long, help_heading = "Variants")]
+    full_peek: bool,
+    /// Points added to a win for every room survived
+    #[arg(long, value_name = "N", help_heading = "Variants", allow_negative_numbers = true)]
+    room_bonus: Option<i32>,
+    /// Weapons break after this many fights
+    #[arg(long, value_name = "N", help_heading = "Variants", value_parser = clap::value_parser!(u8).range(1..))]
+    weapon_uses: Option<u8>,
+    /// Every third room ends in a shop
+    #[arg(long, help_heading = "Variants")]
+    shop: bool,
+    /// Shuffle an avoided room's cards before they go under the deck
+    #[arg(long, help_heading = "Variants")]
+    shuffle_avoided: bool,
+    /// Add half the longest clean-kill streak to the score
+    #[arg(long, help_heading = "Variants")]
+    streak_bonus: bool,
+
+    /// Play one run without the UI (moves on stdin) and write its history as Markdown
+    #[arg(long, value_name = "OUT", help_heading = "Without a terminal")]
+    export_history: Option<PathBuf>,
+    /// Play a seeded run from a command file and print the outcome
+    #[arg(long, value_name = "FILE", help_heading = "Without a terminal")]
+    script: Option<PathBuf>,
+    /// Print a saved run from scoundrel_runs/ as plain text
+    #[arg(long, visible_alias = "replay", value_name = "FILE", help_heading = "Without a terminal")]
+    dump_run: Option<PathBuf>,
+    /// Play this many seeds with the built-in strategies and print their results
+    #[arg(long, visible_alias = "headless", value_name = "N", help_heading = "Without a terminal", value_parser = clap::value_parser!(u32).range(1..))]
+    simulate: Option<u32>,
+    /// Strategies to compare with --simulate: first, cautious
+    #[arg(long, value_name = "A,B", help_heading = "Without a terminal", requires = "simulate", value_delimiter = ',', value_parser = parse_strategy)]
+    strategies: Vec<Strategy>,
+    /// Also write one CSV row per simulated game
+    #[arg(long, value_name = "OUT", help_heading = "Without a terminal", requires = "simulate")]
+    csv: Option<PathBuf>,
+    /// Append the visible game state as JSON lines to a file or FIFO
+    #[arg(long, value_name = "PATH")]
+    state_output: Option<PathBuf>,
+
+    /// Write every leaderboard entry as CSV
+    #[arg(long, value_name = "OUT", help_heading = "Scores")]
+    export_leaderboard: Option<PathBuf>,
+    /// Merge scores from such a CSV into the leaderboard
+    #[arg(long, value_name = "IN", help_heading = "Scores")]
+    import_leaderboard: Option<PathBuf>,
+    /// Copy the whole leaderboard to another scores file
+    #[arg(long, value_name = "OUT", help_heading = "Scores")]
+    export_scores: Option<PathBuf>,
+    /// Merge another scores file into the leaderboard
+    #[arg(long, value_name = "IN", help_heading = "Scores")]
+    import_scores: Option<PathBuf>,
+}
+
+fn parse_seed(code: &str) -> Result<u64, String> {
+    seed_codec::decode(code).map_err(|e| e.to_string())
+}
+
+fn parse_color(level: &str) -> Result<ColorLevel, String> {
+    ColorLevel::parse(level).ok_or_else(|| "expected 16, 256 or truecolor".to_string())
+}
+
+fn parse_strategy(name: &str) -> Result<Strategy, String> {
+    Strategy::parse(name.trim()).ok_or_else(|| "expected first or cautious".to_string())
+}
+
+impl Cli {
+    /// Parse the process arguments (clap prints usage and exits on errors and `--help`) and run the session.
+    pub fn parse_and_run() -> Result<()> {
+        let cli = Self::parse();
+        if let Some(path) = &cli.config { Config::use_path(path.clone()); }
+        let quiet = cli.quiet;
+        let session = app::run(cli.into_options())?;
+        // The terminal is restored by now, so this lands in the normal scrollback
+        if !quiet {
+            let scores = Path::new(Game::scores_path());
+            let scores = std::env::current_dir().map(|dir| dir.join(scores)).unwrap_or_else(|_| scores.to_path_buf());
+            for line in session.lines(&scores) { println!("{}", line); }
+        }
+        if let Some(signal) = session.signal { std::process::exit(shutdown::exit_code(signal)); }
+        Ok(())
+    }
+
+    fn into_options(self) -> RunOptions {
+        let rules = Rules {
+            no_weapons: self.no_weapons,
+            no_potions: self.no_potions,
+            hardcore: self.hardcore,
+            hidden_cards: self.hidden_cards.unwrap_or(0),
+            allow_deck_peek: self.peek || self.full_peek,
+            show_full_next_room: self.full_peek,
+            rooms_bonus_per_room: self.room_bonus.unwrap_or(0),
+            kill_streak_bonus: self.streak_bonus,
+            weapon_breaks_after_uses: self.weapon_uses,
+            enable_shop: self.shop,
+            shuffle_avoided_cards: self.shuffle_avoided,
+        };
+        RunOptions {
+            inline: self.inline,
+            plain: self.plain,
+            no_color: self.no_color,
+            colorblind: self.colorblind,
+            debug: self.debug,
+            deck: self.deck,
+            seed: self.seed,
+            name: self.name,
+            export_history: self.export_history,
+            dump_run: self.dump_run,
+            rules,
+            state_output: self.state_output,
+            script: self.script,
+            export_leaderboard: self.export_leaderboard,
+            import_leaderboard: self.import_leaderboard,
+            export_scores: self.export_scores,
+            import_scores: self.import_scores,
+            color: self.color,
+            simulate: self.simulate,
+            strategies: self.strategies,
+            csv: self.csv,
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::persist::PersistHandle;
+use crate::theme::BackgroundPattern;
+
+/// Player preferences, stored next to the scores file. Missing keys fall back to defaults
+/// so older files keep loading as options are added.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Ask before taking a potion that would be discarded unused.
+    pub confirm_potion_waste: bool,
+    /// Ask before a fight that would kill the player, unless there is nothing else to do.
+    pub confirm_lethal_fight: bool,
+    /// Ask before a new weapon replaces the equipped one and its slain stack.
+    pub confirm_weapon_swap: bool,
+    /// How a rejected move (avoiding twice, an empty slot) is signalled.
+    pub feedback: FeedbackMode,
+    /// How the four room cards are arranged.
+    pub room_layout: RoomLayout,
+    /// Finished runs kept for the Run History screen; older ones are deleted.
+    pub keep_runs: usize,
+    /// Select the strongest monster (or, without monsters, the weakest safe card) when a room is dealt.
+    pub auto_focus_threat: bool,
+    /// Delay between cards when a room is dealt; 0 deals instantly.
+    pub deal_animation_ms: u64,
+    /// No decorative motion: no idle demo on the menu and no flash after fights and potions.
+    pub reduced_motion: bool,
+    /// Order of the Leaderboard screen; `s` cycles it and the choice is saved here.
+    pub leaderboard_sort: LeaderboardSort,
+    /// Show on the Status box what the run would score if it ended now.
+    pub show_score_projection: bool,
+    /// Show on the Status box how many monsters, weapons and potions are left in the deck.
+    pub show_deck_breakdown: bool,
+    /// Show the last rooms of the run as a row of marks on the Equipped box.
+    pub show_minimap: bool,
+    /// Card kinds marked by shape, a blue/yellow HP scale and a double outline on the selected card.
+    pub colorblind: bool,
+    /// Texture behind the screens; `blank` turns it off.
+    pub background_pattern: BackgroundPattern,
+    /// Set once the first-run welcome has been dismissed.
+    pub first_run_done: bool,
+    /// Name submitted for the last run; pre-fills the name entry.
+    pub last_name: Option<String>,
+    /// Page the full help (F1) was last left on; it reopens there.
+    pub help_tab: HelpTab,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { confirm_potion_waste: true, confirm_lethal_fight: true, confirm_weapon_swap: true, feedback: FeedbackMode::Visual, room_layout: RoomLayout::Auto, keep_runs: 200, auto_focus_threat: false, deal_animation_ms: 80, reduced_motion: false, leaderboard_sort: LeaderboardSort::ScoreDesc, show_score_projection: true, show_deck_breakdown: true, show_minimap: true, colorblind: false, background_pattern: BackgroundPattern::Dots, first_run_done: false, last_name: None, help_tab: HelpTab::Rules }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoomLayout {
+    /// One row on wide terminals, a 2x2 grid on narrow ones.
+    Auto,
+    /// Always a single row of four.
+    Row,
+    /// Always two rows of two.
+    Grid,
+}
+
+/// Feedback for a rejected move during a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedbackMode {
+    /// Only the game log mentions it.
+    None,
+    /// The offending cell or the Status box flashes red.
+    Visual,
+    /// The terminal bell rings.
+    Audible,
+    /// Flash and bell.
+    Both,
+}
+
+impl FeedbackMode {
+    pub fn visual(self) -> bool { matches!(self, FeedbackMode::Visual | FeedbackMode::Both) }
+    pub fn audible(self) -> bool { matches!(self, FeedbackMode::Audible | FeedbackMode::Both) }
+}
+
+/// Order of the Leaderboard screen below the podium.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardSort {
+    /// Highest score first.
+    #[serde(alias = "score")]
+    ScoreDesc,
+    /// Lowest score first.
+    ScoreAsc,
+    /// Player names A to Z.
+    NameAsc,
+    /// Newest run first.
+    #[serde(alias = "recent")]
+    DateDesc,
+    /// Players who win most often first.
+    WinRateDesc,
+}
+
+impl LeaderboardSort {
+    // Fastest-time ordering joins the cycle once runs record their duration
+    pub fn next(self) -> Self {
+        match self {
+            LeaderboardSort::ScoreDesc => LeaderboardSort::ScoreAsc,
+            LeaderboardSort::ScoreAsc => LeaderboardSort::NameAsc,
+            LeaderboardSort::NameAsc => LeaderboardSort::DateDesc,
+            LeaderboardSort::DateDesc => LeaderboardSort::WinRateDesc,
+            LeaderboardSort::WinRateDesc => LeaderboardSort::ScoreDesc,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LeaderboardSort::ScoreDesc => "score",
+            LeaderboardSort::ScoreAsc => "lowest score",
+            LeaderboardSort::NameAsc => "name",
+            LeaderboardSort::DateDesc => "date",
+            LeaderboardSort::WinRateDesc => "win rate",
+        }
+    }
+}
+
+/// Pages of the full help popup, in tab order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HelpTab {
+    Rules,
+    Controls,
+    Scoring,
+    Variants,
+}
+
+impl HelpTab {
+    pub const ALL: [HelpTab; 4] = [HelpTab::Rules, HelpTab::Controls, HelpTab::Scoring, HelpTab::Variants];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HelpTab::Rules => "Rules",
+            HelpTab::Controls => "Controls",
+            HelpTab::Scoring => "Scoring",
+            HelpTab::Variants => "Variants",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        Self::ALL.iter().position(|t| *t == self).unwrap_or(0)
+    }
+
+    /// The tab `delta` steps away, wrapping at either end.
+    pub fn step(self, delta: i32) -> Self {
+        let n = Self::ALL.len() as i32;
+        Self::ALL[(self.index() as i32 + delta).rem_euclid(n) as usize]
+    }
+}
+
+// `--config`: settings read from and saved to another file
+static PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+impl Config {
+    fn path() -> &'static Path { PATH_OVERRIDE.get().map_or(Path::new("scoundrel_config.json"), PathBuf::as_path) }
+
+    /// Use `path` for the settings file from now on; only the first call counts.
+    pub fn use_path(path: PathBuf) {
+        let _ = PATH_OVERRIDE.set(path);
+    }
+
+    pub fn load() -> Self {
+        fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    /// Queue a rewrite of the settings file, e.g. after an in-game preference change.
+    pub fn save(&self, persist: &PersistHandle) {
+        match serde_json::to_string_pretty(self) {
+            Ok(json) => persist.write("settings", Self::path(), json),
+            Err(e) => eprintln!("Could not save settings: {}", e),
+        }
+    }
+}
@@ -1,45 +1,302 @@
+use std::fs;
+use std::path::Path;
+
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::rng;
+use rand::{rng, Rng, SeedableRng};
+use serde::Deserialize;
+use thiserror::Error;
 
 use crate::cards::{Card, Rank, Suit};
 
+/// How many of some cards are monsters, weapons and potions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DeckComposition {
+    pub monsters: usize,
+    pub weapons: usize,
+    pub potions: usize,
+}
+
+impl DeckComposition {
+    pub fn of<'a>(cards: impl IntoIterator<Item = &'a Card>) -> Self {
+        let mut counts = Self::default();
+        for card in cards {
+            match card.suit {
+                Suit::Clubs | Suit::Spades => counts.monsters += 1,
+                Suit::Diamonds => counts.weapons += 1,
+                Suit::Hearts => counts.potions += 1,
+            }
+        }
+        counts
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct Deck {
     pub cards: Vec<Card>,
 }
 
 impl Deck {
-    pub fn scoundrel_deck() -> Self {
-        // Build a deck per Scoundrel rules:
-        // - Remove Jokers (not present here)
-        // - Remove Red Face Cards (J,Q,K of Hearts/Diamonds) and Red Aces (A of Hearts/Diamonds)
-        // - Monsters: all Clubs/Spades (2..=10, J,Q,K,A)
-        // - Weapons: Diamonds 2..=10
-        // - Potions: Hearts 2..=10
-        let mut cards = Vec::with_capacity(44);
-        // Clubs & Spades: full 13 ranks (monsters)
-        for suit in [Suit::Clubs, Suit::Spades] {
+    /// All 52 cards, unshuffled: clubs, spades, diamonds, hearts, each ace to king.
+    ///
+    /// Seeded dungeons depend on this order, so keep it stable.
+    pub fn standard_52() -> Self {
+        let mut cards = Vec::with_capacity(52);
+        for suit in [Suit::Clubs, Suit::Spades, Suit::Diamonds, Suit::Hearts] {
             for v in 1..=13u8 {
                 cards.push(Card::new(suit, Rank::new(v)));
             }
         }
-        // Diamonds: only 2..=10 (weapons)
-        for v in 2..=10u8 { cards.push(Card::new(Suit::Diamonds, Rank::new(v))); }
-        // Hearts: only 2..=10 (potions)
-        for v in 2..=10u8 { cards.push(Card::new(Suit::Hearts, Rank::new(v))); }
+        Self { cards }
+    }
+
+    pub fn scoundrel_deck() -> Self {
+        // Scoundrel rules: jokers are never added; red faces and red aces go, leaving
+        // 26 monsters (clubs/spades), 9 weapons (diamonds 2-10) and 9 potions (hearts 2-10)
+        let mut deck = Self::standard_52();
+        deck.remove_cards_matching(|c| c.suit.is_red() && (c.rank.is_face() || c.rank.is_ace()));
+        deck
+    }
+
+    /// Takes out every card matching `pred`, returning them in their original order.
+    pub fn remove_cards_matching<F: Fn(&Card) -> bool>(&mut self, pred: F) -> Vec<Card> {
+        let (removed, kept) = self.cards.drain(..).partition(|c| pred(c));
+        self.cards = kept;
+        removed
+    }
+
+    /// Takes out a whole suit, for variants played without it.
+    pub fn remove_all_of_suit(&mut self, suit: Suit) -> Vec<Card> {
+        self.remove_cards_matching(|c| c.suit == suit)
+    }
+
+    /// Build an (unshuffled) deck from a validated spec.
+    pub fn from_spec(spec: &DeckSpec) -> Result<Self, DeckError> {
+        let mut cards = Vec::new();
+        for entry in &spec.cards {
+            let suit = parse_suit(&entry.suit)?;
+            let rank = entry.rank.value()?;
+            if entry.count == 0 {
+                return Err(DeckError::ZeroCount { card: Card::new(suit, Rank::new(rank)).to_string() });
+            }
+            if !spec.allow_nonstandard {
+                // Red cards only make sense as 2..=10 weapons/potions under the standard rules
+                let ok = match suit {
+                    Suit::Clubs | Suit::Spades => true,
+                    Suit::Diamonds | Suit::Hearts => (2..=10).contains(&rank),
+                };
+                if !ok {
+                    return Err(DeckError::NonstandardCard { card: Card::new(suit, Rank::new(rank)).to_string() });
+                }
+            }
+            for _ in 0..entry.count { cards.push(Card::new(suit, Rank::new(rank))); }
+        }
+        if cards.is_empty() { return Err(DeckError::Empty); }
+        Ok(Self::from_cards(cards))
+    }
+
+    /// Exactly these cards, in this order; the last one is drawn first.
+    pub fn from_cards(cards: Vec<Card>) -> Self {
         Self { cards }
     }
 
     pub fn shuffle(&mut self) {
-        let mut rng = rng();
-        self.cards.shuffle(&mut rng);
+        self.shuffle_with(&mut rng());
+    }
+
+    /// Shuffle with any RNG, so callers can supply a seeded or scripted one.
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.cards.shuffle(rng);
+    }
+
+    /// Shuffles only the cards at `start..end`, counted from the bottom; the range is clamped to the deck.
+    pub fn shuffle_partial(&mut self, start: usize, end: usize, rng: &mut impl Rng) {
+        let end = end.min(self.cards.len());
+        if start < end { self.cards[start..end].shuffle(rng); }
+    }
+
+    /// Deterministic shuffle: the same seed always deals the same dungeon.
+    pub fn shuffle_with_seed(&mut self, seed: u64) {
+        self.shuffle_with(&mut StdRng::seed_from_u64(seed));
     }
 
     pub fn draw(&mut self) -> Option<Card> {
         self.cards.pop()
     }
 
+    /// Cards from the bottom of the deck to the top (the top is the last element).
+    pub fn iter(&self) -> std::slice::Iter<'_, Card> { self.cards.iter() }
+    /// Cards in draw order, starting with the next card `draw` would return.
+    pub fn iter_from_top(&self) -> impl Iterator<Item = &Card> { self.cards.iter().rev() }
+
+    /// Copies of the next `n` cards `draw` would return, in that order; the deck is untouched.
+    pub fn top_n_cards(&self, n: usize) -> Vec<Card> { self.iter_from_top().take(n).copied().collect() }
+
+    /// How many cards of `suit` are left.
+    pub fn cards_of_suit(&self, suit: Suit) -> usize { self.cards.iter().filter(|c| c.suit == suit).count() }
+    pub fn monsters_remaining(&self) -> usize { self.cards_of_suit(Suit::Clubs) + self.cards_of_suit(Suit::Spades) }
+    pub fn weapons_remaining(&self) -> usize { self.cards_of_suit(Suit::Diamonds) }
+    pub fn potions_remaining(&self) -> usize { self.cards_of_suit(Suit::Hearts) }
+
     pub fn len(&self) -> usize { self.cards.len() }
     pub fn is_empty(&self) -> bool { self.cards.is_empty() }
-    pub fn push_bottom(&mut self, card: Card) { self.cards.insert(0, card); }
+    pub fn push_bottom(&mut self, card: Card) { self.insert_at_position(0, card); }
+    /// Puts `card` at `pos` counted from the bottom (0 is the bottom, `len()` the top).
+    pub fn insert_at_position(&mut self, pos: usize, card: Card) { self.cards.insert(pos.min(self.cards.len()), card); }
+    /// Changes the top `n` cards in place, topmost first.
+    pub fn modify_top_n<F: FnMut(&mut Card)>(&mut self, n: usize, f: F) { self.cards.iter_mut().rev().take(n).for_each(f); }
+}
+
+/// A homebrew deck description loaded from TOML or JSON.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeckSpec {
+    pub name: String,
+    /// Lift the weapon/potion 2..=10 restriction on red cards.
+    #[serde(default)]
+    pub allow_nonstandard: bool,
+    pub cards: Vec<DeckSpecEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeckSpecEntry {
+    pub suit: String,
+    pub rank: RankSpec,
+    #[serde(default = "default_count")]
+    pub count: u8,
+}
+
+fn default_count() -> u8 { 1 }
+
+/// Rank as written in a spec: `7`, or a label such as `"K"` / `"A"`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum RankSpec {
+    Number(u8),
+    Label(String),
+}
+
+impl RankSpec {
+    fn value(&self) -> Result<u8, DeckError> {
+        let v = match self {
+            RankSpec::Number(n) => *n,
+            RankSpec::Label(s) => match s.trim().to_ascii_uppercase().as_str() {
+                "A" => 1,
+                "J" => 11,
+                "Q" => 12,
+                "K" => 13,
+                other => other.parse().map_err(|_| DeckError::RankOutOfRange(s.clone()))?,
+            },
+        };
+        if !(1..=13).contains(&v) { return Err(DeckError::RankOutOfRange(v.to_string())); }
+        Ok(v)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DeckError {
+    #[error("unknown suit '{0}'")]
+    UnknownSuit(String),
+    #[error("rank '{0}' is out of range (A, 2-10, J, Q, K)")]
+    RankOutOfRange(String),
+    #[error("{card} is not allowed without allow_nonstandard (weapons and potions are 2-10)")]
+    NonstandardCard { card: String },
+    #[error("{card} has a count of 0 (leave the entry out instead)")]
+    ZeroCount { card: String },
+    #[error("deck has no cards")]
+    Empty,
+    #[error("cannot read deck file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("cannot parse deck file: {0}")]
+    Parse(String),
+}
+
+impl DeckSpec {
+    /// Load a spec, picking the format from the file extension (`.json`, otherwise TOML).
+    pub fn load(path: &Path) -> Result<Self, DeckError> {
+        let text = fs::read_to_string(path)?;
+        if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("json")) {
+            serde_json::from_str(&text).map_err(|e| DeckError::Parse(e.to_string()))
+        } else {
+            toml::from_str(&text).map_err(|e| DeckError::Parse(e.to_string()))
+        }
+    }
+
+    /// Stable identifier used to keep custom-deck scores apart on the leaderboard; fails like
+    /// `Deck::from_spec` when the spec does not make a valid deck.
+    pub fn id(&self) -> Result<String, DeckError> {
+        // FNV-1a over the sorted cards: however the entries are written, ordered or split, the same deck gives the same id
+        let mut cards: Vec<(char, u8)> = Deck::from_spec(self)?.iter().map(|c| (c.suit.letter(), c.rank.value())).collect();
+        cards.sort_unstable();
+        let mut hash: u32 = 0x811c_9dc5;
+        let mut feed = |b: u8| { hash ^= b as u32; hash = hash.wrapping_mul(0x0100_0193); };
+        for (suit, rank) in cards {
+            feed(suit as u8);
+            feed(rank);
+        }
+        Ok(format!("{}#{:08x}", self.name, hash))
+    }
+}
+
+fn parse_suit(s: &str) -> Result<Suit, DeckError> {
+    let s = s.trim();
+    let mut chars = s.chars();
+    let single = chars.next().filter(|_| chars.next().is_none());
+    match s.to_ascii_lowercase().as_str() {
+        "clubs" | "club" => Ok(Suit::Clubs),
+        "diamonds" | "diamond" => Ok(Suit::Diamonds),
+        "hearts" | "heart" => Ok(Suit::Hearts),
+        "spades" | "spade" => Ok(Suit::Spades),
+        _ => single.and_then(Suit::from_char).ok_or_else(|| DeckError::UnknownSuit(s.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(cards: &str) -> DeckSpec {
+        serde_json::from_str(&format!(r#"{{"name": "test", "cards": [{}]}}"#, cards)).expect("test spec")
+    }
+
+    #[test]
+    fn id_is_the_same_however_the_cards_are_written() {
+        let long = spec(r#"{"suit": "Clubs", "rank": "K", "count": 2}, {"suit": "hearts", "rank": 5}"#);
+        let short = spec(r#"{"suit": "c", "rank": 13, "count": 2}, {"suit": "♥", "rank": "5"}"#);
+        assert_eq!(long.id().expect("valid"), short.id().expect("valid"));
+        let other = spec(r#"{"suit": "s", "rank": 13, "count": 2}, {"suit": "h", "rank": 5}"#);
+        assert_ne!(long.id().expect("valid"), other.id().expect("valid"));
+        let reordered = spec(r#"{"suit": "h", "rank": 5}, {"suit": "c", "rank": "K", "count": 2}"#);
+        assert_eq!(long.id().expect("valid"), reordered.id().expect("valid"));
+        let split = spec(r#"{"suit": "c", "rank": "K"}, {"suit": "h", "rank": 5}, {"suit": "c", "rank": "K"}"#);
+        assert_eq!(long.id().expect("valid"), split.id().expect("valid"));
+        let fewer = spec(r#"{"suit": "c", "rank": "K"}, {"suit": "h", "rank": 5}"#);
+        assert_ne!(long.id().expect("valid"), fewer.id().expect("valid"));
+    }
+
+    #[test]
+    fn shipped_example_decks_load() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("decks");
+        for (file, name, size) in [("royal.json", "royal", 20), ("short.toml", "short", 22)] {
+            let spec = DeckSpec::load(&dir.join(file)).unwrap_or_else(|e| panic!("{file}: {e}"));
+            assert_eq!(spec.name, name);
+            let deck = Deck::from_spec(&spec).unwrap_or_else(|e| panic!("{file}: {e}"));
+            assert_eq!(deck.len(), size, "{file}");
+            assert!(spec.id().expect("valid").starts_with(&format!("{name}#")));
+        }
+    }
+
+    #[test]
+    fn id_refuses_a_spec_that_is_not_a_deck() {
+        assert!(matches!(spec(r#"{"suit": "x", "rank": 5}"#).id(), Err(DeckError::UnknownSuit(_))));
+        assert!(matches!(spec(r#"{"suit": "c", "rank": 14}"#).id(), Err(DeckError::RankOutOfRange(_))));
+        assert!(matches!(spec(r#"{"suit": "d", "rank": "K"}"#).id(), Err(DeckError::NonstandardCard { .. })));
+        assert!(matches!(spec("").id(), Err(DeckError::Empty)));
+    }
+
+    #[test]
+    fn zero_count_entries_are_rejected() {
+        let zero = spec(r#"{"suit": "c", "rank": 5}, {"suit": "s", "rank": 9, "count": 0}"#);
+        assert!(matches!(Deck::from_spec(&zero), Err(DeckError::ZeroCount { card }) if card == Card::new(Suit::Spades, Rank::new(9)).to_string()));
+        assert!(zero.id().is_err());
+    }
 }
@@ -0,0 +1,285 @@
+//! Markdown export of a finished run, for posting run histories outside the game,
+//! and CSV export/import of the leaderboard for spreadsheets and merging between machines.
+
+use std::io::{Read, Write};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::cards::Card;
+use crate::game::{format_iso8601, format_ts, now_ts, parse_iso8601, Difficulty, Game, GameEvent, GameMode, Player, RunSummary, ScoreEntry, SCORE_ENTRY_VERSION};
+use crate::runs::RunRecord;
+use crate::seed_codec;
+use crate::ui::history_groups;
+
+/// Header with player, score, date, seed and difficulty, followed by the event table.
+pub fn write_run_markdown(game: &Game, writer: &mut dyn Write) -> Result<()> {
+    let outcome = match (game.score, game.player.hp > 0) {
+        (Some(score), true) => format!("{} (cleared the dungeon)", score),
+        (Some(score), false) => format!("{} (fell in room {})", score, game.room_number),
+        (None, _) => "unfinished".into(),
+    };
+    let difficulty = match &game.deck_spec {
+        Some(spec) => format!("custom deck \"{}\"", spec.name),
+        None => "standard deck".into(),
+    };
+    let difficulty = match game.rules.describe() {
+        Some(active) => format!("{} ({})", difficulty, active),
+        None => difficulty,
+    };
+    let difficulty = match game.game_mode {
+        GameMode::Classic => difficulty,
+        GameMode::Endless => format!("{}, endless ({} dungeons cleared)", difficulty, game.dungeons_cleared),
+    };
+    writeln!(writer, "# Scoundrel run: {}", game.player_name)?;
+    writeln!(writer)?;
+    writeln!(writer, "- **Player:** {}", game.player_name)?;
+    writeln!(writer, "- **Score:** {}", outcome)?;
+    writeln!(writer, "- **Date:** {} UTC", format_ts(now_ts()))?;
+    writeln!(writer, "- **Seed:** `{}`", game.seed_code())?;
+    writeln!(writer, "- **Difficulty:** {}", difficulty)?;
+    if let Some(cause) = &game.death_cause {
+        writeln!(writer, "- **Cause:** {}", cause.describe())?;
+    }
+    writeln!(writer)?;
+    write_history_markdown(&game.history, writer)
+}
+
+/// One table row per event: room, event type, value and HP before/after.
+///
+/// HP is replayed from a fresh player's starting health, mirroring the Game Over history.
+pub fn write_history_markdown(history: &[GameEvent], writer: &mut dyn Write) -> Result<()> {
+    writeln!(writer, "| Room | Event | Value | HP before | HP after |")?;
+    writeln!(writer, "|---:|---|---:|---:|---:|")?;
+    let mut room = 1;
+    let mut hp = Player::new().hp;
+    for ev in history {
+        let (event, value, after) = match ev {
+            GameEvent::RoomStart { number } => {
+                room = *number;
+                continue;
+            }
+            GameEvent::Potion { value, hp_before, hp_after } => {
+                hp = *hp_before;
+                ("Potion".to_string(), value.to_string(), *hp_after)
+            }
+            GameEvent::PotionDiscarded { value } => ("Potion discarded".to_string(), value.to_string(), hp),
+            GameEvent::PotionOverheal { value, capped_at } => (format!("Overheal above {}", capped_at), value.to_string(), hp),
+            GameEvent::Weapon { value } => ("Equip weapon".to_string(), value.to_string(), hp),
+            GameEvent::WeaponReplaced { old, new } => (format!("Replace weapon {}", old), new.to_string(), hp),
+            GameEvent::WeaponDiscarded { value } => ("Weapon discarded".to_string(), value.to_string(), hp),
+            GameEvent::Fight { monster, with_weapon, damage_taken } => {
+                let event = match with_weapon {
+                    Some(w) => format!("Fight with weapon {}", w),
+                    None => "Fight barehanded".to_string(),
+                };
+                (event, monster.to_string(), hp - *damage_taken as i32)
+            }
+            GameEvent::Avoid { cards } if cards.is_empty() => ("Avoid room".to_string(), "–".to_string(), hp),
+            GameEvent::Avoid { cards } => {
+                let cards: Vec<String> = cards.iter().map(Card::to_string).collect();
+                ("Avoid room".to_string(), cards.join(" "), hp)
+            }
+            GameEvent::Abandoned { .. } => ("Abandon run".to_string(), "–".to_string(), hp),
+            GameEvent::WeaponDestroyed { value, kills } => (format!("Weapon broke after {} kill{}", kills, if *kills == 1 { "" } else { "s" }), value.to_string(), hp),
+        };
+        writeln!(writer, "| {} | {} | {} | {} | {} |", room, event, value, hp, after)?;
+        hp = after;
+    }
+    Ok(())
+}
+
+/// Plain-text form of a saved run, grouped by room like the Game Over screen.
+pub fn write_run_text(run: &RunRecord, writer: &mut dyn Write) -> Result<()> {
+    let outcome = if run.won { "cleared the dungeon" } else { "fell" };
+    writeln!(writer, "{} — Score {} ({})", run.name, run.score, outcome)?;
+    writeln!(writer, "{} UTC · Seed {}", format_ts(run.ts), seed_codec::encode(run.seed))?;
+    for (room, events) in history_groups(&run.events) {
+        writeln!(writer)?;
+        writeln!(writer, "Room {}", room)?;
+        if events.is_empty() { writeln!(writer, "  (no actions)")?; }
+        for e in events { writeln!(writer, "  {}", e)?; }
+    }
+    Ok(())
+}
+
+/// One leaderboard CSV row. Column order is the field order; `duration_secs` is empty
+/// for runs saved before they were timed.
+#[derive(Debug, Serialize, Deserialize)]
+struct CsvRow {
+    rank: usize,
+    name: String,
+    score: i32,
+    won: bool,
+    timestamp_iso8601: String,
+    duration_secs: Option<u64>,
+    difficulty: String, // "standard" or the deck id, then "+flag" per active rule and "+endless"
+    cause: Option<String>,
+    rooms_completed: u32,
+    rooms_avoided: u32,
+    monsters_killed: u32,
+    damage_taken: u32,
+    potions_used: u32,
+    potions_wasted: u32,
+    weapons_equipped: u32,
+    seed: u64,
+    #[serde(default)]
+    healing_wasted: u32, // last so files written before it still import
+    #[serde(default)]
+    monster_value_avoided: u32,
+    #[serde(default)]
+    abandoned: bool,
+}
+
+// Marks Endless mode scores in the `difficulty` column
+const ENDLESS_TAG: &str = "endless";
+
+/// Writes `entries` as CSV with a header row; `rank` is the position in `entries`.
+pub fn export_leaderboard_csv(entries: &[ScoreEntry], writer: &mut dyn Write) -> Result<()> {
+    let mut csv = csv::Writer::from_writer(writer);
+    for (i, e) in entries.iter().enumerate() {
+        let endless = (e.mode == GameMode::Endless).then_some(ENDLESS_TAG);
+        let difficulty = std::iter::once(e.deck.as_deref().unwrap_or("standard"))
+            .chain(e.run_flags.iter().map(String::as_str))
+            .chain(endless)
+            .collect::<Vec<_>>()
+            .join("+");
+        let summary = &e.run_summary;
+        csv.serialize(CsvRow {
+            rank: i + 1,
+            name: e.name.clone(),
+            score: e.score,
+            won: e.won,
+            timestamp_iso8601: format_iso8601(e.ts),
+            duration_secs: e.duration_secs,
+            difficulty,
+            cause: e.cause.clone(),
+            rooms_completed: summary.rooms_completed,
+            rooms_avoided: summary.rooms_avoided,
+            monsters_killed: summary.monsters_killed,
+            damage_taken: summary.damage_taken,
+            potions_used: summary.potions_used,
+            potions_wasted: summary.potions_wasted,
+            weapons_equipped: summary.weapons_equipped,
+            seed: e.seed.unwrap_or(summary.seed),
+            healing_wasted: summary.healing_wasted,
+            monster_value_avoided: summary.monster_value_avoided,
+            abandoned: e.abandoned,
+        })?;
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+/// Reads a file written by `export_leaderboard_csv`; errors name the offending line.
+pub fn import_leaderboard_csv(reader: &mut dyn Read) -> Result<Vec<ScoreEntry>> {
+    let mut csv = csv::Reader::from_reader(reader);
+    let mut entries = Vec::new();
+    for (i, row) in csv.deserialize::<CsvRow>().enumerate() {
+        let line = i + 2; // after the header
+        let row = row.with_context(|| format!("line {}", line))?;
+        let ts = parse_iso8601(&row.timestamp_iso8601)
+            .with_context(|| format!("line {}: bad timestamp '{}'", line, row.timestamp_iso8601))?;
+        let mut parts = row.difficulty.split('+');
+        let deck = parts.next().filter(|d| !d.is_empty() && *d != "standard").map(str::to_string);
+        let mut mode = GameMode::Classic;
+        let mut run_flags = Vec::new();
+        for part in parts {
+            if part == ENDLESS_TAG { mode = GameMode::Endless; } else { run_flags.push(part.to_string()); }
+        }
+        entries.push(ScoreEntry {
+            name: row.name,
+            score: row.score,
+            won: row.won,
+            ts,
+            cause: row.cause,
+            run_summary: RunSummary {
+                rooms_completed: row.rooms_completed,
+                rooms_avoided: row.rooms_avoided,
+                monsters_killed: row.monsters_killed,
+                damage_taken: row.damage_taken,
+                potions_used: row.potions_used,
+                potions_wasted: row.potions_wasted,
+                weapons_equipped: row.weapons_equipped,
+                seed: row.seed,
+                healing_wasted: row.healing_wasted,
+                monster_value_avoided: row.monster_value_avoided,
+                difficulty: if deck.is_some() { Difficulty::Custom } else { Difficulty::Standard },
+            },
+            deck,
+            run_flags,
+            duration_secs: row.duration_secs,
+            mode,
+            schema_version: SCORE_ENTRY_VERSION,
+            abandoned: row.abandoned,
+            seed: Some(row.seed),
+        });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, score: i32, seed: u64) -> ScoreEntry {
+        ScoreEntry {
+            name: name.into(),
+            score,
+            won: score > 0,
+            ts: 1_700_000_000,
+            cause: (score <= 0).then(|| "K♠ in room 3".to_string()),
+            deck: None,
+            run_summary: RunSummary { rooms_completed: 7, monsters_killed: 9, damage_taken: 14, potions_used: 2, seed, ..RunSummary::default() },
+            run_flags: Vec::new(),
+            mode: GameMode::Classic,
+            schema_version: SCORE_ENTRY_VERSION,
+            duration_secs: Some(312),
+            abandoned: false,
+            seed: Some(seed),
+        }
+    }
+
+    fn round_trip(entries: &[ScoreEntry]) -> Vec<ScoreEntry> {
+        let mut out = Vec::new();
+        export_leaderboard_csv(entries, &mut out).expect("written to memory");
+        import_leaderboard_csv(&mut out.as_slice()).expect("reads back")
+    }
+
+    #[test]
+    fn export_then_import_gives_the_same_entries() {
+        let mut endless = entry("Bo, \"the brave\"", -20, 7);
+        endless.mode = GameMode::Endless;
+        endless.deck = Some("d3adb33f".into());
+        endless.run_summary.difficulty = Difficulty::Custom;
+        endless.run_flags = vec!["hardcore".into(), "peek".into()];
+        endless.duration_secs = None;
+        endless.abandoned = true;
+        let entries = vec![entry("Ann", 18, 42), endless];
+        let back = round_trip(&entries);
+        assert_eq!(serde_json::to_value(&back).expect("json"), serde_json::to_value(&entries).expect("json"));
+    }
+
+    #[test]
+    fn header_names_the_documented_columns() {
+        let mut out = Vec::new();
+        export_leaderboard_csv(&[entry("Ann", 18, 42)], &mut out).expect("written to memory");
+        let text = String::from_utf8(out).expect("utf-8");
+        let header = text.lines().next().expect("header row");
+        assert!(header.starts_with("rank,name,score,won,timestamp_iso8601,duration_secs,difficulty,"), "{header}");
+        assert!(text.lines().nth(1).expect("one row").starts_with("1,Ann,18,true,"));
+    }
+
+    #[test]
+    fn bad_timestamp_names_its_line() {
+        let mut out = Vec::new();
+        export_leaderboard_csv(&[entry("Ann", 18, 42), entry("Bo", 9, 1)], &mut out).expect("written to memory");
+        let text = String::from_utf8(out).expect("utf-8");
+        let iso = format_iso8601(1_700_000_000);
+        // Header, Ann, then Bo on line 3
+        let broken: Vec<String> = text.lines().enumerate().map(|(i, l)| if i == 2 { l.replace(&iso, "yesterday") } else { l.to_string() }).collect();
+        let broken = broken.join("\n");
+        let err = import_leaderboard_csv(&mut broken.as_bytes()).expect_err("bad timestamp");
+        assert_eq!(err.to_string(), "line 3: bad timestamp 'yesterday'");
+    }
+}
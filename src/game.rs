@@ -1,19 +1,45 @@
 use crate::cards::{Card, Suit, Rank};
+use ratatui::layout::{Position, Rect};
 use serde::{Deserialize, Serialize};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::collections::HashMap;
 use std::fs;
+use std::fmt::{self, Write as _};
+use std::io::Write;
 use std::path::Path;
-use crate::deck::Deck;
+use std::time::{Duration, Instant};
+use crate::config::{Config, HelpTab, LeaderboardSort};
+use crate::ghost::{self, GhostRun, GhostStore};
+use crate::leaderboard::Leaderboard;
+use crate::achievements::{Achievement, Achievements};
+#[cfg(any(test, feature = "testing"))]
+use crate::ai::GreedyAi;
+use crate::rating::{PlayerRating, Ratings};
+use crate::persist::{write_atomic, PersistHandle};
+use crate::rules::Rules;
+use crate::runs::{self, RunRecord};
+use crate::saves::{self, SavedRun, SlotState, SLOT_COUNT};
+use crate::seed_codec;
+use crate::deck::{Deck, DeckComposition, DeckError, DeckSpec};
+use crate::theme::Theme;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 pub enum GamePhase {
     Menu,
     NameEntry,
+    SeedEntry,
     Leaderboard,
+    RunHistory,
     Running,
     GameOver,
+    Changelog,
+    SaveSlots,
+    Shop, // between rooms with `Rules::enable_shop`
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Player {
     pub hp: i32,
     pub max_hp: i32,
@@ -26,18 +52,25 @@ impl Player {
     }
 }
 
-#[derive(Debug, Clone)]
+impl Default for Player {
+    fn default() -> Self { Self::new() }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeaponState {
     pub value: u8,                  // weapon power (2..=10)
     pub last_monster: Option<u8>,   // last monster value fought with this weapon
     pub stack: Vec<Card>,           // monsters stacked on this weapon (for UI)
+    #[serde(default)]
+    pub uses_remaining: Option<u8>, // fights left before it breaks; None without the durability rule
 }
 
 impl WeaponState {
     pub fn new(value: u8) -> Self {
-        Self { value, last_monster: None, stack: Vec::new() }
+        Self { value, last_monster: None, stack: Vec::new(), uses_remaining: None }
     }
     pub fn can_use_on(&self, monster_value: u8) -> bool {
+        if self.uses_remaining == Some(0) { return false; }
         match self.last_monster {
             None => true,
             Some(prev) => monster_value <= prev,
@@ -58,23 +91,214 @@ pub struct Game {
     pub discard: Vec<Card>,
     pub log: Vec<String>,
     pub show_help: bool,
+    pub help_full: bool, // F1: the tabbed reference instead of the current screen's keys
+    pub help_tab: HelpTab, // page of the full help; remembered in the config
+    pub show_welcome: bool, // first-run overlay, until any key dismisses it
     pub score: Option<i32>,
     pub last_card_potion_value: Option<u8>,
     pub menu_selected: usize,
     pub name_input: String,
+    pub name_prefilled: bool, // name_input holds the last used name, selected so typing replaces it
+    pub recent_names: Vec<String>, // leaderboard names, most recent first; Up/Down on name entry
+    pub name_suggestion: Option<usize>, // index into recent_names while cycling
     pub player_name: String,
     pub history: Vec<GameEvent>,
-    pub leaderboard: Vec<ScoreEntry>,
+    pub leaderboard: Leaderboard,
+    pub leaderboard_warning: Option<String>, // set when the scores file was moved aside or is left alone
+    pub scores_read_only: bool, // the scores file is from a newer version; nothing is written over it
     pub new_rank_pos: Option<usize>,
+    pub new_seed_rank: Option<usize>, // the finished run's place among runs on its seed in the same bucket
+    pub best_delta: Option<BestDelta>, // finished run against the player's previous best in its bucket
     pub room_number: u32,
     pub game_over_scroll: u16,
+    pub help_scroll: u16,
+    pub death_cause: Option<DeathCause>,
+    pub outcome: Option<Outcome>, // set when a run reaches Game Over
+    pub run_time_secs: Option<u64>, // length of the finished run; the clock stops when it ends
+    pub last_resolved: Option<Card>,
+    pub theme: Theme,
+    pub retention: RetentionPolicy,
+    pub leaderboard_cursor: usize,
+    pub leaderboard_detail: bool,
+    pub leaderboard_filter: LeaderboardFilter,
+    pub leaderboard_sort_reversed: bool, // `r` on the leaderboard; not saved, unlike the sort itself
+    pub pending_confirm: Option<ConfirmAction>,
+    pub quit_requested: bool, // the app leaves its loop once this is set
+    pub deck_spec: Option<DeckSpec>,
+    custom_deck_id: Option<String>, // `DeckSpec::id` of `deck_spec`, taken when it was validated
+    pub config: Config,
+    pub seed: u64,
+    pub next_seed: Option<u64>, // custom seed for the next run; random when None
+    cli_seed: Option<u64>, // `--seed` for the first run, unless a seed is picked in-game first
+    pub seed_input: String,
+    pub seed_error: Option<String>,
+    pub last_layout: Option<RenderedLayout>,
+    pub ghosts: GhostStore,
+    pub ratings: Ratings,
+    pub rating_change: Option<PlayerRating>, // the finished run's effect on its player's rating; Classic only
+    pub ghost: Option<GhostRun>, // best previous run on the current seed
+    pub notice: Option<(String, Instant)>, // short-lived message and when it was posted
+    pub runs: Vec<RunRecord>, // past runs, newest first; loaded when the Run History screen opens
+    pub runs_cursor: usize,
+    pub run_open: bool,
+    pub run_scroll: u16,
+    pub stats: RunSummary, // counters for the run in progress
+    pub persist: PersistHandle,
+    pub rules: Rules,
+    pub achievements: Achievements,
+    pub cycled: Vec<Card>, // cards seen in an avoided room that are still in the deck, oldest first
+    pub room_summary: Vec<RoomResult>, // how each room of the run went, oldest first; the Equipped minimap
+    pub frame_counter: u64,
+    pub phase_transition_history: Vec<(GamePhase, u64)>, // phases entered and the frame they were entered on; debug builds only
+    pub deal_animation: Option<DealAnimation>,
+    pub game_mode: GameMode, // chosen on the name entry screen; applies from the next run
+    pub dungeons_cleared: u32, // endless mode: dungeons emptied this run
+    pub run_start_time: Option<Instant>, // set when a run is dealt; None before the first run
+    pub current_kill_streak: u32, // weapon kills in a row that took no damage
+    pub max_kill_streak_this_run: u32,
+    pub current_slot: Option<usize>, // save slot autosaved to; None for runs that aren't saved (scripts)
+    pub slots: Vec<SlotState>, // loaded when the Continue screen opens
+    pub slot_cursor: usize,
+    pub choosing_slot: bool, // the Continue screen is picking a slot to overwrite for a new run
+    pub shop_cursor: usize, // 0 trades the leftover card, 1 leaves
+    pub leaderboard_mode: GameMode, // board shown on the Leaderboard screen
+    pub card_phase: [AnimationPhase; 4], // face-down state of each room slot (hidden-cards rule)
+    pub feedback: Option<Feedback>,       // red flash after a rejected move, cleared by `tick`
+    pub animation: Option<AnimationState>, // flash after a weapon fight or a potion, cleared by `tick`
+    pub changelog_scroll: u16,
+    pub changelog_return: GamePhase, // screen that `V` was pressed on; Esc goes back there
+}
+
+/// Which side of a room card is showing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnimationPhase {
+    #[default]
+    FaceUp,
+    FaceDown,
+    Flipping { since: u64 }, // frame the flip started; face up after `FLIP_FRAMES`
+}
+
+/// A short flash after a move lands, advanced by `tick` and gone after `ACTION_FLASH_FRAMES`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationState {
+    /// A weapon fight: the slot the monster was taken from flashes.
+    WeaponAttack { frame: u8, slot: usize },
+    /// A potion healed: HP flashes green.
+    PotionHeal { frame: u8 },
+    /// The dungeon was cleared: confetti falls over the Game Over screen.
+    Confetti { frame: u8 },
+}
+
+impl AnimationState {
+    /// The next frame, or `None` once the flash has run its course.
+    fn advanced(self) -> Option<Self> {
+        let (next, frame, length) = match self {
+            AnimationState::WeaponAttack { frame, slot } => (AnimationState::WeaponAttack { frame: frame + 1, slot }, frame + 1, ACTION_FLASH_FRAMES),
+            AnimationState::PotionHeal { frame } => (AnimationState::PotionHeal { frame: frame + 1 }, frame + 1, ACTION_FLASH_FRAMES),
+            AnimationState::Confetti { frame } => (AnimationState::Confetti { frame: frame + 1 }, frame + 1, CONFETTI_FRAMES),
+        };
+        (frame < length).then_some(next)
+    }
+}
+
+/// How a finished run ended; the Game Over screen is laid out differently for each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Victory,
+    Death,
+}
+
+/// Classic ends when the deck runs out; Endless reshuffles and goes on until death.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GameMode {
+    #[default]
+    Classic,
+    Endless,
+}
+
+impl GameMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            GameMode::Classic => "Classic",
+            GameMode::Endless => "Endless",
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            GameMode::Classic => GameMode::Endless,
+            GameMode::Endless => GameMode::Classic,
+        }
+    }
+}
+
+/// Staggered reveal of freshly dealt room cards. Purely cosmetic: the cards are in
+/// `Game::room` from the start, and `draw_room` shows their backs until each slot's frame.
+#[derive(Debug, Clone)]
+pub struct DealAnimation {
+    pub slots_revealed: usize,
+    pub reveal_at_frame: [u64; 4], // frame from which each slot shows its card
+}
+
+/// Screen positions from the most recent frame, used for mouse hit-testing.
+#[derive(Debug, Clone, Default)]
+pub struct RenderedLayout {
+    pub card_rects: [Option<Rect>; 4],
+    pub grid: bool, // room drawn as 2x2 rather than one row
+}
+
+impl RenderedLayout {
+    /// Room slot under the given terminal cell, if any.
+    pub fn slot_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.card_rects
+            .iter()
+            .position(|r| r.is_some_and(|r| r.contains(Position { x: column, y: row })))
+    }
+}
+
+/// Everything a `Game` starts from that lives in the player's files.
+#[derive(Default)]
+struct Stored {
+    leaderboard: Leaderboard,
+    leaderboard_warning: Option<String>,
+    scores_read_only: bool,
+    config: Config,
+    ratings: Ratings,
+    ghosts: GhostStore,
+    achievements: Achievements,
 }
 
 impl Game {
+    /// A game on the menu with standard rules whose first run deals a random dungeon.
+    // No `Default`: this reads the player's scores, settings and ghosts from disk
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
+        Self::new_with_seed_and_rules(rand::random(), Rules::default())
+    }
+
+    /// A game on the menu playing by `rules`, whose first run deals the dungeon of `seed` (unless
+    /// the player picks another seed before starting it). Later runs draw fresh seeds.
+    pub fn new_with_seed_and_rules(seed: u64, rules: Rules) -> Self {
+        let (leaderboard, leaderboard_warning, scores_read_only) = Self::load_leaderboard(Path::new(Self::scores_path()));
+        let ratings = Ratings::load(&leaderboard);
+        let stored = Stored {
+            leaderboard,
+            leaderboard_warning,
+            scores_read_only,
+            config: Config::load(),
+            ratings,
+            ghosts: GhostStore::load(),
+            achievements: Achievements::load(),
+        };
+        Self::with_stored(seed, rules, stored)
+    }
+
+    // A game on the menu around what was loaded from the player's files
+    fn with_stored(seed: u64, rules: Rules, stored: Stored) -> Self {
+        let Stored { leaderboard, leaderboard_warning, scores_read_only, config, ratings, ghosts, achievements } = stored;
         let mut deck = Deck::scoundrel_deck();
-        deck.shuffle();
-        let leaderboard = Self::load_leaderboard();
+        deck.shuffle_with_seed(seed);
         Self {
             phase: GamePhase::Menu,
             player: Player::new(),
@@ -90,23 +314,98 @@ impl Game {
                 "Press 'n' for quick new run or use menu.".into(),
             ],
             show_help: false,
+            help_full: false,
+            help_tab: config.help_tab,
             score: None,
             last_card_potion_value: None,
             menu_selected: 0,
             name_input: String::new(),
-            player_name: String::from("Scoundrel"),
+            name_prefilled: false,
+            recent_names: Vec::new(),
+            name_suggestion: None,
+            player_name: config.last_name.clone().unwrap_or_else(|| String::from("Scoundrel")),
             history: Vec::new(),
             leaderboard,
+            leaderboard_warning,
+            scores_read_only,
             new_rank_pos: None,
+            new_seed_rank: None,
+            best_delta: None,
             room_number: 0,
             game_over_scroll: 0,
+            help_scroll: 0,
+            death_cause: None,
+            outcome: None,
+            run_time_secs: None,
+            last_resolved: None,
+            theme: Theme { background_pattern: config.background_pattern, colorblind: config.colorblind, ..Theme::default() },
+            retention: RetentionPolicy::default(),
+            leaderboard_cursor: 0,
+            leaderboard_detail: false,
+            leaderboard_filter: LeaderboardFilter::default(),
+            leaderboard_sort_reversed: false,
+            pending_confirm: None,
+            quit_requested: false,
+            deck_spec: None,
+            custom_deck_id: None,
+            show_welcome: !config.first_run_done,
+            config,
+            seed,
+            next_seed: None,
+            cli_seed: Some(seed),
+            seed_input: String::new(),
+            seed_error: None,
+            last_layout: None,
+            ghosts,
+            ratings,
+            rating_change: None,
+            ghost: None,
+            notice: None,
+            runs: Vec::new(),
+            runs_cursor: 0,
+            run_open: false,
+            run_scroll: 0,
+            stats: RunSummary::default(),
+            persist: PersistHandle::default(),
+            rules,
+            achievements,
+            cycled: Vec::new(),
+            room_summary: Vec::new(),
+            frame_counter: 0,
+            phase_transition_history: Vec::new(),
+            deal_animation: None,
+            game_mode: GameMode::Classic,
+            dungeons_cleared: 0,
+            run_start_time: None,
+            current_kill_streak: 0,
+            max_kill_streak_this_run: 0,
+            current_slot: None,
+            slots: Vec::new(),
+            slot_cursor: 0,
+            choosing_slot: false,
+            shop_cursor: 0,
+            leaderboard_mode: GameMode::Classic,
+            card_phase: [AnimationPhase::FaceUp; 4],
+            feedback: None,
+            animation: None,
+            changelog_scroll: 0,
+            changelog_return: GamePhase::Menu,
         }
     }
 
     pub fn new_run(&mut self) {
+        let cli_seed = self.cli_seed.take();
+        self.seed = self.next_seed.take().or(cli_seed).unwrap_or_else(rand::random);
+        let mut deck = self.fresh_deck();
+        deck.shuffle_with_seed(self.seed);
+        self.start_run(deck);
+    }
+
+    // Everything a run resets, with `deck` dealt as-is
+    fn start_run(&mut self, deck: Deck) {
         self.player = Player::new();
-        self.deck = Deck::scoundrel_deck();
-        self.deck.shuffle();
+        self.deck = deck;
+        self.ghost = self.ghosts.get(&self.ghost_key()).cloned();
         self.room = [None, None, None, None];
         self.selected = 0;
         self.choices_this_turn = 0;
@@ -114,9 +413,24 @@ impl Game {
         self.potion_used_this_turn = false;
         self.discard.clear();
         self.score = None;
+        self.best_delta = None;
+        self.rating_change = None;
         self.last_card_potion_value = None;
+        self.death_cause = None;
+        self.outcome = None;
+        self.run_time_secs = None;
+        self.last_resolved = None;
         self.history.clear();
-        self.phase = GamePhase::Running;
+        self.cycled.clear();
+        self.room_summary.clear();
+        self.dungeons_cleared = 0;
+        self.run_start_time = Some(Instant::now());
+        self.current_kill_streak = 0;
+        self.max_kill_streak_this_run = 0;
+        self.card_phase = [AnimationPhase::FaceUp; 4];
+        self.animation = None;
+        self.stats = RunSummary { seed: self.seed, difficulty: self.difficulty(), ..RunSummary::default() };
+        self.set_phase(GamePhase::Running);
         self.log.clear();
         self.log.push("A fresh dungeon awaits...".into());
         self.refill_room();
@@ -124,54 +438,773 @@ impl Game {
         self.history.push(GameEvent::RoomStart { number: self.room_number });
     }
 
-    pub fn toggle_help(&mut self) { self.show_help = !self.show_help; }
+    /// Play subsequent runs with a homebrew deck instead of the standard 44 cards.
+    pub fn set_deck_spec(&mut self, spec: DeckSpec) -> Result<(), DeckError> {
+        self.deck = Deck::from_spec(&spec)?;
+        self.deck.shuffle();
+        self.custom_deck_id = Some(spec.id()?);
+        self.deck_spec = Some(spec);
+        Ok(())
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        if self.deck_spec.is_some() { Difficulty::Custom } else { Difficulty::Standard }
+    }
+
+    /// Leaderboard key of the active deck; `None` for the standard deck.
+    pub fn deck_id(&self) -> Option<String> {
+        self.custom_deck_id.clone()
+    }
+
+    fn fresh_deck(&self) -> Deck {
+        // Specs are validated in `set_deck_spec`, so building again cannot fail
+        self.deck_spec.as_ref().and_then(|s| Deck::from_spec(s).ok()).unwrap_or_else(Deck::scoundrel_deck)
+    }
+
+    /// Leaderboard entries for `mode` with the active deck and rules, by score, paired with their index in `leaderboard`.
+    fn leaderboard_bucket(&self, mode: GameMode) -> Vec<(usize, &ScoreEntry)> {
+        let deck = self.deck_id();
+        let flags = self.rules.flags();
+        self.leaderboard
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.deck == deck && e.run_flags == flags && e.mode == mode)
+            .collect()
+    }
+
+    /// The Leaderboard screen's rows: the active bucket narrowed by `leaderboard_filter`
+    /// and ordered by the saved sort.
+    pub fn leaderboard_view(&self) -> Vec<(usize, &ScoreEntry)> {
+        let filter = &self.leaderboard_filter;
+        let needle = filter.name.to_lowercase();
+        let mut view: Vec<_> = self
+            .leaderboard_bucket(self.leaderboard_mode)
+            .into_iter()
+            .filter(|(_, e)| (!filter.wins_only || e.won) && e.name.to_lowercase().contains(&needle))
+            .filter(|(_, e)| filter.seed.is_none_or(|seed| e.seed == Some(seed)))
+            .collect();
+        if filter.fastest_wins {
+            view.retain(|(_, e)| e.won && e.duration_secs.is_some());
+            view.sort_by_key(|(_, e)| e.duration_secs);
+            return view;
+        }
+        // The bucket is in score order: the podium keeps the top three, the sort applies below it
+        let rest = view.split_off(view.len().min(3));
+        view.extend(self.sorted_below_podium(rest));
+        view
+    }
+
+    fn sorted_below_podium<'a>(&self, mut rows: Vec<(usize, &'a ScoreEntry)>) -> Vec<(usize, &'a ScoreEntry)> {
+        match self.config.leaderboard_sort {
+            LeaderboardSort::ScoreDesc => {}
+            LeaderboardSort::ScoreAsc => rows.reverse(),
+            LeaderboardSort::NameAsc => rows.sort_by_key(|(_, e)| e.name.to_lowercase()),
+            LeaderboardSort::DateDesc => rows.sort_by_key(|(_, e)| std::cmp::Reverse(e.ts)),
+            LeaderboardSort::WinRateDesc => {
+                // Per player over the whole board of this mode, not just the rows shown
+                let mut tally: HashMap<&str, (u32, u32)> = HashMap::new();
+                for (_, e) in self.leaderboard_bucket(self.leaderboard_mode) {
+                    let (wins, runs) = tally.entry(e.name.as_str()).or_default();
+                    *wins += e.won as u32;
+                    *runs += 1;
+                }
+                let rate = |name: &str| tally.get(name).map_or(0.0, |&(w, n)| w as f64 / n as f64);
+                rows.sort_by(|(_, a), (_, b)| rate(&b.name).total_cmp(&rate(&a.name)));
+            }
+        }
+        if self.leaderboard_sort_reversed { rows.reverse(); }
+        rows
+    }
+
+    /// 0-based position a run with `score` would take on the unfiltered leaderboard of the current mode.
+    pub fn leaderboard_rank_for_score(&self, score: i32) -> usize {
+        self.leaderboard_bucket(self.game_mode).partition_point(|(_, e)| e.score > score)
+    }
+
+    fn selected_leaderboard_index(&self) -> Option<usize> {
+        self.leaderboard_view().get(self.leaderboard_cursor).map(|(i, _)| *i)
+    }
+
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+        self.help_full = false;
+        self.help_scroll = 0;
+    }
+
+    /// Opens the full help, or goes back to the current screen's page when it is already showing.
+    pub fn toggle_full_help(&mut self) {
+        self.help_full = !(self.show_help && self.help_full);
+        self.show_help = true;
+        self.help_scroll = 0;
+    }
+
+    /// Left/Right in the full help: the next or previous page, saved so F1 reopens on it.
+    pub fn step_help_tab(&mut self, delta: i32) {
+        if !(self.show_help && self.help_full) { return; }
+        self.help_tab = self.help_tab.step(delta);
+        self.help_scroll = 0;
+        self.config.help_tab = self.help_tab;
+        self.config.save(&self.persist);
+    }
 
-    pub fn move_selection(&mut self, dx: i32, _dy: i32) {
+    pub fn move_selection(&mut self, dx: i32, dy: i32) {
         if self.phase != GamePhase::Running { return; }
-        // 1x4 layout: move horizontally only
-        let ns = (self.selected as i32 + dx).clamp(0, 3);
+        // Left/Right walk the occupied slots in reading order in either layout, wrapping at the ends
+        let mut ns = if dx == 0 { self.selected } else { find_next_occupied(self.selected, dx, &self.room) } as i32;
+        // Up/Down only mean something in the 2x2 grid: slots 0-1 on top, 2-3 below
+        if dy != 0 && self.room_is_grid() {
+            let row = (ns / 2 + dy).clamp(0, 1);
+            ns = row * 2 + ns % 2;
+        }
         self.selected = ns as usize;
     }
 
-    pub fn take_selected_default(&mut self) { self.take_selected(UseMode::Default); }
-    pub fn take_selected_barehand(&mut self) { self.take_selected(UseMode::Barehand); }
-    pub fn take_selected_weapon(&mut self) { self.take_selected(UseMode::Weapon); }
+    /// Whether the last frame drew the room as a 2x2 grid.
+    pub fn room_is_grid(&self) -> bool {
+        self.last_layout.as_ref().is_some_and(|l| l.grid)
+    }
 
-    pub fn avoid_room(&mut self) {
-        if self.phase != GamePhase::Running { return; }
-        if self.avoided_last_turn {
-            self.log.push("You cannot avoid two rooms in a row.".into());
-            return;
+    /// Single entry point for everything the player can do; the app only translates input into actions.
+    pub fn apply_action(&mut self, action: PlayerAction) -> Result<(), ActionError> {
+        // The dealt cards are already in the room and only the visuals lag, so any
+        // input finishes the deal at once and quick picks act on what they will show
+        self.deal_animation = None;
+        let before = self.run_state();
+        match action {
+            PlayerAction::TakeCard { slot, mode } => {
+                self.require_running()?;
+                if slot >= self.room.len() { return Err(ActionError::InvalidSlot(slot)); }
+                let Some(card) = self.room[slot] else { return Err(ActionError::SlotEmpty(slot)); };
+                self.selected = slot;
+                self.reveal(slot);
+                if card.suit == Suit::Hearts && self.potion_used_this_turn && self.config.confirm_potion_waste {
+                    self.pending_confirm = Some(ConfirmAction::WastePotion { slot, mode });
+                    return Ok(());
+                }
+                if self.config.confirm_lethal_fight && !self.rules.hardcore && self.fight_is_lethal(slot, mode) && self.has_alternative_to(slot) {
+                    self.pending_confirm = Some(ConfirmAction::LethalFight { slot, mode });
+                    return Ok(());
+                }
+                if self.config.confirm_weapon_swap && !self.rules.hardcore && self.weapon_trade(slot).is_some() {
+                    self.pending_confirm = Some(ConfirmAction::ReplaceWeapon { slot, mode });
+                    return Ok(());
+                }
+                self.take_selected(mode);
+            }
+            PlayerAction::AvoidRoom => self.avoid_room()?,
+            PlayerAction::AbandonRun => {
+                if !matches!(self.phase, GamePhase::Running | GamePhase::Shop) { return Err(ActionError::GameNotRunning); }
+                self.pending_confirm = Some(ConfirmAction::AbandonRun);
+            }
+            PlayerAction::SelectSlot(slot) => {
+                self.require_running()?;
+                if slot >= self.room.len() { return Err(ActionError::InvalidSlot(slot)); }
+                self.selected = slot;
+                self.reveal(slot);
+            }
+            PlayerAction::MoveSelection { dx, dy } => {
+                self.require_running()?;
+                self.move_selection(dx, dy);
+                self.reveal(self.selected);
+            }
+            PlayerAction::ToggleHelp => self.toggle_help(),
+            PlayerAction::ToggleFullHelp => self.toggle_full_help(),
+            PlayerAction::HelpTab(delta) => self.step_help_tab(delta),
+            PlayerAction::DismissWelcome => {
+                self.show_welcome = false;
+                if !self.config.first_run_done {
+                    self.config.first_run_done = true;
+                    self.config.save(&self.persist);
+                }
+            }
+            PlayerAction::MenuUp => { self.require_phase(GamePhase::Menu)?; self.select_menu_up(); }
+            PlayerAction::MenuDown => { self.require_phase(GamePhase::Menu)?; self.select_menu_down(); }
+            PlayerAction::MenuActivate => { self.require_phase(GamePhase::Menu)?; self.menu_activate(); }
+            PlayerAction::PlayWeeklySeed => {
+                self.require_phase(GamePhase::Menu)?;
+                self.set_phase(GamePhase::NameEntry);
+                self.begin_name_entry();
+                self.next_seed = Some(seed_of_the_week(now_ts()).1);
+            }
+            PlayerAction::NameInputChar(ch) => { self.require_phase(GamePhase::NameEntry)?; self.name_input_char(ch); }
+            PlayerAction::NameInputBackspace => { self.require_phase(GamePhase::NameEntry)?; self.name_input_backspace(); }
+            PlayerAction::NameInputSubmit => { self.require_phase(GamePhase::NameEntry)?; self.name_input_submit(); }
+            PlayerAction::ShopMove(delta) => {
+                self.require_phase(GamePhase::Shop)?;
+                self.shop_cursor = (self.shop_cursor as i32 + delta).clamp(0, 1) as usize;
+            }
+            PlayerAction::ShopConfirm => {
+                self.require_phase(GamePhase::Shop)?;
+                if self.shop_cursor == 0 { self.shop_trade()?; }
+                self.leave_shop();
+            }
+            PlayerAction::ShopLeave => { self.require_phase(GamePhase::Shop)?; self.leave_shop(); }
+            PlayerAction::SaveSlotUp => { self.require_phase(GamePhase::SaveSlots)?; self.move_slot_cursor(-1); }
+            PlayerAction::SaveSlotDown => { self.require_phase(GamePhase::SaveSlots)?; self.move_slot_cursor(1); }
+            PlayerAction::SaveSlotActivate => {
+                self.require_phase(GamePhase::SaveSlots)?;
+                let slot = self.slot_cursor;
+                if self.choosing_slot {
+                    self.pending_confirm = Some(ConfirmAction::OverwriteSlot(slot));
+                } else {
+                    self.resume_slot(slot)?;
+                }
+            }
+            PlayerAction::SaveSlotDelete => {
+                self.require_phase(GamePhase::SaveSlots)?;
+                if self.slots.get(self.slot_cursor).is_none_or(SlotState::is_empty) { return Err(ActionError::NothingSelected); }
+                self.pending_confirm = Some(ConfirmAction::DeleteSlot(self.slot_cursor));
+            }
+            PlayerAction::SeedInputChar(ch) => {
+                self.require_phase(GamePhase::SeedEntry)?;
+                if ch.is_ascii_alphanumeric() && self.seed_input.len() < 20 { self.seed_input.push(ch); }
+                self.seed_error = None;
+            }
+            PlayerAction::SeedInputBackspace => {
+                self.require_phase(GamePhase::SeedEntry)?;
+                self.seed_input.pop();
+                self.seed_error = None;
+            }
+            PlayerAction::SeedInputSubmit => {
+                self.require_phase(GamePhase::SeedEntry)?;
+                match seed_codec::decode(&self.seed_input) {
+                    Ok(seed) => {
+                        self.next_seed = Some(seed);
+                        self.set_phase(GamePhase::NameEntry);
+                        self.begin_name_entry();
+                    }
+                    Err(e) => {
+                        self.seed_error = Some(e.to_string());
+                        return Err(ActionError::InvalidSeed);
+                    }
+                }
+            }
+            PlayerAction::SeedInputCancel => { self.require_phase(GamePhase::SeedEntry)?; self.set_phase(GamePhase::Menu); }
+            PlayerAction::ScrollUp(n) => {
+                let scroll = self.active_scroll()?;
+                *scroll = scroll.saturating_sub(n);
+            }
+            PlayerAction::ScrollDown(n) => {
+                let scroll = self.active_scroll()?;
+                *scroll = scroll.saturating_add(n);
+            }
+            PlayerAction::NewRun => match self.phase {
+                // Starting over gives up the run in progress, so it asks like abandoning does
+                GamePhase::Running | GamePhase::Shop => self.pending_confirm = Some(ConfirmAction::AbandonRun),
+                GamePhase::NameEntry | GamePhase::SeedEntry => return Err(ActionError::WrongPhase(self.phase)),
+                _ => { self.set_phase(GamePhase::NameEntry); self.begin_name_entry(); self.next_seed = None; }
+            },
+            PlayerAction::NameInputToggleMode => {
+                self.require_phase(GamePhase::NameEntry)?;
+                self.game_mode = self.game_mode.toggled();
+            }
+            PlayerAction::NameInputToggleHardcore => {
+                self.require_phase(GamePhase::NameEntry)?;
+                self.rules.hardcore = !self.rules.hardcore;
+            }
+            PlayerAction::NameInputSuggest { forward } => {
+                self.require_phase(GamePhase::NameEntry)?;
+                self.cycle_name_suggestion(forward);
+            }
+            PlayerAction::LeaderboardSwitchMode => {
+                self.require_phase(GamePhase::Leaderboard)?;
+                self.leaderboard_mode = self.leaderboard_mode.toggled();
+                self.leaderboard_cursor = 0;
+                self.leaderboard_detail = false;
+            }
+            PlayerAction::ShowLeaderboard => {
+                self.set_phase(GamePhase::Leaderboard);
+                self.leaderboard_mode = self.game_mode;
+                self.leaderboard_cursor = 0;
+                self.leaderboard_detail = false;
+                self.leaderboard_filter.editing = false;
+            }
+            PlayerAction::ShowMenu => self.set_phase(GamePhase::Menu),
+            PlayerAction::ShowRunHistory => self.open_run_history(),
+            PlayerAction::ShowChangelog => {
+                if self.phase != GamePhase::Changelog { self.changelog_return = self.phase; }
+                self.set_phase(GamePhase::Changelog);
+                self.changelog_scroll = 0;
+            }
+            PlayerAction::CloseChangelog => {
+                self.require_phase(GamePhase::Changelog)?;
+                self.set_phase(self.changelog_return);
+            }
+            PlayerAction::RunHistoryUp => { self.require_phase(GamePhase::RunHistory)?; self.move_runs_cursor(-1); }
+            PlayerAction::RunHistoryDown => { self.require_phase(GamePhase::RunHistory)?; self.move_runs_cursor(1); }
+            PlayerAction::RunHistoryToggle => {
+                self.require_phase(GamePhase::RunHistory)?;
+                if self.runs.is_empty() { return Err(ActionError::NothingSelected); }
+                self.run_open = !self.run_open;
+                self.run_scroll = 0;
+            }
+            PlayerAction::LeaderboardUp => { self.require_phase(GamePhase::Leaderboard)?; self.move_leaderboard_cursor(-1); }
+            PlayerAction::LeaderboardDown => { self.require_phase(GamePhase::Leaderboard)?; self.move_leaderboard_cursor(1); }
+            PlayerAction::LeaderboardToggleDetail => {
+                self.require_phase(GamePhase::Leaderboard)?;
+                if self.selected_leaderboard_index().is_none() { return Err(ActionError::NothingSelected); }
+                self.leaderboard_detail = !self.leaderboard_detail;
+            }
+            PlayerAction::LeaderboardToggleWins => {
+                self.require_phase(GamePhase::Leaderboard)?;
+                self.leaderboard_filter.wins_only = !self.leaderboard_filter.wins_only;
+                self.leaderboard_cursor = 0;
+            }
+            PlayerAction::LeaderboardToggleFastest => {
+                self.require_phase(GamePhase::Leaderboard)?;
+                self.leaderboard_filter.fastest_wins = !self.leaderboard_filter.fastest_wins;
+                self.leaderboard_cursor = 0;
+            }
+            PlayerAction::LeaderboardToggleSeed => {
+                self.require_phase(GamePhase::Leaderboard)?;
+                self.leaderboard_filter.seed = match self.leaderboard_filter.seed {
+                    Some(_) => None,
+                    None => Some(self.leaderboard.latest_seed().ok_or(ActionError::NoSeededRuns)?),
+                };
+                self.leaderboard_cursor = 0;
+            }
+            PlayerAction::LeaderboardExport => {
+                self.require_phase(GamePhase::Leaderboard)?;
+                self.export_scores_in_app();
+            }
+            PlayerAction::LeaderboardCycleSort => {
+                self.require_phase(GamePhase::Leaderboard)?;
+                self.config.leaderboard_sort = self.config.leaderboard_sort.next();
+                self.leaderboard_sort_reversed = false;
+                self.leaderboard_cursor = 0;
+                self.config.save(&self.persist);
+            }
+            PlayerAction::LeaderboardReverseSort => {
+                self.require_phase(GamePhase::Leaderboard)?;
+                self.leaderboard_sort_reversed = !self.leaderboard_sort_reversed;
+                self.leaderboard_cursor = 0;
+            }
+            PlayerAction::LeaderboardFilterStart => {
+                self.require_phase(GamePhase::Leaderboard)?;
+                self.leaderboard_filter.editing = true;
+            }
+            PlayerAction::LeaderboardFilterChar(ch) => {
+                self.require_leaderboard_filter()?;
+                if (ch.is_ascii_graphic() || ch == ' ') && self.leaderboard_filter.name.len() < 20 {
+                    self.leaderboard_filter.name.push(ch);
+                    self.leaderboard_cursor = 0;
+                }
+            }
+            PlayerAction::LeaderboardFilterBackspace => {
+                self.require_leaderboard_filter()?;
+                self.leaderboard_filter.name.pop();
+                self.leaderboard_cursor = 0;
+            }
+            PlayerAction::LeaderboardFilterDone => {
+                self.require_leaderboard_filter()?;
+                self.leaderboard_filter.editing = false;
+            }
+            PlayerAction::LeaderboardFilterCancel => {
+                self.require_leaderboard_filter()?;
+                self.leaderboard_filter.editing = false;
+                self.leaderboard_filter.name.clear();
+                self.leaderboard_cursor = 0;
+            }
+            PlayerAction::LeaderboardDelete => {
+                self.require_phase(GamePhase::Leaderboard)?;
+                let Some(idx) = self.selected_leaderboard_index() else { return Err(ActionError::NothingSelected); };
+                self.pending_confirm = Some(ConfirmAction::DeleteScore(idx));
+            }
+            PlayerAction::ConfirmAccept => {
+                let Some(confirm) = self.pending_confirm.take() else { return Err(ActionError::NothingToConfirm); };
+                self.run_confirmed(confirm);
+            }
+            PlayerAction::ConfirmCancel => {
+                if self.pending_confirm.take().is_none() { return Err(ActionError::NothingToConfirm); }
+            }
+            // Asked again at the prompt, the answer is taken as yes
+            PlayerAction::Quit => {
+                if matches!(self.phase, GamePhase::Running | GamePhase::Shop) && self.pending_confirm != Some(ConfirmAction::Quit) {
+                    self.pending_confirm = Some(ConfirmAction::Quit);
+                } else {
+                    self.quit_requested = true;
+                }
+            }
         }
-        if self.visible_count() < 4 {
-            self.log.push("You may only avoid when 4 cards are visible.".into());
-            return;
+        // Cursor moves and peeks leave the run as it was and aren't worth a write; leaving
+        // the table for a menu saves too, so the clock is current when resumed
+        if (before.0 == GamePhase::Running || self.phase == GamePhase::Running) && self.run_state() != before {
+            self.autosave();
+        }
+        Ok(())
+    }
+
+    // What a save has to keep up with: the phase and every card still to be played
+    fn run_state(&self) -> (GamePhase, [Option<Card>; 4], usize) {
+        (self.phase, self.room, self.deck.len())
+    }
+
+    /// The card left over from the last room, the price of a shop trade.
+    pub fn shop_offer(&self) -> Option<Card> { self.room.iter().flatten().next().copied() }
+
+    // Discards the leftover card; the monsters among the next three cards lose 1
+    fn shop_trade(&mut self) -> Result<(), ActionError> {
+        let Some(slot) = self.room.iter().position(Option::is_some) else { return Err(ActionError::NothingToTrade); };
+        let Some(card) = self.room[slot].take() else { return Err(ActionError::NothingToTrade); };
+        self.discard.push(card);
+        let before = self.deck.top_n_cards(3);
+        self.deck.modify_top_n(3, |c| *c = c.weakened());
+        // Seen-ahead entries name the weakened cards as they now read; each entry is renamed at
+        // most once, so a K that became a Q is not taken for the Q that became a J
+        let mut renamed = vec![false; self.cycled.len()];
+        for (old, new) in before.into_iter().zip(self.deck.top_n_cards(3)) {
+            if old == new { continue; }
+            if let Some(i) = (0..self.cycled.len()).find(|&i| !renamed[i] && self.cycled[i] == old) {
+                self.cycled[i] = new;
+                renamed[i] = true;
+            }
+        }
+        self.log.push(format!("You trade the {} at the shop; the next monsters weaken.", card));
+        Ok(())
+    }
+
+    fn leave_shop(&mut self) {
+        self.set_phase(GamePhase::Running);
+        self.deal_next_room();
+    }
+
+    fn open_save_slots(&mut self, choosing: bool) {
+        self.slots = saves::load_all();
+        self.slot_cursor = 0;
+        self.choosing_slot = choosing;
+        self.set_phase(GamePhase::SaveSlots);
+    }
+
+    fn move_slot_cursor(&mut self, delta: i32) {
+        self.slot_cursor = (self.slot_cursor as i32 + delta).rem_euclid(SLOT_COUNT as i32) as usize;
+    }
+
+    /// Starts a run from name entry in the first free save slot, or asks which slot to
+    /// overwrite when all of them hold a run.
+    fn start_run_in_free_slot(&mut self) {
+        let slots = saves::load_all();
+        match slots.iter().position(SlotState::is_empty) {
+            Some(free) => {
+                self.current_slot = Some(free);
+                self.new_run();
+                self.autosave();
+            }
+            None => self.open_save_slots(true),
+        }
+    }
+
+    fn resume_slot(&mut self, slot: usize) -> Result<(), ActionError> {
+        let run = match self.slots.get(slot) {
+            Some(SlotState::Saved(run)) => run.as_ref().clone(),
+            Some(SlotState::Corrupt) => {
+                self.notify("This save can't be read; Delete clears it.");
+                return Err(ActionError::SaveSlotUnavailable);
+            }
+            _ => return Err(ActionError::NothingSelected),
+        };
+        // The remaining cards are saved, but the leaderboard bucket follows the deck in use
+        if run.deck_id != self.deck_id() {
+            let deck = run.deck_id.as_deref().unwrap_or("standard");
+            self.notify(format!("This run was dealt from the {} deck; start the game with that deck to continue it.", deck));
+            return Err(ActionError::SaveSlotUnavailable);
+        }
+        self.restore(run);
+        self.current_slot = Some(slot);
+        Ok(())
+    }
+
+    fn snapshot(&self) -> SavedRun {
+        SavedRun {
+            ts: now_ts(),
+            player_name: self.player_name.clone(),
+            seed: self.seed,
+            game_mode: self.game_mode,
+            rules: self.rules.clone(),
+            deck_id: self.deck_id(),
+            player: self.player.clone(),
+            deck: self.deck.iter().copied().collect(),
+            room: self.room,
+            face_down: self.card_phase.map(|p| p == AnimationPhase::FaceDown),
+            selected: self.selected,
+            choices_this_turn: self.choices_this_turn,
+            avoided_last_turn: self.avoided_last_turn,
+            potion_used_this_turn: self.potion_used_this_turn,
+            discard: self.discard.clone(),
+            last_card_potion_value: self.last_card_potion_value,
+            history: self.history.clone(),
+            cycled: self.cycled.clone(),
+            room_summary: self.room_summary.clone(),
+            room_number: self.room_number,
+            dungeons_cleared: self.dungeons_cleared,
+            stats: self.stats.clone(),
+            current_kill_streak: self.current_kill_streak,
+            max_kill_streak: self.max_kill_streak_this_run,
+            elapsed_secs: self.run_elapsed_secs(),
+            log: self.log.clone(),
+        }
+    }
+
+    // The resumed run plays by the rules and mode it was started with
+    fn restore(&mut self, run: SavedRun) {
+        self.player_name = run.player_name;
+        self.seed = run.seed;
+        self.next_seed = None;
+        self.game_mode = run.game_mode;
+        self.rules = run.rules;
+        self.player = run.player;
+        self.deck = Deck::from_cards(run.deck);
+        self.room = run.room;
+        self.card_phase = run.face_down.map(|down| if down { AnimationPhase::FaceDown } else { AnimationPhase::FaceUp });
+        self.selected = run.selected;
+        self.choices_this_turn = run.choices_this_turn;
+        self.avoided_last_turn = run.avoided_last_turn;
+        self.potion_used_this_turn = run.potion_used_this_turn;
+        self.discard = run.discard;
+        self.last_card_potion_value = run.last_card_potion_value;
+        self.history = run.history;
+        self.cycled = run.cycled;
+        self.room_summary = run.room_summary;
+        self.room_number = run.room_number;
+        self.dungeons_cleared = run.dungeons_cleared;
+        self.stats = run.stats;
+        self.current_kill_streak = run.current_kill_streak;
+        self.max_kill_streak_this_run = run.max_kill_streak;
+        self.run_start_time = Instant::now().checked_sub(Duration::from_secs(run.elapsed_secs));
+        self.log = run.log;
+        self.ghost = self.ghosts.get(&self.ghost_key()).cloned();
+        self.score = None;
+        self.best_delta = None;
+        self.death_cause = None;
+        self.last_resolved = None;
+        self.new_rank_pos = None;
+        self.new_seed_rank = None;
+        self.deal_animation = None;
+        self.set_phase(GamePhase::Running);
+        // Saved while shopping: the room holds only the leftover card and the next one isn't dealt yet
+        if self.rules.enable_shop && self.visible_count() <= 1 && !self.deck.is_empty() {
+            self.shop_cursor = 0;
+            self.set_phase(GamePhase::Shop);
+        }
+    }
+
+    /// Writes the run to its save slot, if it has one; done after every move that changes it.
+    pub fn autosave(&self) {
+        let Some(slot) = self.current_slot else { return; };
+        let run = self.snapshot();
+        self.persist.run("save slot", move || saves::save(slot, &run));
+    }
+
+    // A finished run no longer needs its slot
+    fn release_slot(&mut self) {
+        if let Some(slot) = self.current_slot.take() {
+            self.persist.run("save slot", move || saves::delete(slot));
+        }
+    }
+
+    /// Whether an overlay (help, confirmation, detail view) should swallow Esc instead of quitting.
+    pub fn overlay_open(&self) -> bool {
+        self.show_welcome || self.show_help || self.pending_confirm.is_some() || self.leaderboard_detail || self.run_open || self.leaderboard_filter.editing
+    }
+
+    fn open_run_history(&mut self) {
+        self.runs = runs::load_all();
+        self.runs_cursor = 0;
+        self.run_open = false;
+        self.set_phase(GamePhase::RunHistory);
+    }
+
+    fn move_runs_cursor(&mut self, delta: i32) {
+        if self.runs.is_empty() { return; }
+        self.runs_cursor = (self.runs_cursor as i32 + delta).rem_euclid(self.runs.len() as i32) as usize;
+    }
+
+    fn run_confirmed(&mut self, confirm: ConfirmAction) {
+        match confirm {
+            ConfirmAction::WastePotion { slot, mode }
+            | ConfirmAction::LethalFight { slot, mode }
+            | ConfirmAction::ReplaceWeapon { slot, mode } => {
+                if self.phase != GamePhase::Running || self.room[slot].is_none() { return; }
+                self.selected = slot;
+                self.take_selected(mode);
+            }
+            ConfirmAction::AbandonRun => {
+                if matches!(self.phase, GamePhase::Running | GamePhase::Shop) { self.abandon_run(); }
+            }
+            ConfirmAction::Quit => self.quit_requested = true,
+            // Queued behind any autosave of the same slot, so that can't bring the file back;
+            // a failure reaches the player as a notice like every other write
+            ConfirmAction::DeleteSlot(slot) => {
+                if self.current_slot == Some(slot) { self.current_slot = None; }
+                self.persist.run("save slot", move || saves::delete(slot));
+                if let Some(state) = self.slots.get_mut(slot) { *state = SlotState::Empty; }
+            }
+            ConfirmAction::OverwriteSlot(slot) => {
+                self.choosing_slot = false;
+                self.current_slot = Some(slot);
+                self.new_run();
+                self.autosave();
+            }
+            ConfirmAction::DeleteScore(idx) => {
+                // The row came from the board on screen, which Tab may have switched away from the run's mode
+                if !self.leaderboard_bucket(self.leaderboard_mode).iter().any(|(i, _)| *i == idx) { return; }
+                // The last run's ranks are positions in the unfiltered, score-ordered bucket of its own mode
+                let bucket = self.leaderboard_bucket(self.game_mode);
+                let view_pos = bucket.iter().position(|(i, _)| *i == idx);
+                let seed_pos = bucket.iter().filter(|(_, e)| e.seed == Some(self.seed)).position(|(i, _)| *i == idx);
+                self.leaderboard.remove(idx);
+                self.new_rank_pos = rank_after_removal(self.new_rank_pos, view_pos);
+                self.new_seed_rank = rank_after_removal(self.new_seed_rank, seed_pos);
+                self.leaderboard_detail = false;
+                self.move_leaderboard_cursor(0);
+                self.save_leaderboard();
+            }
+        }
+    }
+
+    // The cursor walks the entries shown on screen (podium + list) and wraps at both ends
+    fn move_leaderboard_cursor(&mut self, delta: i32) {
+        let shown = self.leaderboard_view().len().min(LEADERBOARD_SHOWN) as i32;
+        if shown == 0 { self.leaderboard_cursor = 0; return; }
+        let cur = (self.leaderboard_cursor as i32).min(shown - 1);
+        self.leaderboard_cursor = (cur + delta).rem_euclid(shown) as usize;
+    }
+
+    fn require_running(&self) -> Result<(), ActionError> {
+        if self.phase != GamePhase::Running { return Err(ActionError::GameNotRunning); }
+        Ok(())
+    }
+
+    fn require_leaderboard_filter(&self) -> Result<(), ActionError> {
+        self.require_phase(GamePhase::Leaderboard)?;
+        if !self.leaderboard_filter.editing { return Err(ActionError::NotTyping); }
+        Ok(())
+    }
+
+    fn require_phase(&self, phase: GamePhase) -> Result<(), ActionError> {
+        if self.phase != phase { return Err(ActionError::WrongPhase(self.phase)); }
+        Ok(())
+    }
+
+    // The help popup scrolls when open, otherwise the history on screen does
+    fn active_scroll(&mut self) -> Result<&mut u16, ActionError> {
+        if self.show_help { return Ok(&mut self.help_scroll); }
+        match self.phase {
+            GamePhase::GameOver => Ok(&mut self.game_over_scroll),
+            GamePhase::RunHistory if self.run_open => Ok(&mut self.run_scroll),
+            GamePhase::Changelog => Ok(&mut self.changelog_scroll),
+            phase => Err(ActionError::WrongPhase(phase)),
+        }
+    }
+
+    /// Whether the current room may be avoided, and if not why; `avoid_room` and the status bar both use it.
+    pub fn avoid_status(&self) -> AvoidStatus {
+        if self.rules.hardcore {
+            AvoidStatus::Hardcore
+        } else if self.avoided_last_turn {
+            AvoidStatus::UsedLastRoom
+        } else if self.visible_count() < 4 {
+            AvoidStatus::NeedFourCards
+        } else {
+            AvoidStatus::Ready
+        }
+    }
+
+    fn avoid_room(&mut self) -> Result<(), ActionError> {
+        self.require_running()?;
+        match self.avoid_status() {
+            AvoidStatus::Ready => {}
+            AvoidStatus::Hardcore => {
+                self.log.push("Not in hardcore.".into());
+                return Err(ActionError::AvoidNotAllowed);
+            }
+            AvoidStatus::UsedLastRoom => {
+                self.log.push("You cannot avoid two rooms in a row.".into());
+                return Err(ActionError::AvoidNotAllowed);
+            }
+            AvoidStatus::NeedFourCards => {
+                self.log.push("You may only avoid when 4 cards are visible.".into());
+                return Err(ActionError::AvoidNotAllowed);
+            }
         }
+        let avoided: Vec<Card> = self.room.iter().flatten().copied().collect();
+        self.stats.monster_value_avoided += avoided.iter().filter(|c| c.is_monster()).map(|c| c.monster_value() as u32).sum::<u32>();
         // Scoop all four to bottom in visible order (top-left, top-right, bottom-left, bottom-right)
         for i in 0..4 {
             if let Some(card) = self.room[i].take() {
                 self.deck.push_bottom(card);
+                // Only cards the player has actually seen count as seen ahead
+                if self.card_phase[i] != AnimationPhase::FaceDown { self.cycled.push(card); }
+                self.card_phase[i] = AnimationPhase::FaceUp;
             }
         }
+        if self.rules.shuffle_avoided_cards {
+            // Seeded from the run, so replaying a seed with the same moves deals the same cards
+            let mut rng = StdRng::seed_from_u64(self.seed.wrapping_add(self.history.len() as u64));
+            self.deck.shuffle_partial(0, avoided.len(), &mut rng);
+        }
         self.avoided_last_turn = true;
         self.potion_used_this_turn = false;
         self.choices_this_turn = 0;
         self.log.push("You avoid the room, slipping past the dangers.".into());
-        self.history.push(GameEvent::Avoid);
+        self.history.push(GameEvent::Avoid { cards: avoided });
+        self.stats.rooms_avoided += 1;
+        self.room_summary.push(RoomResult::Avoided);
         self.refill_room();
         if matches!(self.phase, GamePhase::Running) {
             self.room_number += 1;
             self.history.push(GameEvent::RoomStart { number: self.room_number });
         }
+        Ok(())
+    }
+
+    /// Slot and value of the strongest monster in the room, if any.
+    pub fn highest_threat_in_room(&self) -> Option<(usize, u8)> {
+        self.room
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| c.filter(|c| c.is_monster()).map(|c| (i, c.monster_value())))
+            // Ties go to the leftmost slot
+            .max_by_key(|&(i, v)| (v, std::cmp::Reverse(i)))
+    }
+
+    /// Slot and value of the weakest card that can be taken without losing HP: potions,
+    /// weapons, and monsters the equipped weapon can fight and fully absorb.
+    pub fn lowest_safe_card_in_room(&self) -> Option<(usize, u8)> {
+        let weapon = self.player.weapon.as_ref();
+        self.room
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| c.map(|c| (i, c)))
+            .filter(|(_, c)| {
+                !c.is_monster() || weapon.is_some_and(|w| w.can_use_on(c.monster_value()) && c.monster_value() <= w.value)
+            })
+            .map(|(i, c)| (i, c.monster_value()))
+            .min_by_key(|&(i, v)| (v, i))
     }
 
     fn visible_count(&self) -> usize { self.room.iter().filter(|c| c.is_some()).count() }
 
     fn refill_room(&mut self) {
         // Leave any remaining room card(s) in place; draw until there are 4 or deck empty
-        for slot in 0..4 {
-            if self.room[slot].is_none() && let Some(c) = self.deck.draw() {
-                self.room[slot] = Some(c);
+        let step = self.config.deal_animation_ms.div_ceil(FRAME_MS);
+        let mut reveal_at_frame = [self.frame_counter; 4];
+        let mut dealt = 0;
+        let mut fresh = Vec::new();
+        for (i, (slot, reveal_at)) in self.room.iter_mut().zip(reveal_at_frame.iter_mut()).enumerate() {
+            if slot.is_none() && let Some(c) = self.deck.draw() {
+                *slot = Some(c);
+                fresh.push(i);
+                self.card_phase[i] = AnimationPhase::FaceUp;
+                dealt += 1;
+                *reveal_at = self.frame_counter + dealt * step;
+                // A shop can weaken a card into a copy of another, so equal cards are told apart by
+                // count: seen ahead are at most as many as the deck still holds
+                let in_deck = self.deck.iter().filter(|d| **d == c).count();
+                if self.cycled.iter().filter(|s| **s == c).count() > in_deck
+                    && let Some(pos) = self.cycled.iter().position(|s| *s == c)
+                {
+                    self.cycled.remove(pos);
+                }
             }
         }
         // Reset selection to first non-empty
@@ -180,16 +1213,90 @@ impl Game {
         {
             self.selected = idx;
         }
+        if self.config.auto_focus_threat && !self.rules.hardcore
+            && let Some((idx, _)) = self.highest_threat_in_room().or_else(|| self.lowest_safe_card_in_room())
+        {
+            self.selected = idx;
+        }
+        self.hide_fresh_cards(fresh);
+        if step > 0 && dealt > 0 && !self.theme.plain {
+            self.deal_animation = Some(DealAnimation { slots_revealed: 4 - dealt as usize, reveal_at_frame });
+        }
         if self.deck.is_empty() && self.visible_count() == 0 {
             self.finish_victory();
         }
     }
 
+    // Hidden-cards rule: turn some newly dealt cards face down, never the one already selected.
+    // Seeded from the run so replays hide the same cards.
+    fn hide_fresh_cards(&mut self, mut fresh: Vec<usize>) {
+        let n = self.rules.hidden_cards as usize;
+        if n == 0 { return; }
+        fresh.retain(|&i| i != self.selected);
+        let mut rng = StdRng::seed_from_u64(self.seed.wrapping_add(self.history.len() as u64));
+        fresh.shuffle(&mut rng);
+        for &i in fresh.iter().take(n) { self.card_phase[i] = AnimationPhase::FaceDown; }
+    }
+
+    // Selecting a face-down card turns it over
+    fn reveal(&mut self, slot: usize) {
+        if self.card_phase[slot] == AnimationPhase::FaceDown {
+            self.card_phase[slot] = if self.theme.plain { AnimationPhase::FaceUp } else { AnimationPhase::Flipping { since: self.frame_counter } };
+        }
+    }
+
+    /// Totals over the room cards the player can see, recomputed as cards are taken.
+    pub fn room_summary(&self) -> RoomSummary {
+        let mut summary = RoomSummary::default();
+        for (slot, card) in self.room.iter().enumerate() {
+            let Some(card) = card else { continue; };
+            if self.card_phase[slot] == AnimationPhase::FaceDown || !self.slot_revealed(slot) {
+                summary.hidden += 1;
+            } else if card.is_monster() {
+                summary.monsters += 1;
+                summary.monster_value += card.monster_value() as u32;
+            } else if card.suit == Suit::Diamonds {
+                summary.weapons += 1;
+                summary.best_weapon = summary.best_weapon.max(Some(card.monster_value()));
+            } else {
+                summary.potions += 1;
+            }
+        }
+        summary
+    }
+
+    /// Whether room slot `slot` has finished being dealt.
+    pub fn slot_revealed(&self, slot: usize) -> bool {
+        self.deal_animation.as_ref().is_none_or(|a| self.frame_counter >= a.reveal_at_frame[slot])
+    }
+
     fn end_turn(&mut self) {
+        self.stats.rooms_completed += 1;
+        self.room_summary.push(self.current_room_result());
         // Keep one remaining card (if any) on table; refill to 4 for next turn
         self.avoided_last_turn = false;
         self.potion_used_this_turn = false;
         self.choices_this_turn = 0;
+        if self.rules.enable_shop && self.stats.rooms_completed.is_multiple_of(3) && !self.deck.is_empty() {
+            self.shop_cursor = 0;
+            self.set_phase(GamePhase::Shop);
+            return;
+        }
+        self.deal_next_room();
+    }
+
+    // Damaged when any fight since the room was dealt cost HP
+    fn current_room_result(&self) -> RoomResult {
+        let hurt = self
+            .history
+            .iter()
+            .rev()
+            .take_while(|ev| !matches!(ev, GameEvent::RoomStart { .. }))
+            .any(|ev| matches!(ev, GameEvent::Fight { damage_taken, .. } if *damage_taken > 0));
+        if hurt { RoomResult::Damaged } else { RoomResult::Clean }
+    }
+
+    fn deal_next_room(&mut self) {
         self.refill_room();
         if matches!(self.phase, GamePhase::Running) {
             self.room_number += 1;
@@ -198,89 +1305,284 @@ impl Game {
     }
 
     fn finish_victory(&mut self) {
-        self.phase = GamePhase::GameOver;
-        let mut score = self.player.hp;
-        if self.player.hp == self.player.max_hp
-            && let Some(v) = self.last_card_potion_value
-        { score += v as i32; }
+        if self.game_mode == GameMode::Endless && self.next_dungeon() { return; }
+        self.set_phase(GamePhase::GameOver);
+        self.release_slot();
+        let score = self.projected_score().0;
         self.score = Some(score);
         self.log.push(format!("You clear the dungeon. Final score: {}.", score));
-        self.push_score_and_rank(true);
+        self.outcome = Some(Outcome::Victory);
+        self.start_animation(AnimationState::Confetti { frame: 0 });
+        let earned = [
+            (self.rules.no_weapons, Achievement::NoWeaponWin),
+            (self.rules.no_potions, Achievement::NoPotionWin),
+        ];
+        for (active, achievement) in earned {
+            if active && self.achievements.unlock(achievement, &self.persist) {
+                let text = format!("Achievement unlocked: {}", achievement.title());
+                self.notify(text.clone());
+                self.log.push(text);
+            }
+        }
+        self.push_score_and_rank(true, false);
         self.game_over_scroll = 0;
     }
 
-    fn finish_death(&mut self) {
-        self.phase = GamePhase::GameOver;
-        // Sum remaining monsters in deck and room
-        let mut penalty = 0i32;
-        for c in &self.deck.cards { if c.is_monster() { penalty += c.monster_value() as i32; } }
-        for card in self.room.iter().flatten() {
-            if card.is_monster() { penalty += card.monster_value() as i32; }
-        }
-        let score = self.player.hp - penalty; // hp is <= 0
-        self.score = Some(score);
-        self.log.push(format!("You fall... Final score: {}.", score));
-        self.push_score_and_rank(false);
-        self.game_over_scroll = 0;
+    // Endless mode: the discard pile and the weapon's slain stack become the next dungeon.
+    // The equipped weapon (with its last-kill limit) carries over; false if nothing is left to deal.
+    fn next_dungeon(&mut self) -> bool {
+        let mut cards = std::mem::take(&mut self.discard);
+        if let Some(w) = self.player.weapon.as_mut() { cards.append(&mut w.stack); }
+        let mut deck = Deck { cards };
+        // Suits a rule takes out of play stay on the discard pile instead of being dealt again
+        if self.rules.no_weapons { self.discard.extend(deck.remove_all_of_suit(Suit::Diamonds)); }
+        if self.rules.no_potions { self.discard.extend(deck.remove_all_of_suit(Suit::Hearts)); }
+        if deck.is_empty() { return false; }
+        self.dungeons_cleared += 1;
+        // Seeded runs stay reproducible past the first dungeon
+        deck.shuffle_with_seed(self.seed.wrapping_add(self.dungeons_cleared as u64));
+        self.deck = deck;
+        self.cycled.clear();
+        let text = format!("Dungeon {} cleared (+{}). The cards reshuffle...", self.dungeons_cleared, ENDLESS_DUNGEON_BONUS);
+        self.notify(text.clone());
+        self.log.push(text);
+        self.refill_room();
+        true
     }
 
-    fn take_selected(&mut self, mode: UseMode) {
-        if self.phase != GamePhase::Running { return; }
-        if self.choices_this_turn >= 3 && self.visible_count() >= 2 {
-            self.log.push("You've already taken 3 cards. Ending turn.".into());
-            self.end_turn();
-            return;
-        }
-        let idx = self.selected;
-        let Some(card) = self.room[idx].take() else { return; };
-        // Determine how many picks allowed this turn based on initial room size; default = 3, but when fewer cards visible, allow all but one
-        self.resolve_card(card, mode);
+    /// Endless mode score: monsters slain plus a bonus per dungeon cleared.
+    pub fn endless_score(&self) -> i32 {
+        self.stats.monsters_killed as i32 + self.dungeons_cleared as i32 * ENDLESS_DUNGEON_BONUS
+    }
 
-        if self.player.hp <= 0 { self.finish_death(); return; }
+    // Sum of the monsters still in the deck and room
+    fn remaining_monster_penalty(&self) -> i32 {
+        self.deck.iter().chain(self.room.iter().flatten()).filter(|c| c.is_monster()).map(|c| c.point_value()).sum()
+    }
 
-        // Count pick and decide if turn ends: leave exactly one card if possible
-        self.choices_this_turn += 1;
-        if self.visible_count() <= 1 {
-            // if only one remains (or none), end turn and refill
-            self.end_turn();
-        } else if self.choices_this_turn >= 3 {
-            self.end_turn();
-        }
+    /// "9♦ (binds ≤5, 4 kills) with 3♦" when taking the weapon in `slot` would replace the equipped one.
+    pub fn weapon_trade(&self, slot: usize) -> Option<String> {
+        let card = (*self.room.get(slot)?)?;
+        let old = self.player.weapon.as_ref()?;
+        if card.suit != Suit::Diamonds || self.rules.no_weapons { return None; }
+        let binds = old.last_monster.map_or("any".to_string(), |v| format!("≤{}", v));
+        let old_card = Card::new(Suit::Diamonds, Rank::new(old.value));
+        let kills = match old.stack.len() { 1 => "1 kill".to_string(), n => format!("{} kills", n) };
+        Some(format!("{} (binds {}, {}) with {}", old_card, binds, kills, card))
     }
 
-    fn resolve_card(&mut self, card: Card, mode: UseMode) {
-        match card.suit {
-            Suit::Hearts => {
-                if !self.potion_used_this_turn {
-                    let val = card.monster_value(); // 2..10
-                    let heal = val as i32;
+    /// Cards still to take from this room before the turn ends on its own.
+    pub fn choices_remaining_this_turn(&self) -> u8 {
+        3u8.saturating_sub(self.choices_this_turn)
+    }
+
+    /// Cards the next room will be dealt if this one is played out (not avoided), top of the deck first.
+    pub fn next_room_preview(&self) -> Vec<Card> {
+        let occupied = self.room.iter().flatten().count();
+        let staying = occupied.saturating_sub(self.choices_remaining_this_turn() as usize);
+        self.deck.top_n_cards(4 - staying)
+    }
+
+    /// Wall-clock seconds since the current run was dealt; 0 before the first run.
+    pub fn run_elapsed_secs(&self) -> u64 {
+        self.run_start_time.map(|t| t.elapsed().as_secs()).unwrap_or(0)
+    }
+
+    /// Rooms fully completed before the current one.
+    pub fn rooms_survived(&self) -> u32 { self.room_number.saturating_sub(1) }
+
+    /// The Classic victory score if the dungeon were cleared now, by component.
+    pub fn victory_breakdown(&self) -> VictoryScore {
+        VictoryScore {
+            streak_bonus: self.streak_bonus(),
+            ..VictoryScore::new(
+                self.player.hp,
+                self.player.max_hp,
+                self.last_card_potion_value,
+                self.rooms_survived(),
+                self.rules.rooms_bonus_per_room,
+            )
+        }
+    }
+
+    /// Cards resolved so far out of every card in play, as `(resolved, total)`. The discard pile,
+    /// the equipped weapon and the monsters stacked on it count as resolved; avoided cards went
+    /// back under the deck, so they are still ahead of the player.
+    pub fn progress(&self) -> (usize, usize) {
+        let resolved = self.cards_seen_this_run();
+        let in_room = self.room.iter().flatten().count();
+        (resolved, resolved + in_room + self.deck.len())
+    }
+
+    /// Cards taken from rooms so far: the discard pile plus the equipped weapon and its stack.
+    pub fn cards_seen_this_run(&self) -> usize {
+        self.cards_out_of_play() + self.player.weapon.is_some() as usize
+    }
+
+    /// Cards that will not come back this dungeon: the discard pile plus the monsters slain with
+    /// the equipped weapon, which stay stacked on it instead of being discarded.
+    pub fn cards_out_of_play(&self) -> usize {
+        self.discard.len() + self.player.weapon.as_ref().map_or(0, |w| w.stack.len())
+    }
+
+    /// Summed value of the monsters in the discard pile (barehanded kills and retired weapon stacks).
+    pub fn discard_pile_value(&self) -> u32 {
+        self.discard.iter().filter(|c| c.is_monster()).map(|c| c.monster_value() as u32).sum()
+    }
+
+    pub fn discard_pile_composition(&self) -> DeckComposition {
+        DeckComposition::of(&self.discard)
+    }
+
+    /// Clean weapon kills in a row so far; a hit taken or a barehand fight resets it.
+    pub fn kill_streak(&self) -> u32 { self.current_kill_streak }
+
+    /// Half the run's longest kill streak, added to every final score under the streak bonus rule.
+    pub fn streak_bonus(&self) -> i32 {
+        if self.rules.kill_streak_bonus { (self.max_kill_streak_this_run / 2) as i32 } else { 0 }
+    }
+
+    /// Scores if the run ended right now, as `(won, died)`, computed exactly as the finish would.
+    /// Endless has a single score for both.
+    pub fn projected_score(&self) -> (i32, i32) {
+        match self.game_mode {
+            GameMode::Classic => (
+                self.victory_breakdown().total(),
+                death_score(self.player.hp, self.remaining_monster_penalty()) + self.streak_bonus(),
+            ),
+            GameMode::Endless => (self.endless_score() + self.streak_bonus(), self.endless_score() + self.streak_bonus()),
+        }
+    }
+
+    fn finish_death(&mut self) {
+        self.set_phase(GamePhase::GameOver);
+        self.release_slot();
+        let score = self.projected_score().1;
+        self.score = Some(score);
+        self.log.push(format!("You fall... Final score: {}.", score));
+        self.outcome = Some(Outcome::Death);
+        self.push_score_and_rank(false, false);
+        self.game_over_scroll = 0;
+    }
+
+    // Scored like a death at the current HP, then straight back to the menu
+    fn abandon_run(&mut self) {
+        self.history.push(GameEvent::Abandoned { room: self.room_number, hp: self.player.hp });
+        self.release_slot();
+        let score = self.projected_score().1;
+        self.score = Some(score);
+        self.log.push(format!("You abandon the run. Final score: {}.", score));
+        self.push_score_and_rank(false, true);
+        self.set_phase(GamePhase::Menu);
+        self.notify(format!("Run abandoned. Score {} recorded.", score));
+    }
+
+    /// Damage the monster in `slot` would deal if taken with `mode`; `None` when the slot holds no monster.
+    /// The status bar preview and the lethal-fight guard both go through this.
+    pub fn preview_fight(&self, slot: usize, mode: UseMode) -> Option<i32> {
+        let card = (*self.room.get(slot)?)?;
+        if !card.is_monster() { return None; }
+        let mval = card.monster_value();
+        let weapon = self.player.weapon.as_ref().filter(|w| w.can_use_on(mval) && mode != UseMode::Barehand);
+        Some(match weapon {
+            Some(w) => (mval as i32 - w.value as i32).max(0),
+            None => mval as i32,
+        })
+    }
+
+    fn fight_is_lethal(&self, slot: usize, mode: UseMode) -> bool {
+        self.preview_fight(slot, mode).is_some_and(|dmg| dmg >= self.player.hp)
+    }
+
+    // Another card to take, a safer way to fight this one, or the room can still be avoided
+    fn has_alternative_to(&self, slot: usize) -> bool {
+        let other_card = self.room.iter().enumerate().any(|(i, c)| i != slot && c.is_some());
+        let can_avoid = self.avoid_status() == AvoidStatus::Ready;
+        other_card || can_avoid || !self.fight_is_lethal(slot, UseMode::Weapon)
+    }
+
+    fn take_selected(&mut self, mode: UseMode) {
+        if self.phase != GamePhase::Running { return; }
+        if self.choices_this_turn >= 3 && self.visible_count() >= 2 {
+            self.log.push("You've already taken 3 cards. Ending turn.".into());
+            self.end_turn();
+            return;
+        }
+        let idx = self.selected;
+        let Some(card) = self.room[idx].take() else { return; };
+        // Determine how many picks allowed this turn based on initial room size; default = 3, but when fewer cards visible, allow all but one
+        self.resolve_card(card, mode, idx);
+
+        if self.player.hp <= 0 { self.finish_death(); return; }
+
+        // Count pick and decide if turn ends: leave exactly one card if possible
+        self.choices_this_turn += 1;
+        if self.visible_count() <= 1 {
+            // if only one remains (or none), end turn and refill
+            self.end_turn();
+        } else if self.choices_this_turn >= 3 {
+            self.end_turn();
+        }
+    }
+
+    fn resolve_card(&mut self, card: Card, mode: UseMode, slot: usize) {
+        match card.suit {
+            Suit::Hearts if self.rules.no_potions => {
+                self.log.push(format!("No potions this run; the {} potion is discarded.", card.monster_value()));
+                self.history.push(GameEvent::PotionDiscarded { value: card.monster_value() });
+                self.discard.push(card);
+            }
+            Suit::Diamonds if self.rules.no_weapons => {
+                self.log.push(format!("No weapons this run; the {} weapon is discarded.", card.monster_value()));
+                self.history.push(GameEvent::WeaponDiscarded { value: card.monster_value() });
+                self.discard.push(card);
+            }
+            Suit::Hearts => {
+                if !self.potion_used_this_turn {
+                    let val = card.monster_value(); // 2..10
+                    let heal = val as i32;
                     let before = self.player.hp;
                     self.player.hp = (self.player.hp + heal).min(self.player.max_hp);
                     self.log.push(format!("You drink a potion ({}). HP {}→{}.", heal, before, self.player.hp));
                     self.last_card_potion_value = Some(val);
                     self.history.push(GameEvent::Potion { value: val, hp_before: before, hp_after: self.player.hp });
+                    self.stats.potions_used += 1;
+                    self.start_animation(AnimationState::PotionHeal { frame: 0 });
+                    let wasted = before + heal - self.player.hp;
+                    if wasted > 0 {
+                        self.history.push(GameEvent::PotionOverheal { value: wasted as u8, capped_at: self.player.max_hp });
+                        self.stats.healing_wasted += wasted as u32;
+                    }
                 } else {
-                    self.log.push("You already used a potion this turn; this one is discarded.".into());
+                    self.log.push(format!("You already used a potion this turn; the {} potion is discarded.", card.monster_value()));
                     self.history.push(GameEvent::PotionDiscarded { value: card.monster_value() });
+                    self.stats.potions_wasted += 1;
                 }
                 self.potion_used_this_turn = true;
                 self.discard.push(card);
             }
             Suit::Diamonds => {
                 // Bind weapon: equip and discard previous weapon + its monsters
+                let val = card.monster_value(); // 2..10
                 if let Some(w) = self.player.weapon.take() {
                     // discard previous weapon card and monsters on it
                     self.discard.push(Card::new(Suit::Diamonds, Rank::new(w.value)));
                     for m in w.stack { self.discard.push(m); }
+                    self.log.push(format!("You swap your {} weapon for the {}.", w.value, val));
+                    self.history.push(GameEvent::WeaponReplaced { old: w.value, new: val });
+                } else {
+                    self.log.push(format!("You equip a weapon ({}).", val));
+                    self.history.push(GameEvent::Weapon { value: val });
                 }
-                let val = card.monster_value(); // 2..10
-                self.player.weapon = Some(WeaponState::new(val));
-                self.log.push(format!("You equip a weapon ({}).", val));
-                self.history.push(GameEvent::Weapon { value: val });
+                let mut weapon = WeaponState::new(val);
+                weapon.uses_remaining = self.rules.weapon_breaks_after_uses;
+                self.player.weapon = Some(weapon);
+                self.stats.weapons_equipped += 1;
                 // The weapon card stays equipped (not in discard)
             }
             Suit::Clubs | Suit::Spades => {
                 let mval = card.monster_value();
+                let mut broken = None; // logged after the fight, which the checks below expect last
                 let mut use_weapon = false;
                 if let Some(w) = &self.player.weapon && w.can_use_on(mval) {
                     use_weapon = match mode {
@@ -290,6 +1592,7 @@ impl Game {
                     };
                 }
                 if use_weapon {
+                    self.start_animation(AnimationState::WeaponAttack { frame: 0, slot });
                     let w = self.player.weapon.as_mut().unwrap();
                     let dmg = (mval as i32 - w.value as i32).max(0);
                     if dmg > 0 {
@@ -303,6 +1606,15 @@ impl Game {
                     }
                     w.stack.push(card);
                     w.last_monster = Some(mval);
+                    if let Some(uses) = w.uses_remaining.as_mut() {
+                        *uses = uses.saturating_sub(1);
+                        if *uses == 0 && let Some(w) = self.player.weapon.take() {
+                            self.log.push(format!("Your {} weapon breaks.", w.value));
+                            broken = Some(GameEvent::WeaponDestroyed { value: w.value, kills: w.stack.len() as u8 });
+                            self.discard.push(Card::new(Suit::Diamonds, Rank::new(w.value)));
+                            self.discard.extend(w.stack);
+                        }
+                    }
                 } else {
                     let before = self.player.hp;
                     self.player.hp -= mval as i32;
@@ -310,82 +1622,1952 @@ impl Game {
                     self.discard.push(card);
                      self.history.push(GameEvent::Fight { monster: mval, with_weapon: None, damage_taken: mval });
                 }
+                if let Some(GameEvent::Fight { with_weapon, damage_taken, .. }) = self.history.last() {
+                    self.stats.damage_taken += *damage_taken as u32;
+                    // Any damage or a barehand fight breaks the streak of clean weapon kills
+                    if with_weapon.is_some() && *damage_taken == 0 {
+                        self.current_kill_streak += 1;
+                        self.max_kill_streak_this_run = self.max_kill_streak_this_run.max(self.current_kill_streak);
+                    } else {
+                        self.current_kill_streak = 0;
+                    }
+                }
+                if self.player.hp > 0 { self.stats.monsters_killed += 1; }
+                if self.player.hp <= 0 {
+                    // The last Fight event carries the numbers of the killing blow
+                    if let Some(GameEvent::Fight { with_weapon, damage_taken, .. }) = self.history.last() {
+                        self.death_cause = Some(DeathCause {
+                            card,
+                            with_weapon: *with_weapon,
+                            damage: *damage_taken,
+                            hp_before: self.player.hp + *damage_taken as i32,
+                            room: self.room_number,
+                        });
+                    }
+                }
+                self.history.extend(broken);
             }
         }
+        self.last_resolved = Some(card);
         // Track last-card scoring: only keep if a potion was the most recent resolution
         match card.suit { Suit::Hearts => { /* keep set above */ } _ => { self.last_card_potion_value = None; } }
     }
 
-    pub fn tick(&mut self) { /* future animations */ }
+    pub fn tick(&mut self) {
+        self.frame_counter += 1;
+        if let Some(anim) = self.deal_animation.as_mut() {
+            anim.slots_revealed = anim.reveal_at_frame.iter().filter(|f| **f <= self.frame_counter).count();
+            if anim.slots_revealed == 4 { self.deal_animation = None; }
+        }
+        for phase in self.card_phase.iter_mut() {
+            if let AnimationPhase::Flipping { since } = *phase && self.frame_counter >= since + FLIP_FRAMES {
+                *phase = AnimationPhase::FaceUp;
+            }
+        }
+        if self.feedback.is_some_and(|fb| self.frame_counter >= fb.until_frame) { self.feedback = None; }
+        self.animation = self.animation.and_then(AnimationState::advanced);
+        if self.notice.as_ref().is_some_and(|(_, at)| at.elapsed() >= NOTICE_DURATION) {
+            self.notice = None;
+        }
+    }
+
+    /// Points at a move rejected during a run: the empty cell it targeted, otherwise the Status
+    /// box. Returns whether the terminal bell should ring too.
+    pub fn flag_rejection(&mut self, err: ActionError) -> bool {
+        if self.phase != GamePhase::Running { return false; }
+        let target = match err {
+            ActionError::SlotEmpty(slot) => FeedbackTarget::Slot(slot),
+            _ => FeedbackTarget::Status,
+        };
+        if self.config.feedback.visual() {
+            self.feedback = Some(Feedback { target, until_frame: self.frame_counter + FEEDBACK_FRAMES });
+        }
+        self.config.feedback.audible()
+    }
+
+    // Reduced motion skips the flashes altogether
+    fn start_animation(&mut self, animation: AnimationState) {
+        if !self.config.reduced_motion { self.animation = Some(animation); }
+    }
+
+    /// Whether `target` is flashing right now.
+    pub fn flashing(&self, target: FeedbackTarget) -> bool {
+        self.feedback.is_some_and(|fb| fb.target == target)
+    }
+
+    // Every phase change goes through here so debug builds can trace them
+    fn set_phase(&mut self, phase: GamePhase) {
+        #[cfg(debug_assertions)]
+        {
+            if self.phase_transition_history.len() == PHASE_HISTORY_CAP { self.phase_transition_history.remove(0); }
+            self.phase_transition_history.push((phase, self.frame_counter));
+        }
+        self.phase = phase;
+    }
+
+    /// The recorded phase changes, oldest first, one "frame N: Phase" per line.
+    pub fn phase_history_text(&self) -> String {
+        let mut out = String::new();
+        for (phase, frame) in &self.phase_transition_history {
+            let _ = writeln!(out, "  frame {}: {:?}", frame, phase);
+        }
+        out
+    }
+
+    /// Show a message briefly on top of whatever screen is active.
+    pub fn notify(&mut self, text: impl Into<String>) {
+        self.notice = Some((text.into(), Instant::now()));
+    }
+
+    /// Plain-text snapshot of the run state, meant to be pasted into bug reports.
+    pub fn debug_dump(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "phase: {:?}", self.phase);
+        let _ = writeln!(out, "hp: {}/{}", self.player.hp, self.player.max_hp);
+        let _ = writeln!(out, "seed: {} ({})", self.seed_code(), self.seed);
+        let top: Vec<String> = self.deck.iter_from_top().take(5).map(|c| c.notation()).collect();
+        let _ = writeln!(out, "deck: {} cards, top 5: [{}]", self.deck.len(), top.join(", "));
+        let _ = writeln!(out, "room {}:", self.room_number);
+        for (slot, card) in self.room.iter().enumerate() {
+            let marker = if slot == self.selected { "*" } else { " " };
+            let card = card.map(|c| c.notation()).unwrap_or_else(|| "empty".into());
+            let _ = writeln!(out, "  {}[{}] {}", marker, slot, card);
+        }
+        match &self.player.weapon {
+            Some(w) => {
+                let stack: Vec<String> = w.stack.iter().map(|c| c.to_string()).collect();
+                let _ = writeln!(
+                    out,
+                    "weapon: {} (last monster {:?}, stack [{}])",
+                    w.value,
+                    w.last_monster,
+                    stack.join(", ")
+                );
+            }
+            None => { let _ = writeln!(out, "weapon: none"); }
+        }
+        let _ = writeln!(out, "choices_this_turn: {}", self.choices_this_turn);
+        let _ = writeln!(out, "potion_used_this_turn: {}", self.potion_used_this_turn);
+        let _ = writeln!(out, "avoided_last_turn: {}", self.avoided_last_turn);
+        let _ = writeln!(out, "phase history (debug builds, last {}):", PHASE_HISTORY_CAP);
+        out.push_str(&self.phase_history_text());
+        let _ = writeln!(out, "history (last 10):");
+        for ev in &self.history[self.history.len().saturating_sub(10)..] {
+            let _ = writeln!(out, "  {}", ev);
+        }
+        if let Some(entry) = self.leaderboard.iter().max_by_key(|e| e.ts) {
+            let _ = writeln!(out, "latest score: {}", entry);
+        }
+        let best = self.leaderboard.best_score_for_player(&self.player_name);
+        let _ = writeln!(out, "best score of {:?} on any board: {:?}", self.player_name, best);
+        let _ = writeln!(out, "top scores:");
+        for entry in self.leaderboard.top_n(3) {
+            let _ = writeln!(out, "  {}", entry);
+        }
+        let _ = writeln!(out, "log (last 10):");
+        for line in &self.log[self.log.len().saturating_sub(10)..] {
+            let _ = writeln!(out, "  {}", line);
+        }
+        out
+    }
 
     pub fn select_menu_up(&mut self) { if self.menu_selected > 0 { self.menu_selected -= 1; } }
-    pub fn select_menu_down(&mut self) { if self.menu_selected < 2 { self.menu_selected += 1; } }
+    pub fn select_menu_down(&mut self) { if self.menu_selected < MENU_ITEMS.len() - 1 { self.menu_selected += 1; } }
     pub fn menu_activate(&mut self) {
         match self.menu_selected {
-            0 => { self.phase = GamePhase::NameEntry; self.name_input.clear(); }
-            1 => { self.phase = GamePhase::Leaderboard; }
-            2 => { /* handled in app loop by 'q' */ }
+            0 => { self.set_phase(GamePhase::NameEntry); self.begin_name_entry(); self.next_seed = None; }
+            1 => self.open_save_slots(false),
+            2 => { self.set_phase(GamePhase::SeedEntry); self.seed_input.clear(); self.seed_error = None; }
+            3 => { self.set_phase(GamePhase::Leaderboard); }
+            4 => self.open_run_history(),
+            5 => { /* handled in app loop by 'q' */ }
             _ => {}
         }
     }
 
+    // Ghosts are per seed, deck and rules: the same seed deals differently with another deck
+    fn ghost_key(&self) -> String {
+        let mut key = self.seed_code();
+        if let Some(deck) = self.deck_id() { key = format!("{}@{}", key, deck); }
+        for flag in self.rules.flags() { key = format!("{}+{}", key, flag); }
+        if self.game_mode == GameMode::Endless { key.push_str("+endless"); }
+        key
+    }
+
+    /// "Room N: you X HP, best run had Y HP here" for the last finished room, when a ghost exists.
+    pub fn ghost_comparison(&self) -> Option<String> {
+        let ghost = self.ghost.as_ref()?;
+        let room = self.room_number.checked_sub(1).filter(|r| *r > 0)? as usize;
+        let mine = ghost::room_hp_from_history(&self.history, self.player.max_hp);
+        let (you, best) = (mine.get(room - 1)?, ghost.room_hp.get(room - 1)?);
+        Some(format!("Room {}: you {} HP, best run had {} HP here", room, you, best))
+    }
+
+    /// Shareable code for the current run's seed.
+    pub fn seed_code(&self) -> String { seed_codec::encode(self.seed) }
+
+    /// Screens where printable keys are text input rather than shortcuts.
+    pub fn text_input_active(&self) -> bool {
+        matches!(self.phase, GamePhase::NameEntry | GamePhase::SeedEntry)
+            || (self.phase == GamePhase::Leaderboard && self.leaderboard_filter.editing)
+    }
+
+    fn begin_name_entry(&mut self) {
+        self.name_input = self.config.last_name.clone().unwrap_or_default();
+        self.name_prefilled = !self.name_input.is_empty();
+        self.recent_names = recent_names(&self.leaderboard);
+        self.name_suggestion = None;
+    }
+
+    /// Up/Down on name entry: put the next (or previous) recent name in the input, selected like
+    /// the pre-filled one. Does nothing once the player has typed something.
+    fn cycle_name_suggestion(&mut self, forward: bool) {
+        if self.recent_names.is_empty() || !(self.name_input.is_empty() || self.name_prefilled) { return; }
+        let n = self.recent_names.len();
+        let next = match (self.name_suggestion, forward) {
+            (None, true) => 0,
+            (None, false) => n - 1,
+            (Some(i), true) => (i + 1) % n,
+            (Some(i), false) => (i + n - 1) % n,
+        };
+        self.name_suggestion = Some(next);
+        self.name_input = self.recent_names[next].clone();
+        self.name_prefilled = true;
+    }
+
     pub fn name_input_char(&mut self, ch: char) {
-        if (ch.is_ascii_graphic() || ch == ' ') && self.name_input.len() < 20 {
-            self.name_input.push(ch);
+        if !(ch.is_ascii_graphic() || ch == ' ') { return; }
+        self.name_suggestion = None;
+        // The pre-filled name is selected: typing replaces it
+        if self.name_prefilled {
+            self.name_input.clear();
+            self.name_prefilled = false;
+        }
+        if self.name_input.len() < 20 { self.name_input.push(ch); }
+    }
+    pub fn name_input_backspace(&mut self) {
+        self.name_suggestion = None;
+        if self.name_prefilled {
+            self.name_input.clear();
+            self.name_prefilled = false;
+        } else {
+            self.name_input.pop();
         }
     }
-    pub fn name_input_backspace(&mut self) { self.name_input.pop(); }
     pub fn name_input_submit(&mut self) {
-        if !self.name_input.trim().is_empty() { self.player_name = self.name_input.trim().to_string(); }
-        self.new_run();
+        let name = self.name_input.trim();
+        if !name.is_empty() {
+            self.player_name = name.to_string();
+            if self.config.last_name.as_deref() != Some(name) {
+                self.config.last_name = Some(self.player_name.clone());
+                self.config.save(&self.persist);
+            }
+        }
+        self.name_prefilled = false;
+        self.start_run_in_free_slot();
     }
 
-    fn scores_path() -> &'static str { "scoundrel_scores.json" }
-    fn load_leaderboard() -> Vec<ScoreEntry> {
-        let p = Path::new(Self::scores_path());
-        if let Ok(text) = fs::read_to_string(p)
-            && let Ok(v) = serde_json::from_str::<Vec<ScoreEntry>>(&text)
-        { return v; }
-        Vec::new()
+    pub fn scores_path() -> &'static str { "scoundrel_scores.json" }
+    fn archive_path() -> &'static str { "scoundrel_archive.jsonl" }
+    // A missing file is a fresh install; an unreadable one is moved aside rather than overwritten
+    // The board, a warning for the player, and whether the file must be left as it is
+    fn load_leaderboard(path: &Path) -> (Leaderboard, Option<String>, bool) {
+        let Ok(text) = fs::read_to_string(path) else { return (Leaderboard::default(), None, false) };
+        match parse_scores(&text) {
+            Ok(v) => (Leaderboard::from_entries(v), None, false),
+            // Not ours to move or overwrite: the newer version that wrote it still reads it
+            Err(ScoresError::Newer(version)) => {
+                let warning = format!(
+                    "Scores file is from a newer version (format {}); left untouched, this session's scores are not saved",
+                    version
+                );
+                (Leaderboard::default(), Some(warning), true)
+            }
+            Err(reason) => {
+                let mut backup = path.as_os_str().to_owned();
+                backup.push(format!(".corrupt-{}", now_ts()));
+                let backup = Path::new(&backup);
+                let warning = match fs::rename(path, backup) {
+                    Ok(()) => format!("Scores file was unreadable ({}); moved to {}", reason, backup.display()),
+                    Err(e) => format!("Scores file was unreadable ({}) and could not be moved aside: {}", reason, e),
+                };
+                (Leaderboard::default(), Some(warning), false)
+            }
+        }
+    }
+    // Append-only: pruned runs are never rewritten, so nothing is truly lost
+    fn archive_entries(&self, entries: &[ScoreEntry]) {
+        if entries.is_empty() { return; }
+        let lines: String = entries.iter().filter_map(|e| serde_json::to_string(e).ok()).map(|l| l + "\n").collect();
+        self.persist.run("score archive", move || {
+            fs::OpenOptions::new().create(true).append(true).open(Self::archive_path())?.write_all(lines.as_bytes())
+        });
+    }
+    /// The whole leaderboard in the scores file format, for saving and for `--export-scores`.
+    pub fn scores_json(&self) -> String {
+        let file = ScoresFile { version: SCORES_VERSION, entries: self.leaderboard.to_vec() };
+        serde_json::to_string_pretty(&file).unwrap_or_default()
+    }
+    // Leaderboard key `e`: a timestamped copy of the scores file in the working directory
+    fn export_scores_in_app(&mut self) {
+        let path = format!("scoundrel_scores_export_{}.json", now_ts());
+        let text = self.scores_json();
+        let target = path.clone();
+        self.persist.run("score export", move || write_atomic(Path::new(&target), &text));
+        let msg = format!("Exported {} scores to {}", self.leaderboard.len(), path);
+        self.notify(msg.clone());
+        self.log.push(msg);
     }
     fn save_leaderboard(&self) {
-        let _ = fs::write(Self::scores_path(), serde_json::to_string_pretty(&self.leaderboard).unwrap_or_else(|_| "[]".into()));
+        if self.scores_read_only { return; }
+        let text = self.scores_json();
+        if text.is_empty() { return; }
+        self.persist.write("scores", Self::scores_path(), text);
     }
-    fn push_score_and_rank(&mut self, won: bool) {
+    /// `name`'s best score in `mode` on the current deck and rules, from the leaderboard as it stands.
+    pub fn best_for(&self, name: &str, mode: GameMode) -> Option<i32> {
+        let (deck, flags) = (self.deck_id(), self.rules.flags());
+        self.leaderboard
+            .iter()
+            .filter(|e| e.name == name && e.mode == mode && e.deck == deck && e.run_flags == flags)
+            .map(|e| e.score)
+            .max()
+    }
+
+    fn push_score_and_rank(&mut self, won: bool, abandoned: bool) {
         let score = self.score.unwrap_or(0);
-        let entry = ScoreEntry { name: self.player_name.clone(), score, won, ts: now_ts() };
-        self.leaderboard.push(entry);
-        // Sort descending by score
-        self.leaderboard.sort_by(|a,b| b.score.cmp(&a.score));
-        // Find position of most recent by name & ts & score
-        let last = self.leaderboard.iter().enumerate().find(|(_, e)| e.name == self.player_name && e.score == score && e.won == won).map(|(i,_)| i);
-        self.new_rank_pos = last;
+        self.run_time_secs = Some(self.run_elapsed_secs());
+        // Looked up before this run's entry is inserted, or the run would be compared with itself
+        self.best_delta = Some(BestDelta::new(self.best_for(&self.player_name, self.game_mode), score));
+        // Endless scores count kills, not how well a dungeon went, so only Classic runs are rated
+        self.rating_change = (self.game_mode == GameMode::Classic).then(|| self.ratings.apply(&self.player_name, score, won));
+        if self.rating_change.is_some() { self.ratings.save(&self.persist); }
+        // A quit run is no pace to chase on this seed
+        if !abandoned {
+            let room_hp = ghost::room_hp_from_history(&self.history, self.player.max_hp);
+            let key = self.ghost_key();
+            if self.ghosts.record(key, GhostRun { score, room_hp }) {
+                self.ghosts.save(&self.persist);
+            }
+        }
+        let cause = if won {
+            self.last_resolved.map(|c| format!("last card {}", c))
+        } else if abandoned {
+            Some(format!("abandoned in room {}", self.room_number))
+        } else {
+            self.death_cause.as_ref().map(|d| d.compact())
+        };
+        let deck = self.deck_id();
+        let flags = self.rules.flags();
+        let ts = now_ts();
+        let record = RunRecord {
+            ts,
+            name: self.player_name.clone(),
+            seed: self.seed,
+            score,
+            won,
+            deck: deck.clone(),
+            events: self.history.clone(),
+        };
+        let keep = self.config.keep_runs;
+        self.persist.run("run history", move || runs::save(&record, keep));
+        let entry = ScoreEntry {
+            name: self.player_name.clone(),
+            score,
+            won,
+            ts,
+            cause,
+            deck: deck.clone(),
+            run_summary: self.stats.clone(),
+            run_flags: flags.clone(),
+            mode: self.game_mode,
+            schema_version: SCORE_ENTRY_VERSION,
+            duration_secs: Some(self.run_elapsed_secs()),
+            abandoned,
+            seed: Some(self.seed),
+        };
+        // Insert below any equal scores; the insertion index is the new entry's rank,
+        // so an older identical entry can never be mistaken for this one
+        let pos = self.leaderboard.rank_for_score(score);
+        self.leaderboard.insert_and_sort(entry);
+        let keep = self.retention.keep_mask(&self.leaderboard);
+        // Rank among surviving entries of the same bucket, i.e. the row shown on the leaderboard,
+        // and among those the runs on this seed
+        let in_bucket = |i: usize, e: &ScoreEntry| keep[i] && e.deck == deck && e.run_flags == flags && e.mode == self.game_mode;
+        self.new_rank_pos = Some(self.leaderboard.rank_within(pos, in_bucket));
+        self.new_seed_rank = Some(self.leaderboard.rank_within(pos, |i, e| in_bucket(i, e) && e.seed == Some(self.seed)));
+        self.apply_retention(keep);
+    }
+
+    /// Adds the `entries` not already present (same name, score and time), e.g. scores
+    /// imported from another machine, then prunes and saves like a finished run. Returns how many were new.
+    pub fn merge_scores(&mut self, entries: Vec<ScoreEntry>) -> usize {
+        let mut added = 0;
+        for entry in entries {
+            let known = self.leaderboard.iter().any(|e| e.name == entry.name && e.score == entry.score && e.ts == entry.ts);
+            if known { continue; }
+            self.leaderboard.insert_and_sort(entry);
+            added += 1;
+        }
+        if added > 0 {
+            let keep = self.retention.keep_mask(&self.leaderboard);
+            self.apply_retention(keep);
+        }
+        added
+    }
+
+    // Moves entries outside `keep` to the archive and saves the rest
+    fn apply_retention(&mut self, keep: Vec<bool>) {
+        let pruned = self.leaderboard.prune(&keep);
+        self.archive_entries(&pruned);
         self.save_leaderboard();
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Shortcuts for putting a game into a specific mid-run state, for tests and tooling
+/// built with the `testing` feature. They bypass the rules on purpose.
+#[cfg(any(test, feature = "testing"))]
+impl Game {
+    /// A game on the menu like `new_with_seed_and_rules`, but built without reading any file:
+    /// no scores, ratings, ghosts or achievements, default settings, and every write dropped.
+    pub fn sandboxed(seed: u64, rules: Rules) -> Self {
+        let mut game = Game::with_stored(seed, rules, Stored::default());
+        game.persist = PersistHandle::discard();
+        game.theme = Theme::default();
+        game.show_welcome = false;
+        game.player_name = "Tester".into();
+        game
+    }
+
+    /// A running sandboxed game dealt from `deck` exactly as given (no shuffle), to stage specific rooms.
+    pub fn new_with_deck(deck: Deck) -> Self {
+        let mut game = Game::sandboxed(0, Rules::default());
+        game.start_run(deck);
+        game
+    }
+    pub fn set_hp(&mut self, hp: i32) { self.player.hp = hp; }
+    pub fn set_weapon(&mut self, value: u8) { self.player.weapon = Some(WeaponState::new(value)); }
+    pub fn clear_weapon(&mut self) { self.player.weapon = None; }
+    pub fn set_choices_this_turn(&mut self, n: u8) { self.choices_this_turn = n; }
+
+    /// Lets `ai` pick the next move and plays it; false when it had nothing to take.
+    pub fn apply_ai_action(&mut self, ai: &GreedyAi) -> Result<bool, ActionError> {
+        let Some(action) = ai.choose_action(self) else { return Ok(false); };
+        self.apply_action(action)?;
+        Ok(true)
+    }
+}
+
+/// Builders for staging runs card by card, shared by the unit tests and the integration tests.
+#[cfg(any(test, feature = "testing"))]
+pub mod fixtures {
+    use super::{Game, GameEvent, PlayerAction, UseMode};
+    use crate::cards::Card;
+    use crate::deck::Deck;
+
+    /// Cards written in notation and separated by spaces, e.g. `"7d 5c 4h"`.
+    pub fn cards(text: &str) -> Vec<Card> {
+        text.split_whitespace().map(|c| c.parse().expect("test card")).collect()
+    }
+
+    /// A running sandboxed game dealing `text` in reading order, first room first.
+    pub fn dealt(text: &str) -> Game {
+        let mut deck = cards(text);
+        deck.reverse();
+        Game::new_with_deck(Deck::from_cards(deck))
+    }
+
+    /// Like `dealt`, with every confirmation turned off so no move stops to ask.
+    pub fn deal(text: &str) -> Game {
+        let mut game = dealt(text);
+        game.config.confirm_potion_waste = false;
+        game.config.confirm_lethal_fight = false;
+        game.config.confirm_weapon_swap = false;
+        game
+    }
+
+    pub fn take(game: &mut Game, slot: usize) {
+        take_with(game, slot, UseMode::Default);
+    }
+
+    pub fn take_with(game: &mut Game, slot: usize, mode: UseMode) {
+        game.apply_action(PlayerAction::TakeCard { slot, mode }).expect("slot holds a card");
+    }
+
+    /// Latest event apart from the marker a finished room appends.
+    pub fn last_move(game: &Game) -> Option<&GameEvent> {
+        game.history.iter().rev().find(|ev| !matches!(ev, GameEvent::RoomStart { .. }))
+    }
+
+    pub fn room(game: &Game) -> Vec<Option<Card>> {
+        game.room.to_vec()
+    }
+}
+
+/// What the visible cards of the current room add up to; see `Game::room_summary`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RoomSummary {
+    pub monster_value: u32, // damage if every monster were fought barehanded
+    pub monsters: usize,
+    pub weapons: usize,
+    pub potions: usize,
+    pub best_weapon: Option<u8>,
+    pub hidden: usize, // face down or not dealt yet; left out of everything above
+}
+
+/// Parts of a Classic score for clearing the dungeon, shown separately on the Game Over screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VictoryScore {
+    pub hp: i32,
+    pub rooms_bonus: i32,
+    pub potion_bonus: i32,
+    pub streak_bonus: i32, // set by the caller; `new` leaves it at 0
+}
+
+impl VictoryScore {
+    /// HP left, `bonus_per_room` for each room survived, and the last card's value when it was
+    /// a potion and HP is full.
+    pub fn new(hp: i32, max_hp: i32, last_card_potion: Option<u8>, rooms_survived: u32, bonus_per_room: i32) -> Self {
+        let potion_bonus = match last_card_potion {
+            Some(v) if hp == max_hp => v as i32,
+            _ => 0,
+        };
+        Self { hp, rooms_bonus: rooms_survived as i32 * bonus_per_room, potion_bonus, streak_bonus: 0 }
+    }
+
+    pub fn total(&self) -> i32 { self.hp + self.rooms_bonus + self.potion_bonus + self.streak_bonus }
+}
+
+/// Classic score for a death: HP (zero or below) minus the monsters still in the deck and room.
+pub fn death_score(hp: i32, remaining_monsters: i32) -> i32 {
+    hp - remaining_monsters
+}
+
+/// Next non-empty room slot from `current` in `direction` (sign only), wrapping around;
+/// `current` itself when every other slot is empty.
+pub fn find_next_occupied(current: usize, direction: i32, room: &[Option<Card>; 4]) -> usize {
+    let step = if direction < 0 { room.len() - 1 } else { 1 };
+    (1..room.len())
+        .map(|i| (current + step * i) % room.len())
+        .find(|&slot| room[slot].is_some())
+        .unwrap_or(current)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UseMode { Default, Barehand, Weapon }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerAction {
+    TakeCard { slot: usize, mode: UseMode },
+    AvoidRoom,
+    AbandonRun,
+    SelectSlot(usize),
+    MoveSelection { dx: i32, dy: i32 },
+    ToggleHelp,
+    ToggleFullHelp,
+    HelpTab(i32),
+    DismissWelcome,
+    MenuUp,
+    MenuDown,
+    MenuActivate,
+    PlayWeeklySeed,
+    NameInputChar(char),
+    NameInputBackspace,
+    NameInputSubmit,
+    NameInputToggleMode,
+    NameInputToggleHardcore,
+    NameInputSuggest { forward: bool },
+    ShopMove(i32),
+    ShopConfirm,
+    ShopLeave,
+    SaveSlotUp,
+    SaveSlotDown,
+    SaveSlotActivate,
+    SaveSlotDelete,
+    SeedInputChar(char),
+    SeedInputBackspace,
+    SeedInputSubmit,
+    SeedInputCancel,
+    ScrollUp(u16),
+    ScrollDown(u16),
+    NewRun,
+    ShowLeaderboard,
+    ShowMenu,
+    ShowRunHistory,
+    ShowChangelog,
+    CloseChangelog,
+    RunHistoryUp,
+    RunHistoryDown,
+    RunHistoryToggle,
+    LeaderboardUp,
+    LeaderboardDown,
+    LeaderboardToggleDetail,
+    LeaderboardDelete,
+    LeaderboardToggleWins,
+    LeaderboardToggleFastest,
+    LeaderboardToggleSeed,
+    LeaderboardSwitchMode,
+    LeaderboardCycleSort,
+    LeaderboardExport,
+    LeaderboardReverseSort,
+    LeaderboardFilterStart,
+    LeaderboardFilterChar(char),
+    LeaderboardFilterBackspace,
+    LeaderboardFilterDone,   // keep the typed filter
+    LeaderboardFilterCancel, // clear it
+    ConfirmAccept,
+    ConfirmCancel,
+    Quit, // asks first during a run
+}
+
+/// Something the player has to confirm with y/n before it happens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmAction {
+    DeleteScore(usize), // leaderboard index
+    WastePotion { slot: usize, mode: UseMode },
+    LethalFight { slot: usize, mode: UseMode },
+    ReplaceWeapon { slot: usize, mode: UseMode },
+    DeleteSlot(usize),    // save slot
+    OverwriteSlot(usize), // save slot taken by a new run when all are full
+    AbandonRun,
+    Quit, // leave the app mid-run; the run stays in its save slot
+}
+
+impl ConfirmAction {
+    pub fn prompt(&self, game: &Game) -> String {
+        match self {
+            ConfirmAction::DeleteSlot(slot) => format!("Delete the run in slot {}?", slot + 1),
+            ConfirmAction::Quit => match game.current_slot {
+                Some(slot) => format!("Quit? This run stays in slot {} to continue later.", slot + 1),
+                None => "Quit? This run is not saved.".into(),
+            },
+            ConfirmAction::AbandonRun => format!("Abandon this run? It is scored as a loss ({}).", game.projected_score().1),
+            ConfirmAction::OverwriteSlot(slot) => format!("Start the new run in slot {}, replacing what is there?", slot + 1),
+            ConfirmAction::DeleteScore(idx) => match game.leaderboard.get(*idx) {
+                Some(e) => format!("Delete {} ({})?", e.name, e.score),
+                None => "Delete this entry?".into(),
+            },
+            ConfirmAction::WastePotion { slot, .. } => match game.room[*slot] {
+                Some(card) => format!("You already drank a potion this turn. Waste {}?", card),
+                None => "Waste this potion?".into(),
+            },
+            ConfirmAction::LethalFight { slot, mode } => match (game.room[*slot], game.preview_fight(*slot, *mode)) {
+                (Some(card), Some(dmg)) => format!("This will kill you — proceed? ({} deals {}, HP {})", card, dmg, game.player.hp),
+                _ => "This will kill you — proceed?".into(),
+            },
+            ConfirmAction::ReplaceWeapon { slot, .. } => match game.weapon_trade(*slot) {
+                Some(trade) => format!("Replace {}?", trade),
+                None => "Replace your weapon?".into(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ActionError {
+    #[error("no run in progress")]
+    GameNotRunning,
+    #[error("not available on the {0:?} screen")]
+    WrongPhase(GamePhase),
+    #[error("room slot {0} does not exist")]
+    InvalidSlot(usize),
+    #[error("room slot {0} is empty")]
+    SlotEmpty(usize),
+    #[error("the room cannot be avoided right now")]
+    AvoidNotAllowed,
+    #[error("no leaderboard entry selected")]
+    NothingSelected,
+    #[error("nothing is waiting for confirmation")]
+    NothingToConfirm,
+    #[error("the seed could not be read")]
+    InvalidSeed,
+    #[error("no recorded run has a seed yet")]
+    NoSeededRuns,
+    #[error("the name filter is not open")]
+    NotTyping,
+    #[error("no card left over to trade")]
+    NothingToTrade,
+    #[error("that save slot can't be resumed")]
+    SaveSlotUnavailable,
+}
+
+/// A rejected move being pointed out on screen until `until_frame`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Feedback {
+    pub target: FeedbackTarget,
+    pub until_frame: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedbackTarget {
+    /// Rule violations, such as avoiding twice in a row.
+    Status,
+    /// An empty room cell that was targeted.
+    Slot(usize),
+}
+
+/// Phase changes kept in `Game::phase_transition_history`.
+const PHASE_HISTORY_CAP: usize = 50;
+
+/// Main menu entries, top to bottom.
+pub const MENU_ITEMS: [&str; 6] = ["New Game", "Continue", "Custom Seed", "Leaderboard", "Run History", "Quit"];
+
+/// Length of one `tick`; the app polls input and redraws at this rate.
+pub const FRAME_MS: u64 = 1000 / 30;
+
+/// How long a rejected move keeps its target flashing.
+pub const FEEDBACK_FRAMES: u64 = 8;
+
+/// Length of the flash after a weapon fight or a potion.
+pub const ACTION_FLASH_FRAMES: u8 = 9;
+
+/// Length of the confetti over a victory.
+pub const CONFETTI_FRAMES: u8 = 60;
+
+/// Length of a face-down card turning over.
+pub const FLIP_FRAMES: u64 = 6;
+
+/// Endless mode points for each dungeon cleared, on top of one per monster slain.
+pub const ENDLESS_DUNGEON_BONUS: i32 = 25;
+
+/// How long a `notify` message stays on screen.
+pub const NOTICE_DURATION: Duration = Duration::from_secs(3);
+
+/// Leaderboard rows visible on screen: three podium boxes plus seven list rows.
+pub const LEADERBOARD_SHOWN: usize = 10;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScoreEntry {
     pub name: String,
     pub score: i32,
     pub won: bool,
     pub ts: u64,
+    #[serde(default)]
+    pub cause: Option<String>,
+    #[serde(default)]
+    pub deck: Option<String>, // custom deck id; None for the standard deck
+    #[serde(default)]
+    pub run_summary: RunSummary,
+    #[serde(default)]
+    pub run_flags: Vec<String>, // active `Rules` variants; each combination is its own leaderboard
+    #[serde(default)]
+    pub mode: GameMode, // Classic and Endless scores are ranked separately
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u8, // entry layout it was written with; 1 for entries predating the field
+    #[serde(default)]
+    pub duration_secs: Option<u64>, // wall-clock length of the run; None for entries saved before it was timed
+    #[serde(default)]
+    pub abandoned: bool, // quit with `r` mid-run; scored like a death
+    #[serde(default)]
+    pub seed: Option<u64>, // dungeon seed; None for entries saved before it was kept
+}
+
+/// How a finished run compares with the player's best before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BestDelta {
+    First,
+    NewBest { gain: i32, previous: i32 },
+    Tied(i32),
+    Short { by: i32, best: i32 },
+}
+
+impl BestDelta {
+    pub fn new(previous: Option<i32>, score: i32) -> Self {
+        match previous {
+            None => BestDelta::First,
+            Some(best) if score > best => BestDelta::NewBest { gain: score - best, previous: best },
+            Some(best) if score == best => BestDelta::Tied(best),
+            Some(best) => BestDelta::Short { by: best - score, best },
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            BestDelta::First => "Your first recorded run!".to_string(),
+            BestDelta::NewBest { gain, previous } => format!("New personal best! (+{} over {})", gain, previous),
+            BestDelta::Tied(best) => format!("Tied your best ({})", best),
+            BestDelta::Short { by, best } => format!("{} short of your best ({})", by, best),
+        }
+    }
+}
+
+/// Layout of a `ScoreEntry` written today; bump it alongside a new `ScoreEntryV*` when fields change shape.
+pub const SCORE_ENTRY_VERSION: u8 = 2;
+
+fn legacy_schema_version() -> u8 { 1 }
+
+/// Entry layout of the first release, before causes, decks, run summaries and modes.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScoreEntryV1 {
+    pub name: String,
+    pub score: i32,
+    pub won: bool,
+    pub ts: u64,
+}
+
+impl ScoreEntry {
+    /// Upgrades a first-release entry; everything it never recorded gets its default.
+    pub fn migrate(old: ScoreEntryV1) -> ScoreEntry {
+        ScoreEntry {
+            name: old.name,
+            score: old.score,
+            won: old.won,
+            ts: old.ts,
+            cause: None,
+            deck: None,
+            run_summary: RunSummary::default(),
+            run_flags: Vec::new(),
+            mode: GameMode::Classic,
+            schema_version: SCORE_ENTRY_VERSION,
+            duration_secs: None,
+            abandoned: false,
+            seed: None,
+        }
+    }
+
+    /// 🏆 cleared, 🏳 abandoned or 💀 fell.
+    pub fn result_emoji(&self) -> &'static str {
+        if self.won { "🏆" } else if self.abandoned { "🏳" } else { "💀" }
+    }
+}
+
+/// A compact summary, e.g. `💀 uTestr -12 (Classic, K♠ in room 3, 4:10)`.
+impl fmt::Display for ScoreEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} ({}", self.result_emoji(), self.name, self.score, self.mode.label())?;
+        for flag in &self.run_flags { write!(f, ", {}", flag.replace('_', " "))?; }
+        if let Some(cause) = &self.cause { write!(f, ", {}", cause)?; }
+        if let Some(secs) = self.duration_secs { write!(f, ", {}", format_duration(secs))?; }
+        write!(f, ")")
+    }
+}
+
+/// Outcome of `Game::avoid_status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AvoidStatus {
+    Ready,
+    UsedLastRoom,  // rooms can't be avoided twice in a row
+    NeedFourCards, // a card has already been taken from this room
+    Hardcore,      // never allowed
+}
+
+impl AvoidStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            AvoidStatus::Ready => "ready",
+            AvoidStatus::UsedLastRoom => "used last room",
+            AvoidStatus::NeedFourCards => "need 4 cards",
+            AvoidStatus::Hardcore => "off (hardcore)",
+        }
+    }
+}
+
+/// Narrowing of the Leaderboard screen; lasts for the session.
+#[derive(Debug, Clone, Default)]
+pub struct LeaderboardFilter {
+    pub wins_only: bool,
+    pub fastest_wins: bool, // timed wins only, quickest first; replaces the podium and sort
+    pub name: String,    // case-insensitive substring of the player name
+    pub editing: bool,   // the `/` input has focus
+    pub seed: Option<u64>, // runs on this seed only: the one played most recently when switched on
+}
+
+/// Per-run counters stored with each score; entries saved before these existed read as zeros.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunSummary {
+    #[serde(default)]
+    pub rooms_completed: u32,
+    #[serde(default)]
+    pub rooms_avoided: u32,
+    #[serde(default)]
+    pub monsters_killed: u32,
+    #[serde(default)]
+    pub damage_taken: u32,
+    #[serde(default)]
+    pub potions_used: u32,
+    #[serde(default)]
+    pub potions_wasted: u32,
+    #[serde(default)]
+    pub healing_wasted: u32, // HP a potion would have restored above max HP
+    #[serde(default)]
+    pub monster_value_avoided: u32, // summed monster values in rooms avoided; they come back later
+    #[serde(default)]
+    pub weapons_equipped: u32,
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+}
+
+impl RunSummary {
+    /// Label/value pairs in display order, shared by the Game Over table and the leaderboard detail.
+    pub fn rows(&self) -> [(&'static str, String); 6] {
+        [
+            ("Rooms", self.rooms_completed.to_string()),
+            ("Avoided", format!("{} ({} monster value)", self.rooms_avoided, self.monster_value_avoided)),
+            ("Kills", self.monsters_killed.to_string()),
+            ("Damage", self.damage_taken.to_string()),
+            ("Potions", format!("{} (+{} wasted, {} HP overheal)", self.potions_used, self.potions_wasted, self.healing_wasted)),
+            ("Weapons", self.weapons_equipped.to_string()),
+        ]
+    }
+
+    /// One-line digest for leaderboard rows, e.g. `5 rooms · 9 kills · 3 potions · 1 avoid`.
+    pub fn brief(&self) -> String {
+        let plural = |n: u32, word: &str| format!("{} {}{}", n, word, if n == 1 { "" } else { "s" });
+        [
+            plural(self.rooms_completed, "room"),
+            plural(self.monsters_killed, "kill"),
+            plural(self.potions_used, "potion"),
+            plural(self.rooms_avoided, "avoid"),
+        ]
+        .join(" · ")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    #[default]
+    Standard,
+    Custom, // homebrew deck via --deck
+}
+
+/// Format version written to the scores file; bump it and migrate in `parse_scores` on changes.
+pub const SCORES_VERSION: u32 = 1;
+
+/// On-disk layout of the scores file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScoresFile {
+    version: u32,
+    entries: Vec<ScoreEntry>,
+}
+
+/// Just the version of a scores file, whatever its entries look like.
+#[derive(Deserialize)]
+struct ScoresHeader {
+    version: u32,
+}
+
+/// Why a scores file was rejected.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ScoresError {
+    #[error("written by a newer version, format {0}")]
+    Newer(u32),
+    #[error("{0}")]
+    Malformed(String),
+}
+
+/// Reads any known scores file format.
+pub fn parse_scores(text: &str) -> Result<Vec<ScoreEntry>, ScoresError> {
+    // Before versioning the file was a bare array of entries
+    if let Ok(entries) = serde_json::from_str::<Vec<ScoreEntry>>(text) {
+        return Ok(entries);
+    }
+    // Bare arrays are first-release files; if a later field ever stops them reading, upgrade field by field
+    if let Ok(old) = serde_json::from_str::<Vec<ScoreEntryV1>>(text) {
+        return Ok(old.into_iter().map(ScoreEntry::migrate).collect());
+    }
+    // Checked on its own first: a newer file's entries may not read as ours at all
+    if let Ok(head) = serde_json::from_str::<ScoresHeader>(text) && head.version > SCORES_VERSION {
+        return Err(ScoresError::Newer(head.version));
+    }
+    let file: ScoresFile = serde_json::from_str(text).map_err(|e| ScoresError::Malformed(e.to_string()))?;
+    Ok(file.entries)
+}
+
+/// How many leaderboard entries survive a save; the rest move to the archive file.
+#[derive(Debug, Clone, Copy)]
+pub struct RetentionPolicy {
+    pub keep_top: usize,    // best N by score
+    pub keep_recent: usize, // latest M by timestamp, regardless of score
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self { keep_top: 100, keep_recent: 20 }
+    }
+}
+
+impl RetentionPolicy {
+    /// For a score-sorted leaderboard, marks which entries are retained.
+    pub fn keep_mask(&self, entries: &[ScoreEntry]) -> Vec<bool> {
+        let mut keep: Vec<bool> = (0..entries.len()).map(|i| i < self.keep_top).collect();
+        let mut by_recency: Vec<usize> = (0..entries.len()).collect();
+        // Newest first
+        by_recency.sort_by_key(|&i| std::cmp::Reverse((entries[i].ts, i)));
+        for i in by_recency.into_iter().take(self.keep_recent) { keep[i] = true; }
+        keep
+    }
 }
 
 #[derive(Debug, Clone)]
+pub struct DeathCause {
+    pub card: Card,
+    pub with_weapon: Option<u8>, // weapon value if the fatal fight used one
+    pub damage: u8,              // damage dealt by the killing blow
+    pub hp_before: i32,
+    pub room: u32,
+}
+
+impl DeathCause {
+    /// Full sentence for the Game Over header.
+    pub fn describe(&self) -> String {
+        let with = self.with_weapon.map(|w| format!(" despite your {} weapon", w)).unwrap_or_default();
+        format!(
+            "Slain by {} in Room {}{} (needed {}, had {} HP)",
+            self.card, self.room, with, self.damage, self.hp_before
+        )
+    }
+
+    /// Short form stored on the score entry.
+    pub fn compact(&self) -> String {
+        format!("{} in room {}", self.card, self.room)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameEvent {
     RoomStart { number: u32 },
     Potion { value: u8, hp_before: i32, hp_after: i32 },
     PotionDiscarded { value: u8 },
+    PotionOverheal { value: u8, capped_at: i32 }, // healing lost above max HP; follows its `Potion`
     Weapon { value: u8 },
+    WeaponReplaced { old: u8, new: u8 },
+    WeaponDiscarded { value: u8 }, // no-weapons rule; never equipped
     Fight { monster: u8, with_weapon: Option<u8>, damage_taken: u8 },
-    Avoid,
+    Avoid { cards: Vec<Card> }, // the room in slot order; empty in runs saved before it was recorded
+    Abandoned { room: u32, hp: i32 }, // last event of a run quit from the abandon prompt
+    WeaponDestroyed { value: u8, kills: u8 }, // durability rule; follows the fight that used the weapon up
 }
 
-fn now_ts() -> u64 {
+/// The one-line form used by every history view, e.g. `👾 9 with 7 → dmg 2 💥`.
+impl fmt::Display for GameEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameEvent::RoomStart { number } => write!(f, "Room {}", number),
+            GameEvent::Potion { value, hp_before, hp_after } => write!(f, "🧪 +{} HP {}→{}", value, hp_before, hp_after),
+            GameEvent::PotionDiscarded { value } => write!(f, "🧪 discarded {}", value),
+            GameEvent::PotionOverheal { value, capped_at } => write!(f, "⚕ {} HP lost above {}", value, capped_at),
+            GameEvent::Weapon { value } => write!(f, "🗡️ equip {}", value),
+            GameEvent::WeaponReplaced { old, new } => write!(f, "🗡️ swap {} for {}", old, new),
+            GameEvent::WeaponDiscarded { value } => write!(f, "🗡️ discarded {}", value),
+            GameEvent::Fight { monster, with_weapon, damage_taken } => {
+                write!(f, "👾 {}", monster)?;
+                match with_weapon {
+                    Some(v) => write!(f, " with {}", v)?,
+                    None => write!(f, " barehand")?,
+                }
+                write!(f, " → dmg {} {}", damage_taken, if *damage_taken > 0 { "💥" } else { "✅" })
+            }
+            GameEvent::Avoid { cards } if cards.is_empty() => write!(f, "🌀 avoid room"),
+            GameEvent::Avoid { cards } => {
+                write!(f, "🌀 avoided:")?;
+                for card in cards { write!(f, " {}", card)?; }
+                Ok(())
+            }
+            GameEvent::Abandoned { hp, .. } => write!(f, "🏳 abandoned at {} HP", hp),
+            GameEvent::WeaponDestroyed { value, kills } => {
+                write!(f, "⚔ Weapon ({}) broke after {} {}.", value, kills, if *kills == 1 { "kill" } else { "kills" })
+            }
+        }
+    }
+}
+
+/// How one room of a run went, for the minimap on the Equipped box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoomResult {
+    Clean,   // three cards taken without losing HP
+    Damaged, // at least one fight hurt
+    Avoided,
+}
+
+/// Spectator view of a game, written as one JSON object per line by `--state-output`.
+///
+/// Only what the player can see is included; the order of the remaining deck is never exposed.
+///
+/// ```text
+/// phase               "Menu" | "NameEntry" | "SeedEntry" | "Leaderboard" | "RunHistory" | "Running" | "Shop" | "GameOver"
+/// player              player name
+/// hp, max_hp          current and maximum health
+/// room_number         1-based room counter
+/// room                4 slots, each null or {card: "10♣", kind: "monster"|"weapon"|"potion", value};
+///                     a face-down card reads {card: "??", kind: "hidden", value: 0}
+/// selected            highlighted slot index (0-3)
+/// weapon              null or {value, last_monster: null | n, slain: number of monsters on it}
+/// deck_remaining      cards left to draw
+/// monsters_remaining  summed monster value left in deck and room (the death penalty)
+/// potion_used         whether this turn's potion has been drunk
+/// can_avoid           whether the room may be avoided right now
+/// score               final score once the run is over, else null
+/// last_event          most recent history event ({"Fight": {...}}, {"Avoid": {cards: []}}, ...), or null;
+///                     avoided cards are left out since they now sit at the bottom of the deck
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PublicState {
+    pub phase: GamePhase,
+    pub player: String,
+    pub hp: i32,
+    pub max_hp: i32,
+    pub room_number: u32,
+    pub room: [Option<PublicCard>; 4],
+    pub selected: usize,
+    pub weapon: Option<PublicWeapon>,
+    pub deck_remaining: usize,
+    pub monsters_remaining: u32,
+    pub potion_used: bool,
+    pub can_avoid: bool,
+    pub score: Option<i32>,
+    pub last_event: Option<GameEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PublicCard {
+    pub card: String,
+    pub kind: &'static str,
+    pub value: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PublicWeapon {
+    pub value: u8,
+    pub last_monster: Option<u8>,
+    pub slain: usize,
+}
+
+impl Game {
+    pub fn public_state(&self) -> PublicState {
+        let public_card = |c: &Card| PublicCard {
+            card: c.to_string(),
+            kind: match c.suit {
+                Suit::Clubs | Suit::Spades => "monster",
+                Suit::Diamonds => "weapon",
+                Suit::Hearts => "potion",
+            },
+            value: c.monster_value(),
+        };
+        let monsters_remaining = self
+            .deck
+            .iter()
+            .chain(self.room.iter().flatten())
+            .filter(|c| c.is_monster())
+            .map(|c| c.monster_value() as u32)
+            .sum();
+        PublicState {
+            phase: self.phase,
+            player: self.player_name.clone(),
+            hp: self.player.hp,
+            max_hp: self.player.max_hp,
+            room_number: self.room_number,
+            room: std::array::from_fn(|i| {
+                let card = self.room[i].as_ref()?;
+                Some(if self.card_phase[i] == AnimationPhase::FaceDown {
+                    PublicCard { card: "??".into(), kind: "hidden", value: 0 }
+                } else {
+                    public_card(card)
+                })
+            }),
+            selected: self.selected,
+            weapon: self.player.weapon.as_ref().map(|w| PublicWeapon {
+                value: w.value,
+                last_monster: w.last_monster,
+                slain: w.stack.len(),
+            }),
+            deck_remaining: self.deck.len(),
+            monsters_remaining,
+            potion_used: self.potion_used_this_turn,
+            can_avoid: self.phase == GamePhase::Running && self.avoid_status() == AvoidStatus::Ready,
+            score: self.score,
+            last_event: self.history.last().map(|ev| match ev {
+                GameEvent::Avoid { .. } => GameEvent::Avoid { cards: Vec::new() },
+                ev => ev.clone(),
+            }),
+        }
+    }
+}
+
+/// Current unix time in seconds.
+pub fn now_ts() -> u64 {
     use std::time::{SystemTime, UNIX_EPOCH};
     SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
 }
+
+/// Formats a unix timestamp as `YYYY-MM-DD HH:MM` (UTC).
+pub fn format_ts(ts: u64) -> String {
+    let (year, month, day) = civil_from_days((ts / 86_400) as i64);
+    let secs = ts % 86_400;
+    format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, secs / 3_600, (secs % 3_600) / 60)
+}
+
+// A 0-based rank after the entry at `removed` in the same ranking left the board
+fn rank_after_removal(rank: Option<usize>, removed: Option<usize>) -> Option<usize> {
+    match (rank, removed) {
+        (Some(pos), Some(gone)) if pos == gone => None,
+        (Some(pos), Some(gone)) if pos > gone => Some(pos - 1),
+        (other, _) => other,
+    }
+}
+
+/// Distinct player names from `entries`, most recently played first, at most five.
+pub fn recent_names(entries: &[ScoreEntry]) -> Vec<String> {
+    let mut by_date: Vec<&ScoreEntry> = entries.iter().collect();
+    by_date.sort_by_key(|e| std::cmp::Reverse(e.ts));
+    let mut names: Vec<String> = Vec::new();
+    for e in by_date {
+        if names.len() == 5 { break; }
+        if !names.contains(&e.name) { names.push(e.name.clone()); }
+    }
+    names
+}
+
+/// Formats a run length in seconds as `M:SS`, e.g. `12:05`.
+pub fn format_duration(secs: u64) -> String {
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// Formats a unix timestamp as ISO 8601 in UTC, e.g. `2025-03-01T18:04:09Z`.
+pub fn format_iso8601(ts: u64) -> String {
+    let (year, month, day) = civil_from_days((ts / 86_400) as i64);
+    let secs = ts % 86_400;
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, secs / 3_600, (secs % 3_600) / 60, secs % 60)
+}
+
+/// Reads back the `format_iso8601` form (a trailing `Z` is optional).
+pub fn parse_iso8601(text: &str) -> Option<u64> {
+    let text = text.strip_suffix('Z').unwrap_or(text);
+    let (date, time) = text.split_once('T')?;
+    let mut date = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let mut time = time.splitn(3, ':').map(|p| p.parse::<u64>().ok());
+    let (h, m, s) = (time.next()??, time.next()??, time.next()??);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || h > 23 || m > 59 || s > 60 { return None; }
+    let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    Some(days * 86_400 + h * 3_600 + m * 60 + s)
+}
+
+// Civil-from-days and its inverse (Howard Hinnant), avoids pulling in a date crate
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// ISO 8601 year and week number (1–53) of a unix timestamp: weeks start on Monday and
+/// belong to the year their Thursday falls in.
+fn iso_week(ts: u64) -> (i64, u32) {
+    let days = (ts / 86_400) as i64;
+    // 1970-01-01 was a Thursday
+    let thursday = days - (days + 3).rem_euclid(7) + 3;
+    let (year, _, _) = civil_from_days(thursday);
+    let week = (thursday - days_from_civil(year, 1, 1)) / 7 + 1;
+    (year, week as u32)
+}
+
+/// The seed everyone gets for the ISO week `ts` falls in, with that week's number.
+pub fn seed_of_the_week(ts: u64) -> (u32, u64) {
+    let (year, week) = iso_week(ts);
+    // Spread consecutive weeks over the whole seed range (splitmix64 finalizer)
+    let mut z = (year as u64).wrapping_mul(100).wrapping_add(week as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (week, z ^ (z >> 31))
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::fixtures::{cards, deal, room, take, take_with};
+
+    // Run digest
+
+    #[test]
+    fn brief_counts_in_words() {
+        let one = RunSummary { rooms_completed: 1, monsters_killed: 1, potions_used: 1, rooms_avoided: 1, ..RunSummary::default() };
+        assert_eq!(one.brief(), "1 room · 1 kill · 1 potion · 1 avoid");
+        let some = RunSummary { rooms_completed: 5, monsters_killed: 9, potions_used: 0, rooms_avoided: 2, ..RunSummary::default() };
+        assert_eq!(some.brief(), "5 rooms · 9 kills · 0 potions · 2 avoids");
+    }
+
+    #[test]
+    fn finished_run_records_its_digest_on_the_entry() {
+        let mut game = deal("Kc Qc Jc Tc 2c 3h 4h 5c");
+        game.apply_action(PlayerAction::AvoidRoom).expect("avoid is ready");
+        // 2c, 3h, the wasted 4h, then the 5c carried into the next room
+        for slot in 0..4 { take(&mut game, slot); }
+        assert_eq!(game.phase, GamePhase::Running);
+        let summary = &game.stats;
+        assert_eq!((summary.rooms_completed, summary.monsters_killed, summary.potions_used, summary.rooms_avoided), (1, 2, 1, 1));
+        // The avoided Kc and Qc are too much for the HP left
+        take(&mut game, 0);
+        take(&mut game, 1);
+        assert_eq!(game.phase, GamePhase::GameOver);
+        let entry = game.leaderboard.first().expect("the run was recorded");
+        assert_eq!(entry.run_summary.brief(), game.stats.brief());
+    }
+
+    #[test]
+    fn entries_without_a_digest_read_as_zeros() {
+        let entry: ScoreEntry = serde_json::from_str(r#"{"name":"Ann","score":-10,"won":false,"ts":1}"#).expect("old entry");
+        assert_eq!(entry.run_summary, RunSummary::default());
+        assert_eq!(entry.run_summary.brief(), "0 rooms · 0 kills · 0 potions · 0 avoids");
+    }
+
+    // Weapon swap
+
+    fn swapping(text: &str) -> Game {
+        let mut game = deal(text);
+        game.config.confirm_weapon_swap = true;
+        take(&mut game, 0);
+        take_with(&mut game, 1, UseMode::Weapon);
+        game
+    }
+
+    #[test]
+    fn first_weapon_is_equipped_without_asking() {
+        let mut game = deal("7d 2c 3c 4c 5c");
+        game.config.confirm_weapon_swap = true;
+        take(&mut game, 0);
+        assert_eq!(game.pending_confirm, None);
+        assert_eq!(game.player.weapon.as_ref().map(|w| w.value), Some(7));
+    }
+
+    #[test]
+    fn weaker_weapon_asks_and_names_the_trade() {
+        let mut game = swapping("9d 5c 3d 4c 6c 7c");
+        assert_eq!(game.weapon_trade(2).as_deref(), Some("9♦ (binds ≤5, 1 kill) with 3♦"));
+        take(&mut game, 2);
+        assert_eq!(game.pending_confirm, Some(ConfirmAction::ReplaceWeapon { slot: 2, mode: UseMode::Default }));
+        assert_eq!(game.player.weapon.as_ref().map(|w| w.value), Some(9), "nothing changes until confirmed");
+        game.apply_action(PlayerAction::ConfirmAccept).expect("swap asked");
+        assert_eq!(game.player.weapon.as_ref().map(|w| w.value), Some(3));
+        assert!(game.history.contains(&GameEvent::WeaponReplaced { old: 9, new: 3 }));
+    }
+
+    #[test]
+    fn stronger_weapon_asks_too() {
+        let mut game = swapping("4d 3c Td 4c 6c 7c");
+        take(&mut game, 2);
+        assert!(matches!(game.pending_confirm, Some(ConfirmAction::ReplaceWeapon { slot: 2, .. })));
+        game.apply_action(PlayerAction::ConfirmAccept).expect("swap asked");
+        assert!(game.history.contains(&GameEvent::WeaponReplaced { old: 4, new: 10 }));
+        assert!(game.player.weapon.as_ref().is_some_and(|w| w.stack.is_empty() && w.last_monster.is_none()));
+    }
+
+    // Score projection
+
+    #[test]
+    fn death_projection_is_the_final_score() {
+        let mut game = deal("Kc Qs 2c 3c 9c");
+        take(&mut game, 0);
+        assert_eq!(game.projected_score().1, death_score(7, 12 + 2 + 3 + 9));
+        take(&mut game, 1);
+        assert_eq!(game.phase, GamePhase::GameOver);
+        assert_eq!(game.score, Some(death_score(-5, 2 + 3 + 9)));
+        assert_eq!(game.score, Some(game.projected_score().1));
+    }
+
+    #[test]
+    fn victory_projection_is_the_final_score() {
+        let mut game = deal("2c 3c 4c 5c");
+        for slot in 0..3 { take(&mut game, slot); }
+        // The 5 is still in the room; the win projection scores the HP as it stands
+        assert_eq!(game.projected_score().0, 11);
+        take(&mut game, 3);
+        assert_eq!(game.outcome, Some(Outcome::Victory));
+        assert_eq!(game.score, Some(6));
+        assert_eq!(game.score, Some(game.projected_score().0));
+    }
+
+    // Deal animation
+
+    #[test]
+    fn quick_pick_during_the_deal_takes_the_real_card() {
+        let mut game = deal("Kc 7d 5h 2s 9c");
+        assert!(game.deal_animation.is_some() && !game.slot_revealed(3), "the last slot is still being dealt");
+        take(&mut game, 3);
+        assert!(game.deal_animation.is_none(), "any input finishes the deal");
+        assert_eq!(game.player.hp, 18);
+        assert_eq!(game.discard, cards("2s"));
+        assert_eq!(room(&game)[3], None);
+    }
+
+    // Avoid status
+
+    #[test]
+    fn avoid_status_and_avoid_room_agree_in_every_case() {
+        for hardcore in [false, true] {
+            for avoided_last_turn in [false, true] {
+                for taken in [false, true] {
+                    let mut game = deal("2c 3c 4c 5c 6c 7c 8c 9c");
+                    game.rules.hardcore = hardcore;
+                    game.avoided_last_turn = avoided_last_turn;
+                    if taken { take(&mut game, 0); }
+                    let status = game.avoid_status();
+                    let expected = if hardcore {
+                        AvoidStatus::Hardcore
+                    } else if avoided_last_turn {
+                        AvoidStatus::UsedLastRoom
+                    } else if taken {
+                        AvoidStatus::NeedFourCards
+                    } else {
+                        AvoidStatus::Ready
+                    };
+                    let case = (hardcore, avoided_last_turn, taken);
+                    assert_eq!(status, expected, "{case:?}");
+                    let avoided = game.apply_action(PlayerAction::AvoidRoom);
+                    assert_eq!(avoided.is_ok(), status == AvoidStatus::Ready, "{case:?}");
+                }
+            }
+        }
+    }
+
+    // Lethal fight guard
+
+    fn guarded(text: &str, hp: i32) -> Game {
+        let mut game = deal(text);
+        game.config.confirm_lethal_fight = true;
+        game.set_hp(hp);
+        game
+    }
+
+    #[test]
+    fn preview_fight_differs_between_weapon_and_barehand() {
+        let mut game = deal("9c 2h 3h 4h 5h");
+        game.set_weapon(5);
+        assert_eq!(game.preview_fight(0, UseMode::Weapon), Some(4));
+        assert_eq!(game.preview_fight(0, UseMode::Default), Some(4));
+        assert_eq!(game.preview_fight(0, UseMode::Barehand), Some(9));
+        assert_eq!(game.preview_fight(1, UseMode::Default), None, "potions deal no damage");
+    }
+
+    #[test]
+    fn lethal_barehand_fight_asks_first_when_the_weapon_would_not_kill() {
+        let mut game = guarded("9c 2h 3h 4h 5h", 6);
+        game.set_weapon(5);
+        take_with(&mut game, 0, UseMode::Weapon);
+        assert_eq!(game.pending_confirm, None, "4 damage out of 6 HP is safe");
+        let mut game = guarded("9c 2h 3h 4h 5h", 6);
+        game.set_weapon(5);
+        take_with(&mut game, 0, UseMode::Barehand);
+        assert_eq!(game.pending_confirm, Some(ConfirmAction::LethalFight { slot: 0, mode: UseMode::Barehand }));
+        assert_eq!(game.player.hp, 6, "nothing happens until confirmed");
+        game.apply_action(PlayerAction::ConfirmAccept).expect("fight asked");
+        assert_eq!(game.phase, GamePhase::GameOver);
+    }
+
+    #[test]
+    fn lethal_fight_with_no_alternative_is_not_asked() {
+        let mut game = guarded("Kc 2h 3h 4h 5h", 5);
+        game.room = [Some(cards("Kc")[0]), None, None, None];
+        assert_ne!(game.avoid_status(), AvoidStatus::Ready);
+        take(&mut game, 0);
+        assert_eq!(game.pending_confirm, None);
+        assert_eq!(game.phase, GamePhase::GameOver);
+    }
+
+    #[test]
+    fn lethal_guard_can_be_turned_off() {
+        let mut game = guarded("Kc 2h 3h 4h 5h", 5);
+        game.config.confirm_lethal_fight = false;
+        take(&mut game, 0);
+        assert_eq!(game.pending_confirm, None);
+        assert_eq!(game.phase, GamePhase::GameOver);
+    }
+
+    // Seen ahead
+
+    #[test]
+    fn avoided_cards_are_seen_ahead_until_dealt_again() {
+        let mut game = deal("2c 3c 4c 5c 6h 7h 8h 9h");
+        game.apply_action(PlayerAction::AvoidRoom).expect("avoid is ready");
+        assert_eq!(game.cycled, cards("2c 3c 4c 5c"));
+        for slot in 0..3 { take(&mut game, slot); }
+        // Three of them came back into the room; the last is still under the deck
+        assert_eq!(room(&game)[..3], [Some(cards("2c")[0]), Some(cards("3c")[0]), Some(cards("4c")[0])]);
+        assert_eq!(game.cycled, cards("5c"));
+    }
+
+    #[test]
+    fn new_run_forgets_the_cards_seen_ahead() {
+        let mut game = deal("2c 3c 4c 5c 6h 7h 8h 9h");
+        game.apply_action(PlayerAction::AvoidRoom).expect("avoid is ready");
+        assert!(!game.cycled.is_empty());
+        game.new_run();
+        assert!(game.cycled.is_empty());
+    }
+
+    // Shop
+
+    #[test]
+    fn shop_trade_renames_weakened_cards_seen_ahead() {
+        let mut game = deal("2c 3c 4c 5c Ks Qs 7h 8h");
+        game.cycled = cards("Ks Qs");
+        game.shop_trade().expect("a card is left to trade");
+        assert_eq!(game.deck.top_n_cards(3), cards("Qs Js 7h"));
+        assert_eq!(game.cycled, cards("Qs Js"));
+        game.room = [None; 4];
+        game.refill_room();
+        assert!(game.cycled.is_empty(), "every card seen ahead was dealt");
+    }
+
+    #[test]
+    fn seen_copy_stays_listed_while_one_is_still_in_the_deck() {
+        let mut game = deal("2c 3c 4c 5c Ks 7h 8h 9h Qs");
+        game.cycled = cards("Qs");
+        // The K weakens into a second Q; the seen one is still at the bottom
+        game.shop_trade().expect("a card is left to trade");
+        game.room = [None; 4];
+        game.refill_room();
+        assert_eq!(room(&game)[0], Some(cards("Qs")[0]));
+        assert_eq!(game.cycled, cards("Qs"));
+        game.room = [None; 4];
+        game.refill_room();
+        assert!(game.cycled.is_empty());
+    }
+
+    // Cursor movement
+
+    #[test]
+    fn next_occupied_wraps_both_ways_and_skips_gaps() {
+        let c = Some(cards("5c")[0]);
+        assert_eq!(find_next_occupied(3, 1, &[c; 4]), 0);
+        assert_eq!(find_next_occupied(0, -1, &[c; 4]), 3);
+        let ends = [c, None, None, c];
+        assert_eq!(find_next_occupied(0, 1, &ends), 3);
+        assert_eq!(find_next_occupied(3, 1, &ends), 0);
+        assert_eq!(find_next_occupied(0, -1, &ends), 3);
+        // Only the sign of the direction counts
+        assert_eq!(find_next_occupied(3, -7, &ends), 0);
+        // From a slot just taken the walk still finds a card
+        assert_eq!(find_next_occupied(1, 1, &[c, None, None, None]), 0);
+        assert_eq!(find_next_occupied(2, 1, &[None, None, c, None]), 2);
+        assert_eq!(find_next_occupied(1, -1, &[None; 4]), 1);
+    }
+
+    #[test]
+    fn cursor_wraps_over_taken_cards() {
+        let mut game = deal("2c 3c 4c 5c 6c 7c 8c 9c");
+        take(&mut game, 1);
+        let mut moves = Vec::new();
+        game.apply_action(PlayerAction::SelectSlot(3)).expect("slot holds a card");
+        for dx in [1, -1, -1, -1] {
+            game.apply_action(PlayerAction::MoveSelection { dx, dy: 0 }).expect("run in progress");
+            moves.push(game.selected);
+        }
+        assert_eq!(moves, vec![0, 3, 2, 0]);
+    }
+
+    // Room summary
+
+    #[test]
+    fn room_summary_adds_up_a_mixed_room() {
+        let mut game = deal("Kc As 7d 5h 2c");
+        // Cards still being dealt count as hidden; any input finishes the deal
+        assert_eq!(game.room_summary().hidden, 4);
+        game.apply_action(PlayerAction::SelectSlot(0)).expect("run in progress");
+        let summary = game.room_summary();
+        assert_eq!(summary, RoomSummary { monster_value: 27, monsters: 2, weapons: 1, potions: 1, best_weapon: Some(7), hidden: 0 });
+    }
+
+    #[test]
+    fn room_summary_skips_taken_slots() {
+        let mut game = deal("Kc 3d 7d 5h 2c");
+        take(&mut game, 0);
+        take(&mut game, 2);
+        let summary = game.room_summary();
+        assert_eq!(summary, RoomSummary { monster_value: 0, monsters: 0, weapons: 1, potions: 1, best_weapon: Some(3), hidden: 0 });
+    }
+
+    #[test]
+    fn room_summary_leaves_face_down_cards_out() {
+        let mut game = deal("Kc 9d 8d 5h 2c");
+        game.apply_action(PlayerAction::SelectSlot(0)).expect("run in progress");
+        game.card_phase[0] = AnimationPhase::FaceDown;
+        let summary = game.room_summary();
+        assert_eq!((summary.monsters, summary.monster_value, summary.hidden), (0, 0, 1));
+        assert_eq!((summary.weapons, summary.best_weapon), (2, Some(9)));
+    }
+
+    // Progress
+
+    #[test]
+    fn progress_counts_the_weapon_its_stack_and_the_discards() {
+        let mut game = deal("7d 5c 2h 3h 9c 9s 9h 9d");
+        assert_eq!(game.progress(), (0, 8));
+        take(&mut game, 0);
+        take_with(&mut game, 1, UseMode::Weapon);
+        assert_eq!(game.progress(), (2, 8));
+        take(&mut game, 2);
+        assert_eq!(game.progress(), (3, 8));
+    }
+
+    #[test]
+    fn avoided_cards_are_still_ahead() {
+        let mut game = deal("2c 3c 4c 5c 6c 7c 8c 9c");
+        game.apply_action(PlayerAction::AvoidRoom).expect("avoid is ready");
+        assert_eq!(game.progress(), (0, 8));
+        take(&mut game, 0);
+        assert_eq!(game.progress(), (1, 8));
+    }
+
+    #[test]
+    fn weapon_kills_count_out_of_play_from_the_stack() {
+        let mut game = deal("7d 5c 4c 3c 9c 9s 9h 9d");
+        take(&mut game, 0);
+        assert_eq!(game.cards_out_of_play(), 0, "the equipped weapon is still in play");
+        take_with(&mut game, 1, UseMode::Weapon);
+        assert!(game.discard.is_empty());
+        assert_eq!(game.cards_out_of_play(), 1);
+        take_with(&mut game, 2, UseMode::Barehand);
+        assert_eq!(game.discard, cards("4c"));
+        assert_eq!(game.cards_out_of_play(), 2);
+    }
+
+    #[test]
+    fn replaced_weapon_and_its_stack_move_to_the_discard() {
+        let mut game = deal("7d 5c 8d 2h 9c 9s 9h 9d");
+        take(&mut game, 0);
+        take_with(&mut game, 1, UseMode::Weapon);
+        take(&mut game, 2);
+        assert_eq!(game.discard, cards("7d 5c"));
+        assert_eq!(game.cards_out_of_play(), 2);
+        assert_eq!(game.discard.last(), Some(&"5c".parse().expect("test card")));
+    }
+
+    // Autosave
+
+    #[test]
+    fn autosave_skips_cursor_moves_and_peeks() {
+        let mut game = deal("7d 5c 8d 2h 9c 9s 9h 9d");
+        let (persist, writes) = PersistHandle::recording();
+        game.persist = persist;
+        game.current_slot = Some(0);
+        game.apply_action(PlayerAction::SelectSlot(2)).expect("slot exists");
+        game.apply_action(PlayerAction::MoveSelection { dx: 1, dy: 0 }).expect("running");
+        game.apply_action(PlayerAction::ToggleHelp).expect("always allowed");
+        game.apply_action(PlayerAction::ToggleHelp).expect("always allowed");
+        assert!(crate::persist::queued(&writes).is_empty());
+        take(&mut game, 0);
+        assert_eq!(crate::persist::queued(&writes), vec!["save slot"]);
+        game.apply_action(PlayerAction::AbandonRun).expect("running");
+        assert!(crate::persist::queued(&writes).is_empty(), "only asked to confirm");
+        game.apply_action(PlayerAction::ConfirmCancel).expect("confirm is open");
+        game.apply_action(PlayerAction::AvoidRoom).expect_err("a card was taken");
+        assert!(crate::persist::queued(&writes).is_empty());
+    }
+
+    #[test]
+    fn new_run_mid_run_waits_for_the_abandon_confirmation() {
+        let mut game = deal("7d 5c 8d 2h 9c 9s 9h 9d");
+        let (persist, writes) = PersistHandle::recording();
+        game.persist = persist;
+        game.current_slot = Some(0);
+        let dealt = game.room;
+        game.apply_action(PlayerAction::NewRun).expect("running");
+        assert_eq!(game.pending_confirm, Some(ConfirmAction::AbandonRun));
+        game.apply_action(PlayerAction::ConfirmCancel).expect("confirm is open");
+        assert_eq!((game.phase, game.room, game.current_slot), (GamePhase::Running, dealt, Some(0)));
+        assert!(crate::persist::queued(&writes).is_empty(), "nothing saved or scored");
+        game.apply_action(PlayerAction::NewRun).expect("running");
+        game.apply_action(PlayerAction::ConfirmAccept).expect("confirm is open");
+        assert_eq!((game.phase, game.current_slot), (GamePhase::Menu, None));
+        assert!(crate::persist::queued(&writes).contains(&"save slot"), "the slot is released");
+    }
+
+    #[test]
+    fn autosave_when_the_phase_changes() {
+        let mut game = deal("7d 5c 8d 2h 9c 9s 9h 9d");
+        let (persist, writes) = PersistHandle::recording();
+        game.persist = persist;
+        game.current_slot = Some(0);
+        game.apply_action(PlayerAction::ShowMenu).expect("always allowed");
+        assert_eq!(crate::persist::queued(&writes), vec!["save slot"]);
+        game.apply_action(PlayerAction::MenuDown).expect("on the menu");
+        assert!(crate::persist::queued(&writes).is_empty(), "not at the table");
+    }
+
+    #[test]
+    fn deleting_a_slot_goes_through_the_writer() {
+        let mut game = Game::sandboxed(1, Rules::default());
+        let (persist, writes) = PersistHandle::recording();
+        game.persist = persist;
+        game.phase = GamePhase::SaveSlots;
+        game.slots = vec![SlotState::Corrupt, SlotState::Empty, SlotState::Empty];
+        game.current_slot = Some(0);
+        game.apply_action(PlayerAction::SaveSlotDelete).expect("slot is occupied");
+        game.apply_action(PlayerAction::ConfirmAccept).expect("confirm is open");
+        assert_eq!(crate::persist::queued(&writes), vec!["save slot"]);
+        assert!(game.slots[0].is_empty());
+        assert_eq!(game.current_slot, None);
+    }
+
+    // Quitting
+
+    #[test]
+    fn quit_mid_run_asks_first() {
+        let mut game = deal("7d 5c 8d 2h 9c 9s 9h 9d");
+        game.apply_action(PlayerAction::Quit).expect("always allowed");
+        assert_eq!((game.pending_confirm, game.quit_requested), (Some(ConfirmAction::Quit), false));
+        game.apply_action(PlayerAction::ConfirmCancel).expect("confirm is open");
+        assert_eq!((game.phase, game.quit_requested), (GamePhase::Running, false));
+        game.apply_action(PlayerAction::Quit).expect("always allowed");
+        game.apply_action(PlayerAction::ConfirmAccept).expect("confirm is open");
+        assert!(game.quit_requested);
+        // The same key twice is a yes
+        let mut game = deal("7d 5c 8d 2h 9c 9s 9h 9d");
+        game.apply_action(PlayerAction::Quit).expect("always allowed");
+        game.apply_action(PlayerAction::Quit).expect("always allowed");
+        assert!(game.quit_requested);
+    }
+
+    #[test]
+    fn quit_outside_a_run_is_immediate() {
+        let mut game = Game::sandboxed(1, Rules::default());
+        game.apply_action(PlayerAction::Quit).expect("always allowed");
+        assert_eq!((game.pending_confirm, game.quit_requested), (None, true));
+    }
+
+    // Seeds
+
+    fn start_from_menu(game: &mut Game) {
+        game.apply_action(PlayerAction::ShowMenu).expect("always allowed");
+        game.menu_selected = 0;
+        game.apply_action(PlayerAction::MenuActivate).expect("on the menu");
+        assert_eq!(game.phase, GamePhase::NameEntry);
+        game.apply_action(PlayerAction::NameInputSubmit).expect("entering a name");
+        assert_eq!(game.phase, GamePhase::Running);
+    }
+
+    #[test]
+    fn cli_seed_deals_the_first_run_from_the_menu() {
+        let mut game = Game::sandboxed(4242, Rules::default());
+        start_from_menu(&mut game);
+        assert_eq!(game.seed, 4242);
+        start_from_menu(&mut game);
+        assert_ne!(game.seed, 4242, "later runs draw fresh seeds");
+    }
+
+    #[test]
+    fn seed_picked_in_game_wins_over_the_cli_seed() {
+        let mut game = Game::sandboxed(4242, Rules::default());
+        game.apply_action(PlayerAction::PlayWeeklySeed).expect("on the menu");
+        game.apply_action(PlayerAction::NameInputSubmit).expect("entering a name");
+        assert_eq!(game.seed, seed_of_the_week(now_ts()).1);
+        start_from_menu(&mut game);
+        assert_ne!(game.seed, 4242, "the CLI seed was for the first run only");
+    }
+
+    // Retention and ranks
+
+    fn entry(score: i32, ts: u64) -> ScoreEntry {
+        ScoreEntry::migrate(ScoreEntryV1 { name: "Tester".into(), score, won: false, ts })
+    }
+
+    #[test]
+    fn retention_keeps_exactly_the_top_and_the_newest() {
+        let entries = vec![entry(50, 1), entry(40, 5), entry(30, 2), entry(20, 4), entry(10, 3)];
+        let policy = RetentionPolicy { keep_top: 2, keep_recent: 2 };
+        // Top two by position, then the two newest: the second is in both, so the 20 (ts 4) gets in
+        assert_eq!(policy.keep_mask(&entries), vec![true, true, false, true, false]);
+        let none = RetentionPolicy { keep_top: 0, keep_recent: 0 };
+        assert_eq!(none.keep_mask(&entries), vec![false; 5]);
+        let all = RetentionPolicy { keep_top: 10, keep_recent: 0 };
+        assert_eq!(all.keep_mask(&entries), vec![true; 5]);
+    }
+
+    #[test]
+    fn retention_ties_on_time_keep_the_latest_inserted() {
+        // Runs finished in the same second: the one inserted last sits lowest and is the newest
+        let entries = vec![entry(30, 7), entry(20, 7), entry(20, 7)];
+        let policy = RetentionPolicy { keep_top: 0, keep_recent: 1 };
+        assert_eq!(policy.keep_mask(&entries), vec![false, false, true]);
+    }
+
+    #[test]
+    fn pruned_entries_leave_the_board_after_a_run() {
+        let mut game = deal("Ks Qs 2c 3c");
+        game.retention = RetentionPolicy { keep_top: 1, keep_recent: 1 };
+        game.leaderboard = Leaderboard::from_entries(vec![entry(100, 1), entry(90, 2), entry(-50, 3)]);
+        take(&mut game, 0);
+        take(&mut game, 1);
+        assert_eq!(game.phase, GamePhase::GameOver);
+        // The best entry and this run (the newest) survive; the two in between are archived
+        let scores: Vec<i32> = game.leaderboard.iter().map(|e| e.score).collect();
+        assert_eq!(scores, vec![100, game.score.expect("scored")]);
+        assert_eq!(game.new_rank_pos, Some(1));
+    }
+
+    #[test]
+    fn identical_run_ranks_below_the_older_one() {
+        let mut first = deal("Ks Qs 2c 3c");
+        take(&mut first, 0);
+        take(&mut first, 1);
+        assert_eq!(first.new_rank_pos, Some(0));
+        // Same name, score, outcome and (most likely) second: only the insertion point tells them apart
+        let mut again = deal("Ks Qs 2c 3c");
+        again.leaderboard = first.leaderboard.clone();
+        take(&mut again, 0);
+        take(&mut again, 1);
+        assert_eq!(again.score, first.score);
+        assert_eq!(again.new_rank_pos, Some(1));
+        assert_eq!(again.new_seed_rank, Some(1));
+        assert_eq!(again.leaderboard.len(), 2);
+    }
+
+    #[test]
+    fn best_delta_is_read_before_the_run_is_inserted() {
+        let mut first = deal("Ks Qs 2c 3c");
+        take(&mut first, 0);
+        take(&mut first, 1);
+        // Looked up after the insert, the run would count as its own previous best
+        assert_eq!(first.best_delta, Some(BestDelta::First));
+        let score = first.score.expect("scored");
+        let mut again = deal("Ks Qs 2c 3c");
+        again.leaderboard = first.leaderboard.clone();
+        take(&mut again, 0);
+        take(&mut again, 1);
+        assert_eq!(again.best_delta, Some(BestDelta::Tied(score)));
+    }
+
+    #[test]
+    fn delete_removes_the_row_from_the_tab_switched_board() {
+        let mut game = Game::sandboxed(0, Rules::default());
+        let endless = ScoreEntry { mode: GameMode::Endless, ..entry(70, 3) };
+        game.leaderboard = Leaderboard::from_entries(vec![entry(90, 1), entry(80, 2), endless]);
+        game.new_rank_pos = Some(1);
+        game.apply_action(PlayerAction::ShowLeaderboard).expect("always available");
+        game.apply_action(PlayerAction::LeaderboardSwitchMode).expect("on the leaderboard");
+        assert_eq!(game.leaderboard_view().len(), 1);
+        game.apply_action(PlayerAction::LeaderboardDelete).expect("a row is selected");
+        game.apply_action(PlayerAction::ConfirmAccept).expect("delete asked");
+        let left: Vec<(i32, GameMode)> = game.leaderboard.iter().map(|e| (e.score, e.mode)).collect();
+        assert_eq!(left, vec![(90, GameMode::Classic), (80, GameMode::Classic)]);
+        // The Classic run's rank is untouched by an Endless delete
+        assert_eq!(game.new_rank_pos, Some(1));
+    }
+
+    // Scores file loading
+
+    // A fresh directory under the system temp dir, unique to this process and `name`
+    fn scratch_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("scoundrel-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("temp dir is writable");
+        dir
+    }
+
+    fn load_scores_text(name: &str, text: &str) -> (std::path::PathBuf, Leaderboard, Option<String>, bool) {
+        let path = scratch_dir(name).join("scores.json");
+        fs::write(&path, text).expect("temp dir is writable");
+        let (board, warning, read_only) = Game::load_leaderboard(&path);
+        (path, board, warning, read_only)
+    }
+
+    fn moved_aside(path: &Path) -> bool {
+        let dir = fs::read_dir(path.parent().expect("in a dir")).expect("dir exists");
+        !path.exists() && dir.flatten().any(|e| e.file_name().to_string_lossy().starts_with("scores.json.corrupt-"))
+    }
+
+    #[test]
+    fn truncated_scores_file_is_moved_aside() {
+        let (path, board, warning, read_only) = load_scores_text("truncated", r#"{"version": 1, "entries": [{"name": "A", "sco"#);
+        assert!(board.is_empty() && !read_only);
+        assert!(warning.is_some_and(|w| w.contains("unreadable")));
+        assert!(moved_aside(&path));
+    }
+
+    #[test]
+    fn wrong_shape_scores_file_is_moved_aside() {
+        for (name, text) in [("shape-object", r#"{"version": 1, "scores": []}"#), ("shape-array", "[1, 2, 3]")] {
+            let (path, board, warning, read_only) = load_scores_text(name, text);
+            assert!(board.is_empty() && !read_only && warning.is_some(), "{}", text);
+            assert!(moved_aside(&path), "{}", text);
+        }
+    }
+
+    #[test]
+    fn newer_scores_file_is_left_untouched() {
+        // Entries a future version might write, in a shape this one cannot read
+        let text = r#"{"version": 9, "entries": [{"player": {"id": 3}, "points": 12}]}"#;
+        let (path, board, warning, read_only) = load_scores_text("newer", text);
+        assert!(board.is_empty() && read_only);
+        assert!(warning.is_some_and(|w| w.contains("newer version") && w.contains("not saved")));
+        assert_eq!(fs::read_to_string(&path).expect("still there"), text);
+        assert_eq!(parse_scores(text).map(|v| v.len()), Err(ScoresError::Newer(9)));
+    }
+
+    #[test]
+    fn current_scores_file_loads_sorted() {
+        let mut game = Game::sandboxed(0, Rules::default());
+        game.leaderboard = Leaderboard::from_entries(vec![entry(3, 1), entry(8, 2)]);
+        let (path, board, warning, read_only) = load_scores_text("current", &game.scores_json());
+        assert_eq!(board.iter().map(|e| e.score).collect::<Vec<_>>(), vec![8, 3]);
+        assert!(warning.is_none() && !read_only && path.exists());
+    }
+
+    const V1_SCORES: &str = r#"[{"name":"Ann","score":17,"won":true,"ts":1700000000},{"name":"Bo","score":-40,"won":false,"ts":1600000000}]"#;
+
+    #[test]
+    fn v1_entry_migrates_with_defaults() {
+        let old: Vec<ScoreEntryV1> = serde_json::from_str(V1_SCORES).expect("v1 shape");
+        let entry = ScoreEntry::migrate(old[0].clone());
+        assert_eq!((entry.name.as_str(), entry.score, entry.won, entry.ts), ("Ann", 17, true, 1_700_000_000));
+        assert_eq!(entry.schema_version, SCORE_ENTRY_VERSION);
+        assert_eq!((entry.cause, entry.deck, entry.duration_secs, entry.seed), (None, None, None, None));
+        assert!(entry.run_flags.is_empty() && !entry.abandoned);
+        assert_eq!(entry.mode, GameMode::Classic);
+        assert_eq!(entry.run_summary.rooms_completed, 0);
+        assert_eq!(entry.run_summary.difficulty, Difficulty::Standard);
+    }
+
+    #[test]
+    fn v1_scores_file_reads_and_saves_in_the_current_format() {
+        let entries = parse_scores(V1_SCORES).expect("v1 file reads");
+        assert_eq!(entries.iter().map(|e| (e.name.as_str(), e.score)).collect::<Vec<_>>(), vec![("Ann", 17), ("Bo", -40)]);
+        assert!(entries.iter().all(|e| e.schema_version == 1 && e.mode == GameMode::Classic && e.seed.is_none()));
+        let mut game = Game::sandboxed(0, Rules::default());
+        game.leaderboard = Leaderboard::from_entries(entries);
+        let again = parse_scores(&game.scores_json()).expect("saved file reads back");
+        assert_eq!(serde_json::to_value(&again).expect("json"), serde_json::to_value(&*game.leaderboard).expect("json"));
+    }
+
+    // Score import
+
+    #[test]
+    fn merge_skips_entries_already_on_the_board() {
+        let mut game = Game::sandboxed(0, Rules::default());
+        game.leaderboard = Leaderboard::from_entries(vec![entry(30, 1), entry(10, 2)]);
+        // One known run, one new, and the new one twice
+        let added = game.merge_scores(vec![entry(30, 1), entry(20, 3), entry(20, 3)]);
+        assert_eq!(added, 1);
+        let board: Vec<(i32, u64)> = game.leaderboard.iter().map(|e| (e.score, e.ts)).collect();
+        assert_eq!(board, vec![(30, 1), (20, 3), (10, 2)]);
+        assert_eq!(game.merge_scores(vec![entry(20, 3)]), 0, "importing the same file twice adds nothing");
+    }
+
+    #[test]
+    fn older_scores_file_imports_and_merges() {
+        let mut game = Game::sandboxed(0, Rules::default());
+        game.leaderboard = Leaderboard::from_entries(vec![ScoreEntry { name: "Ann".into(), ..entry(17, 1_700_000_000) }]);
+        let added = game.merge_scores(parse_scores(V1_SCORES).expect("v1 file reads"));
+        assert_eq!(added, 1, "Ann's run is already there");
+        let board: Vec<(&str, i32)> = game.leaderboard.iter().map(|e| (e.name.as_str(), e.score)).collect();
+        assert_eq!(board, vec![("Ann", 17), ("Bo", -40)]);
+    }
+}
@@ -0,0 +1,104 @@
+//! Best-run snapshots per seed, replayed as a "ghost" when the same dungeon is played again.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::game::GameEvent;
+use crate::persist::PersistHandle;
+
+/// HP trajectory of the best finished run on one seed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GhostRun {
+    pub score: i32,
+    /// HP at the end of each room, index 0 = room 1.
+    pub room_hp: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GhostStore {
+    runs: BTreeMap<String, GhostRun>,
+}
+
+impl GhostStore {
+    fn path() -> &'static str { "scoundrel_ghosts.json" }
+
+    pub fn load() -> Self {
+        fs::read_to_string(Path::new(Self::path()))
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, persist: &PersistHandle) {
+        if let Ok(text) = serde_json::to_string(self) { persist.write("ghosts", Self::path(), text); }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&GhostRun> {
+        self.runs.get(key)
+    }
+
+    /// Keep `run` if it beats the stored best for `key`; returns whether the store changed.
+    pub fn record(&mut self, key: String, run: GhostRun) -> bool {
+        if self.runs.get(&key).is_some_and(|best| best.score >= run.score) { return false; }
+        self.runs.insert(key, run);
+        true
+    }
+}
+
+/// Replays the event history and returns the HP at the end of every room.
+pub fn room_hp_from_history(history: &[GameEvent], start_hp: i32) -> Vec<i32> {
+    let mut hp = start_hp;
+    let mut out = Vec::new();
+    let mut in_room = false;
+    for ev in history {
+        match ev {
+            GameEvent::RoomStart { .. } => {
+                if in_room { out.push(hp); }
+                in_room = true;
+            }
+            GameEvent::Potion { hp_after, .. } => hp = *hp_after,
+            GameEvent::Fight { damage_taken, .. } => hp -= *damage_taken as i32,
+            _ => {}
+        }
+    }
+    if in_room { out.push(hp); }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn room_hp_is_the_hp_each_room_ended_on() {
+        let history = [
+            GameEvent::RoomStart { number: 1 },
+            GameEvent::Fight { monster: 7, with_weapon: None, damage_taken: 7 },
+            GameEvent::Potion { value: 9, hp_before: 13, hp_after: 20 },
+            GameEvent::PotionOverheal { value: 9, capped_at: 20 },
+            GameEvent::Fight { monster: 4, with_weapon: Some(2), damage_taken: 2 },
+            GameEvent::RoomStart { number: 2 },
+            GameEvent::Avoid { cards: Vec::new() },
+            GameEvent::RoomStart { number: 3 },
+            GameEvent::PotionDiscarded { value: 5 },
+            GameEvent::Fight { monster: 13, with_weapon: None, damage_taken: 13 },
+        ];
+        assert_eq!(room_hp_from_history(&history, 20), vec![18, 18, 5]);
+    }
+
+    #[test]
+    fn no_rooms_no_hp() {
+        assert!(room_hp_from_history(&[], 20).is_empty());
+    }
+
+    #[test]
+    fn record_keeps_only_a_better_run() {
+        let mut store = GhostStore::default();
+        assert!(store.record("seed".into(), GhostRun { score: 5, room_hp: vec![20] }));
+        assert!(!store.record("seed".into(), GhostRun { score: 5, room_hp: vec![10] }));
+        assert!(store.record("seed".into(), GhostRun { score: 9, room_hp: vec![12] }));
+        assert_eq!(store.get("seed").map(|r| r.room_hp.clone()), Some(vec![12]));
+    }
+}
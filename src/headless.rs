@@ -0,0 +1,261 @@
+//! Runs without a terminal: `--script` files and `--export-history`, driven through the same
+//! `PlayerAction`s the keyboard produces.
+
+use std::fs::{self, File};
+use std::io::{self, IsTerminal, Write};
+
+use anyhow::{bail, Context, Result};
+
+use crate::app::RunOptions;
+use crate::cards::Card;
+use crate::export;
+use crate::game::{Game, GamePhase, PlayerAction, UseMode};
+use crate::simulate;
+
+/// One parsed script command.
+enum Step {
+    Act(PlayerAction),
+    TakeCard { card: Card, mode: UseMode }, // slot looked up when the step runs
+    Quit,
+}
+
+/// Play one seeded run from `--script` (or piped stdin when exporting), print the outcome and,
+/// with `--export-history`, write the history as Markdown.
+pub fn run(mut game: Game, opts: &RunOptions) -> Result<()> {
+    if let Some(warning) = &game.leaderboard_warning { eprintln!("warning: {warning}"); }
+    if let Some(name) = &opts.name { game.player_name = name.clone(); }
+    // Nobody is there to answer a confirmation prompt, and like simulated games a scripted
+    // run leaves no scores, ratings or ghosts behind
+    simulate::make_unattended(&mut game);
+    game.new_run();
+
+    let steps = match &opts.script {
+        Some(path) => {
+            let text = fs::read_to_string(path).with_context(|| format!("reading script {}", path.display()))?;
+            parse_script(&text).with_context(|| format!("in script {}", path.display()))?
+        }
+        None => {
+            let stdin = io::stdin();
+            let text = if stdin.is_terminal() { String::new() } else { io::read_to_string(stdin)? };
+            parse_moves(&text)?
+        }
+    };
+    let quit = play(&mut game, steps);
+
+    if let Some(out) = &opts.export_history {
+        finish(&mut game)?;
+        let mut file = io::BufWriter::new(File::create(out).with_context(|| format!("creating {}", out.display()))?);
+        export::write_run_markdown(&game, &mut file)?;
+        file.flush()?;
+        println!("{}; history written to {}", outcome(&game, quit), out.display());
+    } else {
+        println!("{}", outcome(&game, quit));
+    }
+    Ok(())
+}
+
+// Runs the steps until the run ends or a `quit`; returns whether it was quit. Steps that
+// cannot be played are reported with their line and skipped.
+fn play(game: &mut Game, steps: Vec<(usize, Step)>) -> bool {
+    for (line, step) in steps {
+        skip_shop(game);
+        if game.phase != GamePhase::Running { break; }
+        match step {
+            Step::Act(action) => {
+                if let Err(e) = game.apply_action(action) { eprintln!("line {line}: skipped, {e}"); }
+            }
+            Step::TakeCard { card, mode } => match game.room.iter().position(|c| *c == Some(card)) {
+                Some(slot) => {
+                    if let Err(e) = game.apply_action(PlayerAction::TakeCard { slot, mode }) { eprintln!("line {line}: skipped, {e}"); }
+                }
+                None => eprintln!("line {line}: skipped, {} is not in the room", card.notation()),
+            },
+            Step::Quit => return true,
+        }
+    }
+    false
+}
+
+// An export needs a finished run: play out whatever the moves left open
+fn finish(game: &mut Game) -> Result<()> {
+    while matches!(game.phase, GamePhase::Running | GamePhase::Shop) {
+        skip_shop(game);
+        let Some(slot) = game.room.iter().position(Option::is_some) else { break; };
+        game.apply_action(PlayerAction::TakeCard { slot, mode: UseMode::Default })?;
+    }
+    Ok(())
+}
+
+// Scripts have no shop commands, so `--shop` runs walk past it without trading
+fn skip_shop(game: &mut Game) {
+    if game.phase == GamePhase::Shop { let _ = game.apply_action(PlayerAction::ShopLeave); }
+}
+
+fn outcome(game: &Game, quit: bool) -> String {
+    match game.score {
+        Some(score) => {
+            let result = if game.player.hp > 0 { "cleared" } else { "fell" };
+            format!("Seed {}: {} with score {} after {} rooms", game.seed_code(), result, score, game.room_number)
+        }
+        None => {
+            let how = if quit { "quit" } else { "stopped" };
+            format!("Seed {}: {} in room {} with {} HP", game.seed_code(), how, game.room_number, game.player.hp)
+        }
+    }
+}
+
+/// Script files hold one command per line; blank lines and `#` comments are ignored.
+///
+/// `take <1-4|card> [weapon|barehand]`, `select <1-4>`, `left`, `right`, `avoid`, `quit`;
+/// a card is written like `Ks`, `Td` or `K♠`
+fn parse_script(text: &str) -> Result<Vec<(usize, Step)>> {
+    let mut steps = Vec::new();
+    for (i, raw) in text.lines().enumerate() {
+        let line = i + 1;
+        let words: Vec<String> = raw.split('#').next().unwrap_or("").split_whitespace().map(str::to_ascii_lowercase).collect();
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        let step = match words.as_slice() {
+            [] => continue,
+            ["take", slot, rest @ ..] => {
+                let mode = match rest {
+                    [] => UseMode::Default,
+                    ["weapon"] => UseMode::Weapon,
+                    ["barehand"] => UseMode::Barehand,
+                    _ => bail!("line {line}: expected `take <1-4|card> [weapon|barehand]`, got `{}`", raw.trim()),
+                };
+                if slot.chars().all(|c| c.is_ascii_digit()) {
+                    Step::Act(PlayerAction::TakeCard { slot: parse_slot(slot, line)?, mode })
+                } else {
+                    let card = slot.parse().with_context(|| format!("line {line}: cannot read card `{slot}`"))?;
+                    Step::TakeCard { card, mode }
+                }
+            }
+            ["select", slot] => Step::Act(PlayerAction::SelectSlot(parse_slot(slot, line)?)),
+            ["left"] => Step::Act(PlayerAction::MoveSelection { dx: -1, dy: 0 }),
+            ["right"] => Step::Act(PlayerAction::MoveSelection { dx: 1, dy: 0 }),
+            ["avoid"] => Step::Act(PlayerAction::AvoidRoom),
+            ["quit"] => Step::Quit,
+            _ => bail!("line {line}: unknown command `{}`", raw.trim()),
+        };
+        steps.push((line, step));
+    }
+    Ok(steps)
+}
+
+fn parse_slot(word: &str, line: usize) -> Result<usize> {
+    match word.parse::<usize>() {
+        Ok(n @ 1..=4) => Ok(n - 1),
+        _ => bail!("line {line}: room slot must be 1-4, got `{word}`"),
+    }
+}
+
+/// Shorthand for piped exports, whitespace-separated like the quick-pick keys: `1`-`4` take
+/// a card, `b1`/`w1` force barehanded/weapon, `v` avoids, `#` starts a comment.
+fn parse_moves(text: &str) -> Result<Vec<(usize, Step)>> {
+    let mut steps = Vec::new();
+    for (i, raw) in text.lines().enumerate() {
+        for token in raw.split('#').next().unwrap_or("").split_whitespace() {
+            let action = move_action(token).with_context(|| format!("line {}: unknown move `{token}`", i + 1))?;
+            steps.push((i + 1, Step::Act(action)));
+        }
+    }
+    Ok(steps)
+}
+
+fn move_action(token: &str) -> Option<PlayerAction> {
+    let token = token.to_ascii_lowercase();
+    if token == "v" { return Some(PlayerAction::AvoidRoom); }
+    let (mode, digit) = match token.as_bytes() {
+        [d] => (UseMode::Default, *d),
+        [b'b', d] => (UseMode::Barehand, *d),
+        [b'w', d] => (UseMode::Weapon, *d),
+        _ => return None,
+    };
+    matches!(digit, b'1'..=b'4').then(|| PlayerAction::TakeCard { slot: (digit - b'1') as usize, mode })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::fixtures::dealt;
+
+    // A run dealt `text` in reading order, set up like `run` does
+    fn staged(text: &str) -> Game {
+        let mut game = dealt(text);
+        simulate::make_unattended(&mut game);
+        game
+    }
+
+    const CLEAR_SCRIPT: &str = "
+        # equip, fight with it, then the last two cards
+        take 7d
+        take 5c weapon
+        take 4h
+        take 2s   # room 2 deals nothing new: the deck is empty
+    ";
+
+    #[test]
+    fn script_clears_a_staged_dungeon() {
+        let mut game = staged("7d 5c 4h 2s");
+        let quit = play(&mut game, parse_script(CLEAR_SCRIPT).expect("valid script"));
+        assert!(!quit);
+        assert_eq!(game.phase, GamePhase::GameOver);
+        assert_eq!(game.player.hp, 20);
+        assert_eq!(outcome(&game, quit), format!("Seed {}: cleared with score {} after 2 rooms", game.seed_code(), game.score.expect("scored")));
+    }
+
+    #[test]
+    fn script_quit_stops_the_run_where_it_is() {
+        let mut game = staged("Kc 5c 4h 2s 9c");
+        let steps = parse_script("take 1 barehand\nquit\ntake 2").expect("valid script");
+        let quit = play(&mut game, steps);
+        assert!(quit);
+        assert_eq!(game.phase, GamePhase::Running);
+        assert_eq!(outcome(&game, quit), format!("Seed {}: quit in room 1 with 7 HP", game.seed_code()));
+    }
+
+    #[test]
+    fn unplayable_steps_are_skipped() {
+        let mut game = staged("Kc 5c 4h 2s 9c");
+        // No such card in the room, then a slot already taken
+        let steps = parse_script("take Qh\ntake 1\ntake 1\nselect 3").expect("valid script");
+        assert!(!play(&mut game, steps));
+        assert_eq!(game.player.hp, 7);
+        assert_eq!(game.selected, 2);
+    }
+
+    #[test]
+    fn script_errors_name_their_line() {
+        let err = |text: &str| parse_script(text).err().map(|e| format!("{:#}", e)).expect("rejected");
+        assert!(err("take 1\n\ndance").starts_with("line 3: unknown command `dance`"));
+        assert!(err("take 5").starts_with("line 1: room slot must be 1-4"));
+        assert!(err("# fine\ntake 2 sideways").starts_with("line 2: expected `take <1-4|card>"));
+        assert!(err("take Zz").starts_with("line 1: cannot read card `zz`"));
+    }
+
+    #[test]
+    fn piped_moves_read_like_the_quick_pick_keys() {
+        let steps = parse_moves("1 w2 # comment\nb3 v").expect("valid moves");
+        let actions: Vec<_> = steps.into_iter().map(|(line, s)| match s { Step::Act(a) => (line, a), _ => panic!("only actions") }).collect();
+        assert_eq!(actions, vec![
+            (1, PlayerAction::TakeCard { slot: 0, mode: UseMode::Default }),
+            (1, PlayerAction::TakeCard { slot: 1, mode: UseMode::Weapon }),
+            (2, PlayerAction::TakeCard { slot: 2, mode: UseMode::Barehand }),
+            (2, PlayerAction::AvoidRoom),
+        ]);
+        assert!(parse_moves("1 x5").is_err());
+    }
+
+    #[test]
+    fn export_plays_out_the_rest_and_writes_the_history() {
+        let mut game = staged("7d 5c 4h 2s");
+        assert!(!play(&mut game, parse_script("take 7d").expect("valid script")));
+        finish(&mut game).expect("moves are legal");
+        assert_eq!(game.phase, GamePhase::GameOver);
+        let mut out = Vec::new();
+        export::write_run_markdown(&game, &mut out).expect("writes to memory");
+        let text = String::from_utf8(out).expect("utf-8");
+        assert!(text.starts_with("# Scoundrel run: Tester"));
+        assert!(text.contains("Equip weapon"), "{}", text);
+    }
+}
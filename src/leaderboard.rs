@@ -0,0 +1,118 @@
+//! Every recorded score, kept in score order so ranks and the podium can be read off by position.
+
+use std::ops::Deref;
+
+use crate::game::ScoreEntry;
+
+/// Score entries, best first; equal scores keep the order they were added in. Read access goes
+/// through `Deref`, changes through the methods below so the order can't be broken.
+#[derive(Debug, Clone, Default)]
+pub struct Leaderboard(Vec<ScoreEntry>);
+
+impl Deref for Leaderboard {
+    type Target = Vec<ScoreEntry>;
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl Leaderboard {
+    /// Sorts `entries` by score; older score files may not be.
+    pub fn from_entries(mut entries: Vec<ScoreEntry>) -> Self {
+        entries.sort_by_key(|e| std::cmp::Reverse(e.score));
+        Self(entries)
+    }
+
+    /// The best `n` entries over every mode, deck and rule set (fewer when the board is shorter).
+    pub fn top_n(&self, n: usize) -> &[ScoreEntry] {
+        &self.0[..n.min(self.0.len())]
+    }
+
+    /// 0-based index a new entry with `score` would take: below every entry scoring at least as much.
+    pub fn rank_for_score(&self, score: i32) -> usize {
+        self.0.partition_point(|e| e.score >= score)
+    }
+
+    /// Adds `entry` at its `rank_for_score`, so an older identical entry always stays above it.
+    pub fn insert_and_sort(&mut self, entry: ScoreEntry) {
+        let pos = self.rank_for_score(entry.score);
+        self.0.insert(pos, entry);
+    }
+
+    /// `name`'s best score on the whole board, whatever the mode, deck or rules.
+    pub fn best_score_for_player(&self, name: &str) -> Option<i32> {
+        // Sorted by score, so the first match is the best
+        self.0.iter().find(|e| e.name == name).map(|e| e.score)
+    }
+
+    /// 0-based rank of the entry at `index` among the entries above it that `in_group` accepts
+    /// (given each one's index and entry). Equal scores above it count, so a tie goes to the older run.
+    pub fn rank_within(&self, index: usize, in_group: impl Fn(usize, &ScoreEntry) -> bool) -> usize {
+        self.0[..index].iter().enumerate().filter(|(i, e)| in_group(*i, e)).count()
+    }
+
+    /// Seed of the most recently recorded run that kept one.
+    pub fn latest_seed(&self) -> Option<u64> {
+        self.0.iter().filter(|e| e.seed.is_some()).max_by_key(|e| e.ts).and_then(|e| e.seed)
+    }
+
+    pub fn remove(&mut self, index: usize) -> ScoreEntry {
+        self.0.remove(index)
+    }
+
+    /// Keeps the entries marked in `keep` and returns the others, both in their original order.
+    pub fn prune(&mut self, keep: &[bool]) -> Vec<ScoreEntry> {
+        let mut pruned = Vec::new();
+        let mut kept = Vec::with_capacity(self.0.len());
+        for (e, k) in self.0.drain(..).zip(keep) {
+            if *k { kept.push(e); } else { pruned.push(e); }
+        }
+        self.0 = kept;
+        pruned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::ScoreEntryV1;
+
+    fn entry(name: &str, score: i32, seed: u64) -> ScoreEntry {
+        ScoreEntry { seed: Some(seed), ..ScoreEntry::migrate(ScoreEntryV1 { name: name.into(), score, won: true, ts: 0 }) }
+    }
+
+    fn on_seed(seed: u64) -> impl Fn(usize, &ScoreEntry) -> bool {
+        move |_, e| e.seed == Some(seed)
+    }
+
+    #[test]
+    fn tie_within_a_seed_goes_to_the_older_run() {
+        let board = Leaderboard::from_entries(vec![entry("A", 20, 1), entry("B", 20, 1), entry("C", 20, 1)]);
+        let ranks: Vec<usize> = (0..3).map(|i| board.rank_within(i, on_seed(1))).collect();
+        assert_eq!(ranks, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn ties_on_other_seeds_do_not_count() {
+        let board = Leaderboard::from_entries(vec![entry("A", 20, 2), entry("B", 20, 1), entry("C", 20, 2), entry("D", 20, 1)]);
+        // Globally D is fourth; on seed 1 only B, tied and older, is above it
+        assert_eq!(board.rank_within(3, |_, _| true), 3);
+        assert_eq!(board.rank_within(3, on_seed(1)), 1);
+        assert_eq!(board.rank_within(1, on_seed(1)), 0);
+    }
+
+    #[test]
+    fn group_can_filter_by_index() {
+        let board = Leaderboard::from_entries(vec![entry("A", 30, 1), entry("B", 20, 1), entry("C", 20, 1)]);
+        // Leaving out the entry at index 1 (as pruning does) lifts the tied entry below it
+        assert_eq!(board.rank_within(2, |i, e| i != 1 && e.seed == Some(1)), 1);
+        assert_eq!(board.rank_within(0, on_seed(1)), 0);
+    }
+
+    #[test]
+    fn equal_score_is_inserted_below_the_older_entry() {
+        let mut board = Leaderboard::from_entries(vec![entry("A", 30, 1), entry("B", 20, 1), entry("C", 10, 1)]);
+        assert_eq!(board.rank_for_score(20), 2);
+        board.insert_and_sort(entry("D", 20, 1));
+        let names: Vec<&str> = board.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["A", "B", "D", "C"]);
+    }
+}
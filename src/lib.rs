@@ -0,0 +1,28 @@
+//! Scoundrel, the single-player dungeon crawl with a deck of cards, in the terminal. The
+//! binary is a thin wrapper around `cli`; tests and benches reach the game through here.
+
+pub mod achievements;
+pub mod ai;
+pub mod app;
+pub mod attract;
+pub mod cards;
+pub mod cli;
+pub mod config;
+pub mod deck;
+pub mod export;
+pub mod game;
+pub mod headless;
+pub mod ghost;
+pub mod leaderboard;
+pub mod persist;
+pub mod rating;
+pub mod rules;
+pub mod runs;
+pub mod saves;
+pub mod seed_codec;
+pub mod session;
+pub mod shutdown;
+pub mod simulate;
+pub mod spectator;
+pub mod theme;
+pub mod ui;
@@ -1,11 +1,5 @@
-mod app;
-mod cards;
-mod deck;
-mod game;
-mod ui;
-
 use anyhow::Result;
 
 fn main() -> Result<()> {
-    app::run()
+    scoundrel::cli::Cli::parse_and_run()
 }
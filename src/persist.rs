@@ -0,0 +1,185 @@
+//! File writes off the render loop: the game queues owned snapshots and a background
+//! thread writes them in order, reporting failures back to the app.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+type Job = Box<dyn FnOnce() -> io::Result<()> + Send>;
+
+pub(crate) enum Message {
+    Run { what: &'static str, job: Job },
+    Shutdown,
+}
+
+/// Owns the writer thread; lives in the app for the whole session.
+pub struct Persister {
+    tx: Sender<Message>,
+    errors: Receiver<String>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Persister {
+    pub fn spawn() -> Self {
+        let (tx, rx) = mpsc::channel::<Message>();
+        let (err_tx, errors) = mpsc::channel();
+        let worker = thread::spawn(move || {
+            // Runs until Shutdown; everything queued before it is still written
+            for msg in rx {
+                match msg {
+                    Message::Run { what, job } => {
+                        if let Err(e) = job() { let _ = err_tx.send(format!("Could not save {}: {}", what, e)); }
+                    }
+                    Message::Shutdown => break,
+                }
+            }
+        });
+        Self { tx, errors, worker: Some(worker) }
+    }
+
+    /// Cheap handle for queueing writes, stored on the `Game`.
+    pub fn handle(&self) -> PersistHandle {
+        PersistHandle { tx: Some(self.tx.clone()), discard: false }
+    }
+
+    /// Failures reported since the last call.
+    pub fn take_errors(&self) -> Vec<String> {
+        self.errors.try_iter().collect()
+    }
+
+    /// Finish every queued write and stop the thread; call once on the way out.
+    pub fn shutdown(mut self) -> Vec<String> {
+        let _ = self.tx.send(Message::Shutdown);
+        if let Some(worker) = self.worker.take() { let _ = worker.join(); }
+        self.take_errors()
+    }
+}
+
+/// Where the game sends its writes. Without a `Persister` (headless runs) writes happen inline.
+#[derive(Debug, Clone, Default)]
+pub struct PersistHandle {
+    tx: Option<Sender<Message>>,
+    discard: bool, // drop every write, e.g. for simulated games
+}
+
+impl PersistHandle {
+    /// A handle that writes nothing at all.
+    pub fn discard() -> Self {
+        PersistHandle { tx: None, discard: true }
+    }
+
+    /// Queue `job`; `what` names the data in error messages ("scores", "run history").
+    pub fn run(&self, what: &'static str, job: impl FnOnce() -> io::Result<()> + Send + 'static) {
+        if self.discard { return; }
+        let job: Job = Box::new(job);
+        let job = match &self.tx {
+            Some(tx) => match tx.send(Message::Run { what, job }) {
+                Ok(()) => return,
+                // Writer already gone (shutting down): fall back to writing here
+                Err(mpsc::SendError(Message::Run { job, .. })) => job,
+                Err(_) => return,
+            },
+            None => job,
+        };
+        if let Err(e) = job() { eprintln!("Could not save {}: {}", what, e); }
+    }
+
+    /// Queue a whole-file replacement of `path` with `contents`.
+    pub fn write(&self, what: &'static str, path: &'static (impl AsRef<Path> + Sync + ?Sized), contents: String) {
+        self.run(what, move || write_atomic(path.as_ref(), &contents));
+    }
+
+    /// A handle whose writes are only queued, never run; `queued` lists them by name.
+    #[cfg(test)]
+    pub(crate) fn recording() -> (Self, Receiver<Message>) {
+        let (tx, rx) = mpsc::channel();
+        (PersistHandle { tx: Some(tx), discard: false }, rx)
+    }
+}
+
+/// Names of the writes a `recording` handle has queued since the last call.
+#[cfg(test)]
+pub(crate) fn queued(rx: &Receiver<Message>) -> Vec<&'static str> {
+    rx.try_iter().filter_map(|msg| match msg { Message::Run { what, .. } => Some(what), Message::Shutdown => None }).collect()
+}
+
+/// Write a sibling temp file and rename it over `path`, so a crash mid-save leaves
+/// either the old or the new contents, never half of one.
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("scoundrel-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("temp dir is writable");
+        dir
+    }
+
+    #[test]
+    fn atomic_write_replaces_the_file_and_leaves_no_temp() {
+        let dir = scratch_dir("atomic");
+        let path = dir.join("data.json");
+        write_atomic(&path, "old").expect("first write");
+        write_atomic(&path, "new").expect("second write");
+        assert_eq!(fs::read_to_string(&path).expect("written"), "new");
+        assert_eq!(fs::read_dir(&dir).expect("dir exists").count(), 1);
+    }
+
+    #[test]
+    fn failed_atomic_write_keeps_the_old_contents() {
+        let dir = scratch_dir("atomic-fail");
+        let path = dir.join("data.json");
+        write_atomic(&path, "old").expect("first write");
+        // The temp file cannot be written where a directory is in the way
+        fs::create_dir(dir.join("data.json.tmp")).expect("temp dir is writable");
+        assert!(write_atomic(&path, "new").is_err());
+        assert_eq!(fs::read_to_string(&path).expect("still there"), "old");
+    }
+
+    #[test]
+    fn slow_writes_do_not_block_and_shutdown_drains_them() {
+        let persister = Persister::spawn();
+        let handle = persister.handle();
+        let done = Arc::new(Mutex::new(Vec::new()));
+        let queued = Instant::now();
+        for i in 0..3 {
+            let done = Arc::clone(&done);
+            handle.run("slow data", move || {
+                thread::sleep(Duration::from_millis(100));
+                done.lock().expect("not poisoned").push(i);
+                Ok(())
+            });
+        }
+        handle.run("scores", || Err(io::Error::other("disk full")));
+        // Queueing returns long before the 300ms of writing it asked for
+        assert!(queued.elapsed() < Duration::from_millis(100));
+        let errors = persister.shutdown();
+        assert_eq!(*done.lock().expect("not poisoned"), vec![0, 1, 2]);
+        assert_eq!(errors, vec!["Could not save scores: disk full".to_string()]);
+    }
+
+    #[test]
+    fn discard_handle_runs_nothing() {
+        let ran = Arc::new(Mutex::new(false));
+        let flag = Arc::clone(&ran);
+        PersistHandle::discard().run("scores", move || { *flag.lock().expect("not poisoned") = true; Ok(()) });
+        assert!(!*ran.lock().expect("not poisoned"));
+        // Without a writer thread the job runs right here
+        let flag = Arc::clone(&ran);
+        PersistHandle::default().run("scores", move || { *flag.lock().expect("not poisoned") = true; Ok(()) });
+        assert!(*ran.lock().expect("not poisoned"));
+    }
+}
@@ -0,0 +1,132 @@
+//! A per-player rating that follows how well recent Classic runs went, so progress shows
+//! beyond the single best score.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::game::{GameMode, ScoreEntry};
+use crate::persist::PersistHandle;
+
+/// Where every player starts.
+pub const START_RATING: f64 = 1000.0;
+/// Ratings a run can pull toward: a fall on the first room aims at the floor, a full-health win with a potion at the ceiling.
+const FLOOR: f64 = 600.0;
+const CEILING: f64 = 2000.0;
+/// Share of the gap to a run's target that the rating closes; bigger while a player has few runs.
+const K_NEW: f64 = 0.25;
+const K_SETTLED: f64 = 0.1;
+const SETTLE_AFTER: u32 = 10;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PlayerRating {
+    pub rating: f64,
+    pub last_delta: f64, // change from the most recent rated run
+    pub runs: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Ratings {
+    players: BTreeMap<String, PlayerRating>,
+}
+
+/// The rating a run is worth on its own. Deaths map from about -200 (nothing cleared) up to
+/// the middle of the range; wins take the upper part, more for a higher score.
+pub fn run_target(score: i32, won: bool) -> f64 {
+    let mid = (FLOOR + CEILING) / 2.0;
+    if won {
+        let quality = (score as f64 / 30.0).clamp(0.0, 1.0);
+        mid + (CEILING - mid) * quality
+    } else {
+        let reach = ((score as f64 + 200.0) / 200.0).clamp(0.0, 1.0);
+        FLOOR + (mid - FLOOR) * reach
+    }
+}
+
+/// `current` moved part of the way toward the target of a run, damped once `runs` are on record.
+pub fn updated_rating(current: f64, runs: u32, score: i32, won: bool) -> f64 {
+    let k = if runs < SETTLE_AFTER { K_NEW } else { K_SETTLED };
+    current + k * (run_target(score, won) - current)
+}
+
+impl Ratings {
+    fn path() -> &'static str { "scoundrel_ratings.json" }
+
+    /// Saved ratings, or ratings replayed from the Classic `leaderboard` oldest first when there is no file yet.
+    pub fn load(leaderboard: &[ScoreEntry]) -> Self {
+        match fs::read_to_string(Path::new(Self::path())) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_default(),
+            Err(_) => Self::from_history(leaderboard),
+        }
+    }
+
+    fn from_history(leaderboard: &[ScoreEntry]) -> Self {
+        let mut entries: Vec<&ScoreEntry> = leaderboard.iter().filter(|e| e.mode == GameMode::Classic).collect();
+        entries.sort_by_key(|e| e.ts);
+        let mut ratings = Self::default();
+        for e in entries { ratings.apply(&e.name, e.score, e.won); }
+        ratings
+    }
+
+    pub fn save(&self, persist: &PersistHandle) {
+        if let Ok(text) = serde_json::to_string(self) { persist.write("ratings", Self::path(), text); }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PlayerRating> {
+        self.players.get(name)
+    }
+
+    /// Rates one finished run and returns the player's new standing.
+    pub fn apply(&mut self, name: &str, score: i32, won: bool) -> PlayerRating {
+        let entry = self.players.entry(name.to_string()).or_insert(PlayerRating { rating: START_RATING, last_delta: 0.0, runs: 0 });
+        let rating = updated_rating(entry.rating, entry.runs, score, won);
+        *entry = PlayerRating { rating, last_delta: rating - entry.rating, runs: entry.runs + 1 };
+        *entry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MID: f64 = (FLOOR + CEILING) / 2.0;
+
+    #[test]
+    fn deaths_run_from_the_floor_to_the_middle() {
+        assert_eq!(run_target(-200, false), FLOOR);
+        assert_eq!(run_target(-500, false), FLOOR, "clamped below");
+        assert_eq!(run_target(-100, false), (FLOOR + MID) / 2.0);
+        assert_eq!(run_target(0, false), MID);
+    }
+
+    #[test]
+    fn wins_run_from_the_middle_to_the_ceiling() {
+        assert_eq!(run_target(0, true), MID);
+        assert_eq!(run_target(15, true), (MID + CEILING) / 2.0);
+        assert_eq!(run_target(30, true), CEILING);
+        assert_eq!(run_target(50, true), CEILING, "clamped above");
+    }
+
+    #[test]
+    fn new_players_move_faster_until_settled() {
+        let target = run_target(30, true);
+        let before = updated_rating(START_RATING, SETTLE_AFTER - 1, 30, true);
+        assert_eq!(before, START_RATING + K_NEW * (target - START_RATING));
+        let after = updated_rating(START_RATING, SETTLE_AFTER, 30, true);
+        assert_eq!(after, START_RATING + K_SETTLED * (target - START_RATING));
+    }
+
+    #[test]
+    fn apply_counts_runs_and_records_the_delta() {
+        let mut ratings = Ratings::default();
+        let first = ratings.apply("Ann", -200, false);
+        assert_eq!(first.runs, 1);
+        assert_eq!(first.rating, START_RATING + K_NEW * (FLOOR - START_RATING));
+        assert_eq!(first.last_delta, first.rating - START_RATING);
+        for _ in 1..SETTLE_AFTER { ratings.apply("Ann", -200, false); }
+        let settled = ratings.apply("Ann", 30, true);
+        assert_eq!(settled.runs, SETTLE_AFTER + 1);
+        assert!((settled.last_delta - K_SETTLED * (CEILING - (settled.rating - settled.last_delta))).abs() < 1e-9);
+    }
+}
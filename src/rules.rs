@@ -0,0 +1,54 @@
+//! House-rule variants chosen at launch; each active rule gets its own leaderboard bucket.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Diamonds are discarded instead of equipped.
+    pub no_weapons: bool,
+    /// Hearts are discarded instead of drunk.
+    pub no_potions: bool,
+    /// Rooms can't be avoided and fights aren't previewed or double-checked.
+    pub hardcore: bool,
+    /// Newly dealt cards per room that stay face down until selected.
+    pub hidden_cards: u8,
+    /// The Equipped box shows the suits of the cards the next room will deal.
+    pub allow_deck_peek: bool,
+    /// With `allow_deck_peek`, show their ranks too.
+    pub show_full_next_room: bool,
+    /// Added to a Classic victory score for every room survived, rewarding short clears.
+    pub rooms_bonus_per_room: i32,
+    /// Adds half the run's longest streak of clean weapon kills to the final score.
+    pub kill_streak_bonus: bool,
+    /// A weapon breaks after this many fights; `None` keeps weapons until replaced.
+    pub weapon_breaks_after_uses: Option<u8>,
+    /// Every third room ends in a shop: trade the leftover card to weaken the next three monsters.
+    pub enable_shop: bool,
+    /// An avoided room's cards are shuffled among themselves at the bottom of the deck.
+    pub shuffle_avoided_cards: bool,
+}
+
+impl Rules {
+    /// Stable names of the active variants, stored as `ScoreEntry::run_flags`.
+    pub fn flags(&self) -> Vec<String> {
+        let mut flags = Vec::new();
+        if self.no_weapons { flags.push("no_weapons".to_string()); }
+        if self.no_potions { flags.push("no_potions".to_string()); }
+        if self.hardcore { flags.push("hardcore".to_string()); }
+        if self.hidden_cards > 0 { flags.push(format!("hidden_{}", self.hidden_cards)); }
+        if self.rooms_bonus_per_room != 0 { flags.push(format!("room_bonus_{}", self.rooms_bonus_per_room)); }
+        if self.kill_streak_bonus { flags.push("streak_bonus".to_string()); }
+        if self.enable_shop { flags.push("shop".to_string()); }
+        if self.shuffle_avoided_cards { flags.push("shuffle_avoided".to_string()); }
+        if let Some(uses) = self.weapon_breaks_after_uses { flags.push(format!("weapon_uses_{}", uses)); }
+        if self.allow_deck_peek { flags.push(if self.show_full_next_room { "full_peek" } else { "deck_peek" }.to_string()); }
+        flags
+    }
+
+    /// Human-readable list of active variants, e.g. "no weapons, no potions".
+    pub fn describe(&self) -> Option<String> {
+        let flags = self.flags();
+        (!flags.is_empty()).then(|| flags.join(", ").replace('_', " "))
+    }
+}
@@ -0,0 +1,131 @@
+//! Full event logs of finished runs, one JSON file per run, for the Run History screen.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::game::GameEvent;
+
+/// Directory holding one `<ts>.json` file per finished run.
+pub const RUNS_DIR: &str = "scoundrel_runs";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub ts: u64,
+    pub name: String,
+    pub seed: u64,
+    pub score: i32,
+    pub won: bool,
+    #[serde(default)]
+    pub deck: Option<String>, // custom deck id; None for the standard deck
+    #[serde(deserialize_with = "events_compat")]
+    pub events: Vec<GameEvent>,
+}
+
+// Runs saved before avoided rooms were recorded store a bare `"Avoid"`
+fn events_compat<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<GameEvent>, D::Error> {
+    let raw = Vec::<serde_json::Value>::deserialize(deserializer)?;
+    raw.into_iter()
+        .map(|ev| match ev {
+            serde_json::Value::String(s) if s == "Avoid" => Ok(GameEvent::Avoid { cards: Vec::new() }),
+            ev => serde_json::from_value(ev).map_err(serde::de::Error::custom),
+        })
+        .collect()
+}
+
+impl RunRecord {
+    /// Reads a single run file, e.g. for `--dump-run`.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
+
+/// Writes `record` under `RUNS_DIR`, then drops all but the newest `keep` runs.
+pub fn save(record: &RunRecord, keep: usize) -> io::Result<()> {
+    fs::create_dir_all(RUNS_DIR)?;
+    // Several runs can finish within the same second (scripted exports)
+    let mut path = Path::new(RUNS_DIR).join(format!("{}.json", record.ts));
+    let mut n = 1;
+    while path.exists() {
+        path = Path::new(RUNS_DIR).join(format!("{}-{}.json", record.ts, n));
+        n += 1;
+    }
+    fs::write(&path, serde_json::to_string(record)?)?;
+    prune(keep);
+    Ok(())
+}
+
+fn run_files() -> Vec<PathBuf> {
+    let Ok(dir) = fs::read_dir(RUNS_DIR) else { return Vec::new() };
+    dir.flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .collect()
+}
+
+fn prune(keep: usize) {
+    let mut files: Vec<(u64, PathBuf)> = run_files().into_iter().filter_map(|p| Some((file_ts(&p)?, p))).collect();
+    if files.len() <= keep { return; }
+    files.sort();
+    for (_, path) in &files[..files.len() - keep] { let _ = fs::remove_file(path); }
+}
+
+// "<ts>.json" or "<ts>-<n>.json"
+fn file_ts(path: &Path) -> Option<u64> {
+    path.file_stem()?.to_str()?.split('-').next()?.parse().ok()
+}
+
+/// Every readable run, newest first; unreadable files are skipped.
+pub fn load_all() -> Vec<RunRecord> {
+    let mut runs: Vec<RunRecord> = run_files().iter().filter_map(|p| RunRecord::load(p).ok()).collect();
+    runs.sort_by_key(|r| std::cmp::Reverse(r.ts));
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn every_event() -> Vec<GameEvent> {
+        vec![
+            GameEvent::RoomStart { number: 1 },
+            GameEvent::Potion { value: 5, hp_before: 12, hp_after: 17 },
+            GameEvent::PotionDiscarded { value: 3 },
+            GameEvent::PotionOverheal { value: 2, capped_at: 20 },
+            GameEvent::Weapon { value: 7 },
+            GameEvent::WeaponReplaced { old: 7, new: 9 },
+            GameEvent::WeaponDiscarded { value: 4 },
+            GameEvent::Fight { monster: 11, with_weapon: Some(9), damage_taken: 2 },
+            GameEvent::Fight { monster: 6, with_weapon: None, damage_taken: 6 },
+            GameEvent::Avoid { cards: vec!["Ks".parse().expect("test card"), "9d".parse().expect("test card")] },
+            GameEvent::WeaponDestroyed { value: 9, kills: 3 },
+            GameEvent::Abandoned { room: 4, hp: 3 },
+        ]
+    }
+
+    #[test]
+    fn run_record_round_trips_every_event() {
+        let record = RunRecord { ts: 1_700_000_000, name: "Ann".into(), seed: 42, score: -12, won: false, deck: Some("abc".into()), events: every_event() };
+        let text = serde_json::to_string(&record).expect("serializable");
+        let back: RunRecord = serde_json::from_str(&text).expect("reads back");
+        assert_eq!((back.ts, back.name.as_str(), back.seed, back.score, back.won), (record.ts, "Ann", 42, -12, false));
+        assert_eq!(back.deck.as_deref(), Some("abc"));
+        assert_eq!(back.events, record.events);
+    }
+
+    #[test]
+    fn old_bare_avoid_and_missing_deck_still_load() {
+        let text = r#"{"ts":5,"name":"Bo","seed":1,"score":8,"won":true,"events":[{"RoomStart":{"number":1}},"Avoid"]}"#;
+        let record: RunRecord = serde_json::from_str(text).expect("old run file");
+        assert_eq!(record.deck, None);
+        assert_eq!(record.events, vec![GameEvent::RoomStart { number: 1 }, GameEvent::Avoid { cards: Vec::new() }]);
+    }
+
+    #[test]
+    fn file_names_give_the_timestamp() {
+        assert_eq!(file_ts(Path::new("scoundrel_runs/1700.json")), Some(1700));
+        assert_eq!(file_ts(Path::new("scoundrel_runs/1700-2.json")), Some(1700));
+        assert_eq!(file_ts(Path::new("scoundrel_runs/notes.json")), None);
+    }
+}
@@ -0,0 +1,128 @@
+//! Suspended runs, one JSON file per save slot, for the Continue menu.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::cards::Card;
+use crate::game::{GameEvent, GameMode, Player, RoomResult, RunSummary};
+use crate::persist::write_atomic;
+use crate::rules::Rules;
+
+/// Directory holding `slot<N>.json` for each occupied slot.
+pub const SAVES_DIR: &str = "scoundrel_saves";
+
+/// Number of save slots offered by the Continue screen.
+pub const SLOT_COUNT: usize = 3;
+
+/// Everything needed to pick a run up where it was left.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedRun {
+    pub ts: u64, // last autosave
+    pub player_name: String,
+    pub seed: u64,
+    pub game_mode: GameMode,
+    pub rules: Rules,
+    pub deck_id: Option<String>, // custom deck the run was dealt from; None for the standard deck
+    pub player: Player,
+    pub deck: Vec<Card>, // bottom to top
+    pub room: [Option<Card>; 4],
+    pub face_down: [bool; 4],
+    pub selected: usize,
+    pub choices_this_turn: u8,
+    pub avoided_last_turn: bool,
+    pub potion_used_this_turn: bool,
+    pub discard: Vec<Card>,
+    pub last_card_potion_value: Option<u8>,
+    pub history: Vec<GameEvent>,
+    pub cycled: Vec<Card>,
+    #[serde(default)]
+    pub room_summary: Vec<RoomResult>, // empty in saves made before the minimap
+    pub room_number: u32,
+    pub dungeons_cleared: u32,
+    pub stats: RunSummary,
+    pub current_kill_streak: u32,
+    pub max_kill_streak: u32,
+    pub elapsed_secs: u64,
+    pub log: Vec<String>,
+}
+
+/// What the picker shows for one slot.
+#[derive(Debug, Clone)]
+pub enum SlotState {
+    Empty,
+    Corrupt, // the file exists but doesn't parse; it can only be deleted
+    Saved(Box<SavedRun>),
+}
+
+impl SlotState {
+    pub fn is_empty(&self) -> bool { matches!(self, SlotState::Empty) }
+}
+
+fn path(slot: usize) -> PathBuf {
+    Path::new(SAVES_DIR).join(format!("slot{}.json", slot + 1))
+}
+
+pub fn load(slot: usize) -> SlotState {
+    load_from(&path(slot))
+}
+
+// Only a missing file is a free slot; one that can't be read must not be handed to a new run
+fn load_from(path: &Path) -> SlotState {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return SlotState::Empty,
+        Err(_) => return SlotState::Corrupt,
+    };
+    match serde_json::from_str(&text) {
+        Ok(run) => SlotState::Saved(Box::new(run)),
+        Err(_) => SlotState::Corrupt,
+    }
+}
+
+/// Every slot in order.
+pub fn load_all() -> Vec<SlotState> {
+    (0..SLOT_COUNT).map(load).collect()
+}
+
+pub fn save(slot: usize, run: &SavedRun) -> io::Result<()> {
+    fs::create_dir_all(SAVES_DIR)?;
+    write_atomic(&path(slot), &serde_json::to_string(run)?)
+}
+
+/// Empties `slot`; an already empty slot is fine.
+pub fn delete(slot: usize) -> io::Result<()> {
+    match fs::remove_file(path(slot)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("scoundrel-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("temp dir is writable");
+        dir
+    }
+
+    #[test]
+    fn only_a_missing_file_is_an_empty_slot() {
+        let dir = scratch_dir("saves-load");
+        assert!(load_from(&dir.join("slot1.json")).is_empty());
+        let garbage = dir.join("slot2.json");
+        fs::write(&garbage, [0x7b, 0xff, 0xfe, 0x00]).expect("temp dir is writable");
+        assert!(matches!(load_from(&garbage), SlotState::Corrupt), "not UTF-8");
+        let truncated = dir.join("slot3.json");
+        fs::write(&truncated, "{\"ts\": 17").expect("temp dir is writable");
+        assert!(matches!(load_from(&truncated), SlotState::Corrupt));
+        // A directory in the slot's place can't be read as a file either
+        let blocked = dir.join("slot4.json");
+        fs::create_dir(&blocked).expect("temp dir is writable");
+        assert!(matches!(load_from(&blocked), SlotState::Corrupt));
+    }
+}
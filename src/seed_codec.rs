@@ -0,0 +1,87 @@
+//! Text form of run seeds: short base36 codes for sharing, decimal accepted on input.
+
+use thiserror::Error;
+
+const DIGITS: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SeedError {
+    #[error("enter a number or a seed code")]
+    Empty,
+    #[error("'{0}' is not a valid seed character")]
+    InvalidChar(char),
+    #[error("seed is too large")]
+    Overflow,
+}
+
+/// Canonical code for a seed: uppercase base36.
+///
+/// Input that is all digits is read as decimal, so a code that would be all digits is
+/// written in decimal instead; either way `decode(&encode(s)) == Ok(s)`.
+pub fn encode(seed: u64) -> String {
+    let mut n = seed;
+    let mut out = Vec::new();
+    loop {
+        out.push(DIGITS[(n % 36) as usize]);
+        n /= 36;
+        if n == 0 { break; }
+    }
+    out.reverse();
+    let code = String::from_utf8(out).unwrap_or_default();
+    if code.bytes().all(|b| b.is_ascii_digit()) { seed.to_string() } else { code }
+}
+
+/// Parse a decimal number or a base36 code (case-insensitive; spaces and dashes ignored).
+pub fn decode(input: &str) -> Result<u64, SeedError> {
+    let cleaned: String = input.chars().filter(|c| !matches!(c, ' ' | '-' | '_')).collect();
+    if cleaned.is_empty() { return Err(SeedError::Empty); }
+    let radix = if cleaned.chars().all(|c| c.is_ascii_digit()) { 10 } else { 36 };
+    let mut value: u64 = 0;
+    for c in cleaned.chars() {
+        let d = c.to_digit(radix).ok_or(SeedError::InvalidChar(c))?;
+        value = value
+            .checked_mul(radix as u64)
+            .and_then(|v| v.checked_add(d as u64))
+            .ok_or(SeedError::Overflow)?;
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn every_seed_decodes_back_from_its_code() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let edges = [0, 1, 9, 10, 35, 36, 1295, 1296, u32::MAX as u64, u64::MAX - 1, u64::MAX];
+        for seed in edges.into_iter().chain((0..10_000).map(|_| rng.random::<u64>())) {
+            assert_eq!(decode(&encode(seed)), Ok(seed), "seed {} via {}", seed, encode(seed));
+        }
+    }
+
+    #[test]
+    fn all_digit_codes_are_written_in_decimal() {
+        // 36 is "10" in base36, which would read back as ten
+        assert_eq!(encode(36), "36");
+        assert_eq!(encode(35), "Z");
+        assert_eq!(encode(u64::MAX), "3W5E11264SGSF");
+    }
+
+    #[test]
+    fn codes_ignore_case_and_separators() {
+        assert_eq!(decode("3w5e-1126 4sgsf"), Ok(u64::MAX));
+        assert_eq!(decode("z"), Ok(35));
+        assert_eq!(decode(" 42 "), Ok(42));
+    }
+
+    #[test]
+    fn bad_input_is_reported() {
+        assert_eq!(decode(" - "), Err(SeedError::Empty));
+        assert_eq!(decode("AB!C"), Err(SeedError::InvalidChar('!')));
+        assert_eq!(decode("3W5E11264SGSG"), Err(SeedError::Overflow));
+        assert_eq!(decode("18446744073709551616"), Err(SeedError::Overflow));
+    }
+}
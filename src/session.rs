@@ -0,0 +1,132 @@
+//! What was played since the program started, printed to the normal screen on quit so the
+//! session leaves a trace once the alternate screen is gone.
+
+use std::path::Path;
+
+use crate::game::{Game, GamePhase, Outcome};
+
+/// How one scored run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FinishedRun {
+    pub score: i32,
+    pub won: bool,
+    pub abandoned: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SessionSummary {
+    pub runs_started: u32,
+    pub runs_finished: u32,
+    pub best_score: Option<i32>,
+    pub last_run: Option<FinishedRun>,
+    pub signal: Option<i32>, // the session was ended from outside by this signal
+    in_run: bool, // a started run has not been scored yet
+}
+
+impl SessionSummary {
+    /// Follow `game` after every input and tick: a run counts once when it starts and once when it is scored.
+    pub fn observe(&mut self, game: &Game) {
+        let playing = matches!(game.phase, GamePhase::Running | GamePhase::Shop);
+        if !self.in_run && playing && game.score.is_none() {
+            self.runs_started += 1;
+            self.in_run = true;
+        }
+        if self.in_run && let Some(score) = game.score {
+            self.in_run = false;
+            self.runs_finished += 1;
+            self.best_score = Some(self.best_score.map_or(score, |best| best.max(score)));
+            let won = game.outcome == Some(Outcome::Victory);
+            self.last_run = Some(FinishedRun { score, won, abandoned: !won && game.outcome.is_none() });
+        }
+    }
+
+    /// The run in progress was dropped without a score (Ctrl+R starts over from the menu).
+    pub fn leave_run(&mut self) {
+        self.in_run = false;
+        self.last_run = None;
+    }
+
+    /// Plain lines for stdout; empty when no run was played.
+    pub fn lines(&self, scores_path: &Path) -> Vec<String> {
+        if self.runs_started == 0 { return Vec::new(); }
+        let plural = if self.runs_started == 1 { "" } else { "s" };
+        let mut out = vec![format!("Scoundrel session: {} run{} played, {} finished.", self.runs_started, plural, self.runs_finished)];
+        match self.last_run {
+            Some(last) if !self.in_run => {
+                let how = if last.won { "won" } else if last.abandoned { "abandoned" } else { "died" };
+                out.push(format!("Last run: {} with {} points.", how, last.score));
+            }
+            _ => out.push("Last run: left unfinished.".to_string()),
+        }
+        if let Some(best) = self.best_score { out.push(format!("Best score this session: {}.", best)); }
+        if let Some(signal) = self.signal {
+            let saved = if self.in_run { "; the run in progress was saved" } else { "" };
+            out.push(format!("Stopped by signal {}{}.", signal, saved));
+        }
+        out.push(format!("Scores are kept in {}", scores_path.display()));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Rules;
+
+    // Sets `game` to what the app loop would see for one finished run, observing each step
+    fn play_run(session: &mut SessionSummary, game: &mut Game, score: i32, outcome: Option<Outcome>) {
+        game.phase = GamePhase::Running;
+        game.score = None;
+        game.outcome = None;
+        session.observe(game);
+        session.observe(game);
+        game.phase = GamePhase::GameOver;
+        game.score = Some(score);
+        game.outcome = outcome;
+        session.observe(game);
+        session.observe(game);
+    }
+
+    #[test]
+    fn runs_are_counted_once_each_across_a_session() {
+        let mut game = Game::sandboxed(1, Rules::default());
+        let mut session = SessionSummary::default();
+        session.observe(&game);
+        assert!(session.lines(Path::new("scores.json")).is_empty(), "nothing played yet");
+        play_run(&mut session, &mut game, 12, Some(Outcome::Victory));
+        play_run(&mut session, &mut game, -30, Some(Outcome::Death));
+        assert_eq!((session.runs_started, session.runs_finished), (2, 2));
+        assert_eq!(session.best_score, Some(12));
+        assert_eq!(session.last_run, Some(FinishedRun { score: -30, won: false, abandoned: false }));
+        let lines = session.lines(Path::new("scores.json"));
+        assert_eq!(lines[0], "Scoundrel session: 2 runs played, 2 finished.");
+        assert_eq!(lines[1], "Last run: died with -30 points.");
+        assert_eq!(lines[2], "Best score this session: 12.");
+    }
+
+    #[test]
+    fn an_unfinished_run_is_reported_as_such() {
+        let mut game = Game::sandboxed(1, Rules::default());
+        let mut session = SessionSummary::default();
+        play_run(&mut session, &mut game, 5, None);
+        assert_eq!(session.last_run.map(|r| r.abandoned), Some(true));
+        game.phase = GamePhase::Running;
+        game.score = None;
+        session.observe(&game);
+        let lines = session.lines(Path::new("scores.json"));
+        assert_eq!(lines[0], "Scoundrel session: 2 runs played, 1 finished.");
+        assert_eq!(lines[1], "Last run: left unfinished.");
+    }
+
+    #[test]
+    fn a_dropped_run_is_forgotten() {
+        let mut game = Game::sandboxed(1, Rules::default());
+        let mut session = SessionSummary::default();
+        game.phase = GamePhase::Running;
+        session.observe(&game);
+        session.leave_run();
+        game.phase = GamePhase::Menu;
+        session.observe(&game);
+        assert_eq!((session.runs_started, session.runs_finished, session.last_run), (1, 0, None));
+    }
+}
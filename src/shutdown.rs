@@ -0,0 +1,76 @@
+//! Signals that end the session from outside (`kill`, a closing terminal or tmux pane). The
+//! handlers only record which signal arrived; the main loop sees it, saves and restores the
+//! terminal like a normal quit, and the process then exits with the shell's `128 + N` status.
+
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use signal_hook::consts::{SIGINT, SIGTERM};
+
+// No signal yet; signal numbers are never 0
+const NONE: usize = 0;
+
+/// Set by the first stop signal; later ones overwrite it, which is harmless.
+#[derive(Debug, Clone, Default)]
+pub struct Shutdown(Arc<AtomicUsize>);
+
+impl Shutdown {
+    /// Listens for SIGTERM, SIGINT and (on Unix) SIGHUP. Windows only delivers the first two.
+    pub fn install() -> io::Result<Self> {
+        let shutdown = Self::default();
+        #[cfg(unix)]
+        let signals = [SIGTERM, SIGINT, signal_hook::consts::SIGHUP];
+        #[cfg(not(unix))]
+        let signals = [SIGTERM, SIGINT];
+        for signal in signals {
+            signal_hook::flag::register_usize(signal, Arc::clone(&shutdown.0), signal as usize)?;
+        }
+        Ok(shutdown)
+    }
+
+    /// The signal that asked the session to stop, if one has.
+    pub fn requested(&self) -> Option<i32> {
+        match self.0.load(Ordering::Relaxed) {
+            NONE => None,
+            signal => Some(signal as i32),
+        }
+    }
+}
+
+/// Exit status for a session ended by `signal`, as a shell reports a process killed by it.
+pub fn exit_code(signal: i32) -> i32 {
+    128 + signal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Stands in for a signal handler: writes the signal number into the shared flag
+    fn deliver(source: &Shutdown, signal: i32) {
+        source.0.store(signal as usize, Ordering::Relaxed);
+    }
+
+    #[test]
+    fn nothing_is_requested_until_a_signal_arrives() {
+        assert_eq!(Shutdown::default().requested(), None);
+    }
+
+    #[test]
+    fn a_signal_on_any_clone_reaches_the_main_loop() {
+        let shutdown = Shutdown::default();
+        deliver(&shutdown.clone(), SIGTERM);
+        assert_eq!(shutdown.requested(), Some(SIGTERM));
+        assert_eq!(exit_code(SIGTERM), 143);
+    }
+
+    #[test]
+    fn the_latest_signal_wins() {
+        let shutdown = Shutdown::default();
+        deliver(&shutdown, SIGTERM);
+        deliver(&shutdown, SIGINT);
+        assert_eq!(shutdown.requested(), Some(SIGINT));
+        assert_eq!(exit_code(SIGINT), 130);
+    }
+}
@@ -0,0 +1,161 @@
+//! `--simulate N`: built-in strategies play whole runs without a terminal, every strategy on
+//! the same seeds, for comparing them side by side. Nothing is saved; `--csv` writes one row per game.
+
+use std::fs::File;
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+
+use crate::app::RunOptions;
+use crate::cards::{Card, Suit};
+use crate::game::{AvoidStatus, Game, GamePhase, PlayerAction, UseMode};
+use crate::persist::PersistHandle;
+use crate::seed_codec;
+
+/// A run that takes this many actions is stopped and counted as lost (Endless can go on for a long time).
+const MAX_ACTIONS: u32 = 10_000;
+
+/// How a simulated player picks its next move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Always takes the leftmost card, fighting with the weapon when it can.
+    First,
+    /// Avoids rooms that would kill it, drinks when hurt, upgrades weapons, then takes the cheapest fight.
+    Cautious,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 2] = [Strategy::First, Strategy::Cautious];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Strategy::First => "first",
+            Strategy::Cautious => "cautious",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|st| st.name() == s)
+    }
+
+    /// The move this strategy makes next, or `None` when the room is empty.
+    pub fn choose(self, game: &Game) -> Option<PlayerAction> {
+        let cards: Vec<(usize, Card)> = game.room.iter().enumerate().filter_map(|(i, c)| c.map(|c| (i, c))).collect();
+        let take = |slot: usize| PlayerAction::TakeCard { slot, mode: UseMode::Default };
+        let first = cards.first().map(|(slot, _)| take(*slot));
+        if self == Strategy::First { return first; }
+
+        let damage = |slot: usize| game.preview_fight(slot, UseMode::Default).unwrap_or(0);
+        let room_damage: i32 = cards.iter().map(|(slot, _)| damage(*slot)).sum();
+        if game.avoid_status() == AvoidStatus::Ready && room_damage >= game.player.hp {
+            return Some(PlayerAction::AvoidRoom);
+        }
+        let best_of = |suit: Suit| cards.iter().filter(|(_, c)| c.suit == suit).max_by_key(|(_, c)| c.monster_value());
+        if !game.potion_used_this_turn
+            && game.player.hp < game.player.max_hp
+            && let Some((slot, _)) = best_of(Suit::Hearts)
+        {
+            return Some(take(*slot));
+        }
+        let current = game.player.weapon.as_ref().map_or(0, |w| w.value);
+        if let Some((slot, card)) = best_of(Suit::Diamonds)
+            && card.monster_value() > current
+        {
+            return Some(take(*slot));
+        }
+        let cheapest = cards.iter().filter(|(_, c)| c.is_monster()).min_by_key(|(slot, _)| damage(*slot));
+        cheapest.map(|(slot, _)| take(*slot)).or(first)
+    }
+}
+
+/// One simulated game. Column order is the CSV schema and stays fixed:
+///
+/// ```text
+/// seed            seed code, as shown in the status bar and accepted by --seed
+/// strategy        strategy name (first, cautious)
+/// won             true when the dungeon was cleared
+/// score           final score, as the leaderboard would record it
+/// rooms           rooms completed
+/// monsters_slain  monsters killed
+/// potions_used    potions drunk (wasted ones not counted)
+/// actions         moves taken, avoids included
+/// ```
+#[derive(Debug, Serialize)]
+struct SimRow {
+    seed: String,
+    strategy: &'static str,
+    won: bool,
+    score: i32,
+    rooms: u32,
+    monsters_slain: u32,
+    potions_used: u32,
+    actions: u32,
+}
+
+/// Play `games` seeds with every strategy, print a summary per strategy and, with `--csv`, write every game.
+/// Seeds count up from `--seed` (random when not given), so a run can be repeated exactly.
+pub fn run(mut game: Game, games: u32, opts: &RunOptions) -> Result<()> {
+    make_unattended(&mut game);
+    if let Some(name) = &opts.name { game.player_name = name.clone(); }
+    let strategies = if opts.strategies.is_empty() { vec![Strategy::First] } else { opts.strategies.clone() };
+    let base = opts.seed.unwrap_or_else(rand::random);
+
+    let mut rows = Vec::new();
+    for i in 0..games as u64 {
+        let seed = base.wrapping_add(i);
+        for &strategy in &strategies {
+            rows.push(play(&mut game, seed, strategy)?);
+        }
+    }
+
+    if let Some(path) = &opts.csv {
+        let file = File::create(path).with_context(|| format!("creating {}", path.display()))?;
+        let mut csv = csv::Writer::from_writer(file);
+        for row in &rows { csv.serialize(row)?; }
+        csv.flush()?;
+    }
+    for strategy in strategies {
+        let mine: Vec<&SimRow> = rows.iter().filter(|r| r.strategy == strategy.name()).collect();
+        let wins = mine.iter().filter(|r| r.won).count();
+        let mean = mine.iter().map(|r| r.score as f64).sum::<f64>() / mine.len().max(1) as f64;
+        println!("{}: {}/{} won, mean score {:.1}", strategy.name(), wins, mine.len(), mean);
+    }
+    if let Some(path) = &opts.csv { println!("{} games written to {}", rows.len(), path.display()); }
+    Ok(())
+}
+
+/// Set up `game` for a bot: nothing it does is saved and no move stops to ask first.
+pub fn make_unattended(game: &mut Game) {
+    game.persist = PersistHandle::discard();
+    game.config.confirm_potion_waste = false;
+    game.config.confirm_lethal_fight = false;
+    game.config.confirm_weapon_swap = false;
+}
+
+fn play(game: &mut Game, seed: u64, strategy: Strategy) -> Result<SimRow> {
+    game.next_seed = Some(seed);
+    game.new_run();
+    let mut actions = 0;
+    while matches!(game.phase, GamePhase::Running | GamePhase::Shop) && actions < MAX_ACTIONS {
+        // Strategies never trade
+        if game.phase == GamePhase::Shop {
+            game.apply_action(PlayerAction::ShopLeave)?;
+            continue;
+        }
+        let Some(action) = strategy.choose(game) else { break; };
+        if let Err(e) = game.apply_action(action) {
+            bail!("seed {}: {} strategy made a rejected move: {}", seed_codec::encode(seed), strategy.name(), e);
+        }
+        actions += 1;
+    }
+    Ok(SimRow {
+        seed: seed_codec::encode(seed),
+        strategy: strategy.name(),
+        won: game.phase == GamePhase::GameOver && game.player.hp > 0,
+        score: game.score.unwrap_or_else(|| game.projected_score().1),
+        rooms: game.stats.rooms_completed,
+        monsters_slain: game.stats.monsters_killed,
+        potions_used: game.stats.potions_used,
+        actions,
+    })
+}
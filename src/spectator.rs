@@ -0,0 +1,103 @@
+//! `--state-output`: streams `PublicState` JSON lines to a file or FIFO for overlay tools.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, SyncSender};
+use std::thread;
+
+use crate::game::PublicState;
+
+// Lines buffered while the reader is slow or absent; past this new states are not queued
+// until it drains, and the latest one is sent then
+const BACKLOG: usize = 64;
+
+pub struct StateWriter {
+    tx: SyncSender<String>,
+    last: Option<PublicState>,
+}
+
+impl StateWriter {
+    /// Starts the writer thread. Opening a FIFO blocks until a reader attaches, which only
+    /// ever stalls that thread; the game keeps running and drops states meanwhile.
+    pub fn spawn(path: PathBuf) -> Self {
+        let (tx, rx) = mpsc::sync_channel::<String>(BACKLOG);
+        thread::spawn(move || {
+            let open = || OpenOptions::new().create(true).append(true).open(&path).ok();
+            let mut out: Option<File> = None;
+            for line in rx {
+                if out.is_none() { out = open(); }
+                // A reader that went away (broken pipe) gets a fresh open on the next state
+                if let Some(file) = out.as_mut() && writeln!(file, "{}", line).is_err() {
+                    out = None;
+                }
+            }
+        });
+        Self { tx, last: None }
+    }
+
+    /// Queue `state` if it differs from the last one queued. A state that finds the backlog full
+    /// is not remembered, so the next publish offers it (or a newer one) again.
+    pub fn publish(&mut self, state: PublicState) {
+        if self.last.as_ref() == Some(&state) { return; }
+        let Ok(line) = serde_json::to_string(&state) else { return };
+        if self.tx.try_send(line).is_ok() { self.last = Some(state); }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::fixtures::dealt as running;
+    use crate::game::{PlayerAction, UseMode};
+    use serde_json::json;
+
+    #[test]
+    fn public_state_has_the_documented_shape() {
+        let mut game = running("7d 5c 4h 2s 9c");
+        game.apply_action(PlayerAction::TakeCard { slot: 0, mode: UseMode::Default }).expect("slot holds a card");
+        let state = serde_json::to_value(game.public_state()).expect("serializable");
+        let expected = json!({
+            "phase": "Running",
+            "player": "Tester",
+            "hp": 20,
+            "max_hp": 20,
+            "room_number": 1,
+            "room": [
+                null,
+                {"card": "5♣", "kind": "monster", "value": 5},
+                {"card": "4♥", "kind": "potion", "value": 4},
+                {"card": "2♠", "kind": "monster", "value": 2},
+            ],
+            "selected": 0,
+            "weapon": {"value": 7, "last_monster": null, "slain": 0},
+            "deck_remaining": 1,
+            "monsters_remaining": 16,
+            "potion_used": false,
+            "can_avoid": false,
+            "score": null,
+            "last_event": {"Weapon": {"value": 7}},
+        });
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn state_dropped_on_a_full_backlog_is_offered_again() {
+        let (tx, rx) = mpsc::sync_channel(1);
+        let mut writer = StateWriter { tx, last: None };
+        let mut game = running("7d 5c 4h 2s 9c");
+        let first = game.public_state();
+        writer.publish(first.clone());
+        game.apply_action(PlayerAction::SelectSlot(2)).expect("slot holds a card");
+        let second = game.public_state();
+        // The backlog is full: this one is dropped, not marked as written
+        writer.publish(second.clone());
+        assert_eq!(rx.try_recv().ok(), serde_json::to_string(&first).ok());
+        assert!(rx.try_recv().is_err());
+        writer.publish(second.clone());
+        assert_eq!(rx.try_recv().ok(), serde_json::to_string(&second).ok());
+        // Unchanged states are still sent once
+        writer.publish(second);
+        assert!(rx.try_recv().is_err());
+    }
+}
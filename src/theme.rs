@@ -0,0 +1,267 @@
+use ratatui::style::Color;
+use ratatui::widgets::BorderType;
+use serde::{Deserialize, Serialize};
+
+use crate::cards::Suit;
+
+/// Colors shared by the UI widgets. Kept separate from layout code so
+/// alternative palettes can be swapped in without touching `ui.rs`.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub potion: Color,
+    pub weapon: Color,
+    pub enemy: Color,
+    pub background_dot: Color,
+    pub background_pattern: BackgroundPattern,
+    pub hp_orange: Color, // HP between half and three quarters
+    pub bronze: Color,    // third place on the podium
+    pub gold: Color,      // the victory screen
+    pub color_level: ColorLevel,
+    pub plain: bool, // --plain: screen-reader layout, no animations
+    pub colorblind: bool, // shapes and a blue/yellow HP scale where color alone told things apart
+}
+
+const ORANGE: Color = Color::Rgb(255, 165, 0);
+const BRONZE: Color = Color::Rgb(205, 127, 50);
+const GOLD: Color = Color::Rgb(255, 215, 0);
+// Blue-to-yellow HP scale for colorblind mode; it stays ordered by brightness for deuteranopia
+const HP_SAFE: [Color; 4] = [Color::Rgb(86, 180, 233), Color::Rgb(0, 114, 178), Color::Rgb(240, 228, 66), Color::Rgb(230, 159, 0)];
+
+impl Theme {
+    /// Re-derives the RGB colors for a terminal that can show `level`.
+    pub fn set_color_level(&mut self, level: ColorLevel) {
+        self.color_level = level;
+        self.hp_orange = level.adapt(ORANGE);
+        self.bronze = level.adapt(BRONZE);
+        self.gold = level.adapt(GOLD);
+    }
+
+    /// HP color for `pct` of max HP: full, from three quarters, from half, below half.
+    pub fn hp_color(&self, pct: f32) -> Color {
+        let tier = if (pct - 1.0).abs() < f32::EPSILON {
+            0
+        } else if pct >= 0.75 {
+            1
+        } else if pct >= 0.5 {
+            2
+        } else {
+            3
+        };
+        if self.colorblind {
+            self.color_level.adapt(HP_SAFE[tier])
+        } else {
+            [Color::LightGreen, Color::Yellow, self.hp_orange, Color::LightRed][tier]
+        }
+    }
+
+    /// Corner mark giving a card's kind by shape in colorblind mode: ⚔ enemy, † weapon, + potion.
+    pub fn kind_mark(&self, suit: Suit) -> &'static str {
+        if !self.colorblind { return ""; }
+        match suit {
+            Suit::Clubs | Suit::Spades => "⚔",
+            Suit::Diamonds => "†",
+            Suit::Hearts => "+",
+        }
+    }
+
+    /// Outline of the selected room cell; doubled in colorblind mode so yellow isn't the only cue.
+    pub fn selected_border(&self) -> BorderType {
+        if self.colorblind { BorderType::Double } else { BorderType::Plain }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            potion: Color::LightRed,
+            weapon: Color::LightBlue,
+            enemy: Color::LightMagenta,
+            background_dot: Color::DarkGray,
+            background_pattern: BackgroundPattern::Dots,
+            hp_orange: ORANGE,
+            bronze: BRONZE,
+            gold: GOLD,
+            color_level: ColorLevel::TrueColor,
+            plain: false,
+            colorblind: false,
+        }
+    }
+}
+
+/// Texture drawn behind the screens, in `background_dot` color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundPattern {
+    /// Staggered `·` dots.
+    #[default]
+    Dots,
+    /// Alternating `░` and blank cells.
+    Checkerboard,
+    /// `/` stripes.
+    DiagonalLines,
+    /// Nothing, for terminals where the patterns flicker.
+    Blank,
+}
+
+/// Colors the terminal can display; RGB colors are reduced to the nearest one it has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorLevel {
+    /// The 16 named ANSI colors.
+    Basic,
+    /// The xterm 256-color palette.
+    Ansi256,
+    #[default]
+    TrueColor,
+}
+
+// xterm's defaults for the 16 named colors, which most terminals keep
+const BASIC_PALETTE: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+// Channel values of the 6x6x6 cube at indices 16..=231
+const CUBE_STEPS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl ColorLevel {
+    /// Guesses from `COLORTERM` and `TERM` the way most terminal apps do.
+    pub fn detect() -> ColorLevel {
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+        let term = std::env::var("TERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorLevel::TrueColor
+        } else if term.contains("256color") {
+            ColorLevel::Ansi256
+        } else {
+            ColorLevel::Basic
+        }
+    }
+
+    /// Parses a `--color` value: `16`, `256` or `truecolor`.
+    pub fn parse(s: &str) -> Option<ColorLevel> {
+        match s {
+            "16" => Some(ColorLevel::Basic),
+            "256" => Some(ColorLevel::Ansi256),
+            "truecolor" | "24bit" => Some(ColorLevel::TrueColor),
+            _ => None,
+        }
+    }
+
+    /// The closest color to `color` this level can show; only RGB colors ever change.
+    pub fn adapt(self, color: Color) -> Color {
+        let Color::Rgb(r, g, b) = color else { return color; };
+        match self {
+            ColorLevel::TrueColor => color,
+            ColorLevel::Ansi256 => nearest_indexed(r, g, b),
+            ColorLevel::Basic => {
+                BASIC_PALETTE.iter().min_by_key(|(_, rgb)| distance((r, g, b), *rgb)).map(|(c, _)| *c).unwrap_or(color)
+            }
+        }
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+// Best of the nearest cube color and the nearest of the 24 grays (232..=255)
+fn nearest_indexed(r: u8, g: u8, b: u8) -> Color {
+    let step = |v: u8| (0..6).min_by_key(|&i| (CUBE_STEPS[i] as i32 - v as i32).abs()).unwrap_or(0);
+    let (ri, gi, bi) = (step(r), step(g), step(b));
+    let cube = (CUBE_STEPS[ri], CUBE_STEPS[gi], CUBE_STEPS[bi]);
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_i = ((avg.saturating_sub(8) + 5) / 10).min(23) as u8;
+    let gray_v = 8 + 10 * gray_i;
+    if distance((r, g, b), (gray_v, gray_v, gray_v)) < distance((r, g, b), cube) {
+        Color::Indexed(232 + gray_i)
+    } else {
+        Color::Indexed(16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colorblind() -> Theme {
+        Theme { colorblind: true, color_level: ColorLevel::TrueColor, ..Theme::default() }
+    }
+
+    #[test]
+    fn colorblind_hp_scale_has_no_red_or_green() {
+        let tiers: Vec<Color> = [1.0, 0.8, 0.6, 0.2].iter().map(|&pct| colorblind().hp_color(pct)).collect();
+        assert_eq!(tiers, HP_SAFE.to_vec());
+        let normal = Theme::default();
+        assert_eq!(normal.hp_color(1.0), Color::LightGreen);
+        assert_eq!(normal.hp_color(0.2), Color::LightRed);
+    }
+
+    #[test]
+    fn kind_marks_and_double_border_only_in_colorblind_mode() {
+        let marks: Vec<&str> = [Suit::Clubs, Suit::Spades, Suit::Diamonds, Suit::Hearts].iter().map(|&s| colorblind().kind_mark(s)).collect();
+        assert_eq!(marks, vec!["⚔", "⚔", "†", "+"]);
+        assert_eq!(colorblind().selected_border(), BorderType::Double);
+        let normal = Theme::default();
+        assert_eq!(normal.kind_mark(Suit::Clubs), "");
+        assert_eq!(normal.selected_border(), BorderType::Plain);
+    }
+
+    #[test]
+    fn orange_and_bronze_stay_distinct_on_256_colors() {
+        let level = ColorLevel::Ansi256;
+        assert_eq!(level.adapt(ORANGE), Color::Indexed(214));
+        assert_eq!(level.adapt(BRONZE), Color::Indexed(173));
+        let mut theme = Theme::default();
+        theme.set_color_level(level);
+        let tiers: Vec<Color> = [1.0, 0.8, 0.6, 0.2].iter().map(|&pct| theme.hp_color(pct)).collect();
+        assert_eq!(tiers[2], Color::Indexed(214));
+        assert!(tiers.iter().all(|c| !matches!(c, Color::Rgb(..))));
+        let podium = [theme.gold, Color::Gray, theme.bronze];
+        assert!(podium[0] != podium[1] && podium[1] != podium[2] && podium[0] != podium[2], "{podium:?}");
+    }
+
+    #[test]
+    fn basic_level_picks_a_named_color() {
+        let level = ColorLevel::Basic;
+        assert_eq!(level.adapt(GOLD), Color::LightYellow);
+        assert_eq!(level.adapt(BRONZE), Color::Yellow);
+        assert_eq!(level.adapt(Color::Rgb(0, 0, 0)), Color::Black);
+        assert_eq!(level.adapt(Color::Rgb(250, 250, 250)), Color::White);
+        // Grays go to the gray ramp rather than a tinted cube corner
+        assert_eq!(ColorLevel::Ansi256.adapt(Color::Rgb(128, 128, 128)), Color::Indexed(244));
+    }
+
+    #[test]
+    fn only_rgb_colors_are_adapted() {
+        for level in [ColorLevel::Basic, ColorLevel::Ansi256, ColorLevel::TrueColor] {
+            for color in [Color::LightRed, Color::Indexed(42), Color::Reset] {
+                assert_eq!(level.adapt(color), color);
+            }
+        }
+        assert_eq!(ColorLevel::TrueColor.adapt(ORANGE), ORANGE);
+    }
+
+    #[test]
+    fn color_flag_values() {
+        assert_eq!(ColorLevel::parse("16"), Some(ColorLevel::Basic));
+        assert_eq!(ColorLevel::parse("256"), Some(ColorLevel::Ansi256));
+        assert_eq!(ColorLevel::parse("truecolor"), Some(ColorLevel::TrueColor));
+        assert_eq!(ColorLevel::parse("24bit"), Some(ColorLevel::TrueColor));
+        assert_eq!(ColorLevel::parse("8"), None);
+    }
+}
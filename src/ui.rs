@@ -1,38 +1,130 @@
 use ratatui::Frame;
+use ratatui::buffer::Buffer;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span, Text};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Row, Table, Tabs, Widget, Wrap};
 use ratatui::widgets::block::BorderType;
 
 use crate::cards::{Card, Rank, Suit};
-use crate::game::{Game, GameEvent, GamePhase};
+use crate::config::{HelpTab, RoomLayout};
+use crate::rating::PlayerRating;
+use crate::rules::Rules;
+use crate::saves::SlotState;
+use crate::game::{format_duration, BestDelta, format_ts, now_ts, seed_of_the_week, AnimationPhase, AnimationState, AvoidStatus, Outcome, FeedbackTarget, Game, GameEvent, GamePhase, RenderedLayout, GameMode, RoomResult, RunSummary, ScoreEntry, UseMode, ENDLESS_DUNGEON_BONUS, FLIP_FRAMES, MENU_ITEMS};
+use crate::seed_codec;
+use crate::theme::{BackgroundPattern, Theme};
 
-pub fn draw(f: &mut Frame, game: &Game) {
+/// Collects widget positions while drawing; the app stores the result on `Game::last_layout`.
+#[derive(Debug, Default)]
+pub struct LayoutCapture {
+    pub layout: RenderedLayout,
+}
+
+/// Draws the full UI into an in-memory buffer, for tests and tooling built with the `testing` feature.
+#[cfg(any(test, feature = "testing"))]
+pub struct TestRenderer {
+    terminal: ratatui::Terminal<ratatui::backend::TestBackend>,
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl TestRenderer {
+    pub fn new(width: u16, height: u16) -> Self {
+        let terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height)).expect("test backend never fails");
+        Self { terminal }
+    }
+
+    /// One `draw` of `game`, returned as a string per screen row; a wide character takes its cells once.
+    pub fn render(&mut self, game: &Game) -> Vec<String> {
+        self.terminal.draw(|f| draw(f, game, None, &mut LayoutCapture::default())).expect("test backend never fails");
+        let buffer = self.terminal.backend().buffer();
+        let area = buffer.area;
+        (area.top()..area.bottom())
+            .map(|y| {
+                let mut row = String::new();
+                let mut skip = 0;
+                for x in area.left()..area.right() {
+                    if skip > 0 { skip -= 1; continue; }
+                    let symbol = buffer[(x, y)].symbol();
+                    skip = Span::raw(symbol).width().saturating_sub(1);
+                    row.push_str(symbol);
+                }
+                row
+            })
+            .collect()
+    }
+
+    /// The cell at `row`, `col` from the last `render`, for checking colors and modifiers.
+    pub fn cell_at(&self, row: u16, col: u16) -> Option<ratatui::buffer::Cell> {
+        self.terminal.backend().buffer().cell((col, row)).cloned()
+    }
+}
+
+/// The String-per-row `Paragraph` the background was drawn with before `SubtlePattern`: the
+/// reference its output is checked against and the baseline of the background benchmark.
+#[cfg(any(test, feature = "testing"))]
+pub fn paragraph_pattern(f: &mut Frame, area: Rect, pattern: BackgroundPattern, color: Color) {
+    let lines: Vec<Line> = (0..area.height as usize).map(|y| {
+        let s: String = (0..area.width as usize).map(|x| match pattern {
+            BackgroundPattern::Dots => if (x + y).is_multiple_of(2) { '·' } else { ' ' },
+            BackgroundPattern::Checkerboard => if (x + y).is_multiple_of(2) { '░' } else { ' ' },
+            BackgroundPattern::DiagonalLines => if (x + y).is_multiple_of(4) { '/' } else { ' ' },
+            BackgroundPattern::Blank => ' ',
+        }).collect();
+        Line::from(Span::styled(s, Style::default().fg(color)))
+    }).collect();
+    f.render_widget(Paragraph::new(Text::from(lines)), area);
+}
+
+pub fn draw(f: &mut Frame, game: &Game, demo: Option<&Game>, capture: &mut LayoutCapture) {
     let size = f.area();
     match game.phase {
         GamePhase::Menu => {
-            draw_menu(f, size, game);
-            if game.show_help { draw_help(f, centered_rect(70, 70, size)); }
+            draw_menu(f, size, game, demo);
+            if game.show_help { draw_help(f, help_rect(game, size), game, game.phase); }
         }
         GamePhase::NameEntry => {
             draw_name_entry(f, size, game);
-            if game.show_help { draw_help(f, centered_rect(70, 70, size)); }
+            if game.show_help { draw_help(f, help_rect(game, size), game, game.phase); }
+        }
+        GamePhase::SeedEntry => {
+            draw_seed_entry(f, size, game);
+            if game.show_help { draw_help(f, help_rect(game, size), game, game.phase); }
         }
         GamePhase::Leaderboard => {
             draw_leaderboard(f, size, game);
-            if game.show_help { draw_help(f, centered_rect(70, 70, size)); }
+            if game.show_help { draw_help(f, help_rect(game, size), game, game.phase); }
+        }
+        GamePhase::RunHistory => {
+            draw_run_history(f, size, game);
+            if game.show_help { draw_help(f, help_rect(game, size), game, game.phase); }
         }
         GamePhase::GameOver => {
             draw_game_over(f, size, game);
-            if game.show_help { draw_help(f, centered_rect(70, 70, size)); }
+            if game.show_help { draw_help(f, help_rect(game, size), game, game.phase); }
+        }
+        GamePhase::Changelog => {
+            draw_changelog(f, size, game);
+            if game.show_help { draw_help(f, help_rect(game, size), game, game.phase); }
+        }
+        GamePhase::SaveSlots => {
+            draw_save_slots(f, size, game);
+            if game.show_help { draw_help(f, help_rect(game, size), game, game.phase); }
+        }
+        GamePhase::Shop => {
+            draw_shop(f, size, game);
+            if game.show_help { draw_help(f, help_rect(game, size), game, game.phase); }
         }
         GamePhase::Running => {
             // Outer bordered frame for consistent visual identity
-            let outer = Block::default()
+            let mut outer = Block::default()
                 .borders(Borders::ALL)
-                .title("Scoundrel")
+                .title(format!("Scoundrel — Room {}", game.room_number))
                 .border_style(Style::default().fg(Color::White));
+            if game.rules.hardcore {
+                let badge = Span::styled(" HARDCORE ", Style::default().fg(Color::White).bg(Color::Red).add_modifier(Modifier::BOLD));
+                outer = outer.title(Line::from(badge).right_aligned());
+            }
             let inner = outer.inner(size);
             f.render_widget(outer, size);
 
@@ -45,36 +137,93 @@ pub fn draw(f: &mut Frame, game: &Game) {
                     Constraint::Percentage(37),
                 ])
                 .split(inner);
-            draw_room(f, layout[0], game);
+            // One line under the room sums up its threat; hardcore shows no such help
+            if game.rules.hardcore {
+                draw_room(f, layout[0], game, capture);
+            } else {
+                let room = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(0), Constraint::Length(1)])
+                    .split(layout[0]);
+                draw_room(f, room[0], game, capture);
+                draw_room_summary(f, room[1], game);
+            }
             draw_status(f, layout[1], game);
             draw_equipped(f, layout[2], game);
-            if game.show_help { draw_help(f, centered_rect(70, 70, inner)); }
-            // Bottom-border right-aligned help hint on the outer frame
-            let border_hint_area = Rect {
-                x: size.x.saturating_add(1),
-                y: size.y.saturating_add(size.height.saturating_sub(1)),
-                width: size.width.saturating_sub(2),
-                height: 1,
-            };
-            let hint = Paragraph::new(Span::styled("? - help", Style::default().fg(Color::Gray))).alignment(Alignment::Right);
-            f.render_widget(hint, border_hint_area);
+            if game.show_help { draw_help(f, help_rect(game, inner), game, game.phase); }
+            draw_footer(f, size, game);
         }
     }
+    if game.show_welcome {
+        draw_welcome(f, size, game);
+    }
+    if let Some(confirm) = game.pending_confirm {
+        draw_confirm(f, size, &confirm.prompt(game));
+    }
+    if let Some((text, _)) = &game.notice {
+        draw_notice(f, size, text);
+    }
+}
+
+// Transient toast in the top-right corner, over everything else
+fn draw_notice(f: &mut Frame, area: Rect, text: &str) {
+    let w = (text.chars().count() as u16 + 4).min(area.width);
+    let rect = Rect { x: area.right().saturating_sub(w + 1), y: area.y.saturating_add(1), width: w, height: 3.min(area.height) };
+    let block = Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan));
+    f.render_widget(Clear, rect);
+    f.render_widget(Paragraph::new(text.to_string()).alignment(Alignment::Center).block(block), rect);
+}
+
+// One-time first-run popup: the three card types in a nutshell
+fn draw_welcome(f: &mut Frame, area: Rect, game: &Game) {
+    let theme = &game.theme;
+    let inner = centered_popup(f, "Welcome to Scoundrel", 58, 12, area, Color::Cyan);
+    let kind = |label: &'static str, color: Color, text: &'static str| {
+        Line::from(vec![
+            Span::styled(label, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::raw(format!("{:<27}", text)), // equal widths keep the three rows lined up
+        ])
+    };
+    let text = Text::from(vec![
+        Line::from("Clear the dungeon four cards at a time."),
+        Line::from(""),
+        kind("Monsters ♠♣ ", theme.enemy, "hit you for their value"),
+        kind("Weapons  ♦  ", theme.weapon, "soften the blows they block"),
+        kind("Potions  ♥  ", theme.potion, "heal, one per room"),
+        Line::from(""),
+        Line::from(Span::styled("? shows the full rules at any time", Style::default().fg(Color::Gray))),
+        Line::from(""),
+        Line::from(Span::styled("Press any key to start", Style::default().fg(Color::Gray))),
+    ]);
+    f.render_widget(Paragraph::new(text).alignment(Alignment::Center), inner);
+}
+
+// Small y/n popup centered on screen
+fn draw_confirm(f: &mut Frame, area: Rect, prompt: &str) {
+    let w = (prompt.chars().count() as u16 + 6).max(30);
+    let inner = centered_popup(f, "Confirm", w, 5, area, Color::Yellow);
+    let text = Text::from(vec![
+        Line::from(Span::styled(prompt.to_string(), Style::default().add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        Line::from(Span::styled("y - yes   n - no", Style::default().fg(Color::Gray))),
+    ]);
+    f.render_widget(Paragraph::new(text).alignment(Alignment::Center), inner);
 }
 
-fn draw_menu(f: &mut Frame, area: Rect, game: &Game) {
+fn draw_menu(f: &mut Frame, area: Rect, game: &Game, demo: Option<&Game>) {
     // Outer frame
     let outer = Block::default()
         .borders(Borders::ALL)
-        .title("Scoundrel")
+        .title(format!("Scoundrel v{}", env!("CARGO_PKG_VERSION")))
         .border_style(Style::default().fg(Color::White));
     let inner = outer.inner(area);
     f.render_widget(outer, area);
     // fill subtle dots across the entire Scoundrel box background
-    render_subtle_pattern(f, inner);
+    render_subtle_pattern(f, inner, game.theme.background_pattern, game.theme.background_dot);
 
     // Center a box with ASCII art + options and render subtle background dots inside it
-    let content = centered_rect_fixed(54, 12, inner);
+    let content = centered_rect_fixed(54, 15, inner);
+    if let Some(demo) = demo { draw_attract_demo(f, inner, content, demo); }
     let v = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -83,6 +232,11 @@ fn draw_menu(f: &mut Frame, area: Rect, game: &Game) {
             Constraint::Length(1), // option 1
             Constraint::Length(1), // option 2
             Constraint::Length(1), // option 3
+            Constraint::Length(1), // option 4
+            Constraint::Length(1), // option 5
+            Constraint::Length(1), // option 6
+            Constraint::Length(1), // spacer
+            Constraint::Length(1), // seed of the week
             Constraint::Min(0),
         ])
         .split(content);
@@ -95,13 +249,10 @@ fn draw_menu(f: &mut Frame, area: Rect, game: &Game) {
         "  ___) | (_| (_) | |_| | | | | (_| | | |  __/ |",
         " |____/ \\___\\___/ \\__,_|_| |_|\\__,_|_|  \\___|_|",
     ];
-    let art_lines: Vec<Line> = art.into_iter().map(|s| Line::from(Span::raw(s))).collect();
-    let p_art = Paragraph::new(Text::from(art_lines)).alignment(Alignment::Center);
-    f.render_widget(p_art, v[0]);
+    draw_ascii_banner(f, v[0], &art, Color::White);
 
     // Options
-    let opts = ["New Game", "Leaderboard", "Quit"];
-    for (i, label) in opts.iter().enumerate() {
+    for (i, label) in MENU_ITEMS.iter().enumerate() {
         let style = if game.menu_selected == i {
             Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
         } else {
@@ -110,61 +261,134 @@ fn draw_menu(f: &mut Frame, area: Rect, game: &Game) {
         let p = Paragraph::new(Line::from(Span::styled(*label, style))).alignment(Alignment::Center);
         f.render_widget(p, v[2 + i]);
     }
+    let (week, seed) = seed_of_the_week(now_ts());
+    let weekly = Line::from(vec![
+        Span::styled(format!("Seed of the week {}: ", week), Style::default().fg(Color::Gray)),
+        Span::styled(seed_codec::encode(seed), Style::default().fg(Color::Yellow)),
+        Span::styled(" — w to play", Style::default().fg(Color::Gray)),
+    ]);
+    f.render_widget(Paragraph::new(weekly).alignment(Alignment::Center), v[9]);
 
-    // Bottom-right minimal help hint on the border line itself
-    let border_hint_area = Rect {
-        x: area.x.saturating_add(1),
-        y: area.y.saturating_add(area.height.saturating_sub(1)),
-        width: area.width.saturating_sub(2),
-        height: 1,
-    };
-    let hint = Paragraph::new(Span::styled("? - help", Style::default().fg(Color::Gray))).alignment(Alignment::Right);
-    f.render_widget(hint, border_hint_area);
+    draw_footer(f, area, game);
 
     // No menu helpers at bottom-right; help is available via '?'
 }
 
+// The idle demo's room and status, dimmed, with the menu's own box cleared back out of it
+fn draw_attract_demo(f: &mut Frame, area: Rect, menu: Rect, demo: &Game) {
+    let v = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+    draw_room(f, v[0], demo, &mut LayoutCapture::default());
+    draw_status(f, v[1], demo);
+    let buf = f.buffer_mut();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            cell.set_fg(Color::DarkGray);
+            cell.set_bg(Color::Reset);
+            cell.modifier = Modifier::DIM;
+        }
+    }
+    let pad = Rect { x: menu.x.saturating_sub(2), y: menu.y.saturating_sub(1), width: menu.width + 4, height: menu.height + 2 }.intersection(area);
+    f.render_widget(Clear, pad);
+}
+
+// Center a block of ASCII art in `area`, sized to its longest line
+fn draw_ascii_banner(f: &mut Frame, area: Rect, lines: &[&str], color: Color) {
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as u16;
+    let rect = centered_rect_fixed(width, lines.len() as u16, area);
+    let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+    let text: Vec<Line> = lines.iter().map(|l| Line::from(Span::styled(*l, style))).collect();
+    f.render_widget(Paragraph::new(Text::from(text)), rect);
+}
+
 fn draw_name_entry(f: &mut Frame, area: Rect, game: &Game) {
+    let hardcore = if game.rules.hardcore { "on" } else { "off" };
+    let mode = Span::styled(
+        format!("Tab mode: {} · Shift+Tab hardcore: {}", game.game_mode.label(), hardcore),
+        Style::default().fg(Color::Gray),
+    );
+    // The remembered name is shown selected until the player types over it or accepts it
+    let style = if game.name_prefilled {
+        Style::default().fg(Color::Black).bg(Color::Cyan)
+    } else {
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+    };
+    let name = Span::styled(game.name_input.clone(), style);
+    let mut notes = Vec::new();
+    if !game.recent_names.is_empty() {
+        let text = match game.name_suggestion {
+            Some(i) => format!("↑↓ recent names: {}/{}", i + 1, game.recent_names.len()),
+            None => format!("↑↓ recent names ({})", game.recent_names.len()),
+        };
+        notes.push(Line::from(Span::styled(text, Style::default().fg(Color::DarkGray))));
+    }
+    notes.push(Line::from(mode));
+    draw_text_entry(f, area, game, "Enter your name", "Type your run name and press Enter", name, notes);
+}
+
+fn draw_seed_entry(f: &mut Frame, area: Rect, game: &Game) {
+    draw_text_entry(
+        f,
+        area,
+        game,
+        "Custom seed",
+        "Seed number or code, Enter to start, Esc for menu",
+        Span::styled(game.seed_input.clone(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        game.seed_error.iter().map(|err| Line::from(Span::styled(err.clone(), Style::default().fg(Color::LightRed)))).collect(),
+    );
+}
+
+// Shared input screen for the name and seed prompts; `notes` (an error, the run mode) are shown under the input
+fn draw_text_entry(f: &mut Frame, area: Rect, game: &Game, title: &str, prompt: &str, value: Span<'static>, notes: Vec<Line<'static>>) {
     let block = Block::default()
-        .title("Enter your name")
+        .title(title.to_string())
         .borders(Borders::ALL);
     let outer_inner = block.inner(area);
     f.render_widget(block, area);
     // Subtle background dots across the entire name box
-    render_subtle_pattern(f, outer_inner);
+    render_subtle_pattern(f, outer_inner, game.theme.background_pattern, game.theme.background_dot);
     // Compact inline input box
-    let inner = centered_rect_fixed(48, 5, area);
-    let name = game.name_input.to_string();
-    let p = Paragraph::new(Text::from(vec![
-        Line::from("Type your run name and press Enter"),
+    let inner = centered_rect_fixed(52, 5 + notes.len() as u16, area);
+    let mut lines = vec![
+        Line::from(prompt.to_string()),
         Line::from(""),
-        Line::from(Span::styled(
-            name,
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )),
-    ]))
+        Line::from(value),
+    ];
+    lines.extend(notes);
+    let p = Paragraph::new(Text::from(lines))
     .alignment(Alignment::Center)
     .block(Block::default().borders(Borders::ALL));
     f.render_widget(p, inner);
-    // Bottom-border right-aligned help hint
-    let border_hint_area = Rect {
-        x: area.x.saturating_add(1),
-        y: area.y.saturating_add(area.height.saturating_sub(1)),
-        width: area.width.saturating_sub(2),
-        height: 1,
-    };
-    let hint = Paragraph::new(Span::styled("? - help", Style::default().fg(Color::Gray))).alignment(Alignment::Right);
-    f.render_widget(hint, border_hint_area);
+    draw_footer(f, area, game);
 }
 
 fn draw_leaderboard(f: &mut Frame, area: Rect, game: &Game) {
     // Outer box with dots background
-    let block = Block::default().title("Leaderboard (Top 10)").borders(Borders::ALL);
+    // Classic and Endless are separate boards; Tab flips between them
+    let tabs = [GameMode::Classic, GameMode::Endless]
+        .map(|m| if m == game.leaderboard_mode { format!("[{}]", m.label()) } else { m.label().to_string() })
+        .join(" ");
+    let mut title = match &game.deck_spec {
+        Some(spec) => format!("Leaderboard (Top 10) {} — deck {}", tabs, spec.name),
+        None => format!("Leaderboard (Top 10) {}", tabs),
+    };
+    if let Some(active) = game.rules.describe() { title = format!("{} — {}", title, active); }
+    let fastest = game.leaderboard_filter.fastest_wins;
+    if fastest {
+        title.push_str(" — fastest wins");
+    } else {
+        title = format!("{} — sorted by {}", title, game.config.leaderboard_sort.label());
+        if game.leaderboard_sort_reversed { title.push_str(" (reversed)"); }
+        if game.leaderboard_filter.wins_only { title.push_str(" — wins only"); }
+    }
+    if let Some(seed) = game.leaderboard_filter.seed { title = format!("{} — seed {}", title, seed_codec::encode(seed)); }
+    let block = Block::default().title(title).borders(Borders::ALL);
     let inner = block.inner(area);
     f.render_widget(block, area);
-    render_subtle_pattern(f, inner);
+    render_subtle_pattern(f, inner, game.theme.background_pattern, game.theme.background_dot);
 
     // Center a content region within the leaderboard box
     let content = centered_rect(80, 70, inner);
@@ -182,7 +406,8 @@ fn draw_leaderboard(f: &mut Frame, area: Rect, game: &Game) {
         ])
         .split(content);
 
-    let entries = &game.leaderboard;
+    let view = game.leaderboard_view();
+    let entries: Vec<&ScoreEntry> = view.iter().map(|(_, e)| *e).collect();
 
     // Center the 1st place box horizontally
     let first_w: u16 = content.width.clamp(24, 40);
@@ -194,7 +419,8 @@ fn draw_leaderboard(f: &mut Frame, area: Rect, game: &Game) {
             Constraint::Min((layout[0].width.saturating_sub(first_w)) / 2),
         ])
         .split(layout[0]);
-    draw_podium_box(f, first_hsplit[1], entries.first(), 1, Color::Yellow);
+    let cursor = game.leaderboard_cursor;
+    draw_podium_box(f, first_hsplit[1], entries.first().copied(), 1, Color::Yellow, cursor == 0, fastest);
 
     // Row with 2nd and 3rd, centered as a pair
     let box_w: u16 = ((layout[2].width as f32 * 0.35) as u16).clamp(18, 32);
@@ -209,21 +435,31 @@ fn draw_leaderboard(f: &mut Frame, area: Rect, game: &Game) {
             Constraint::Min((layout[2].width.saturating_sub(pair_w)) / 2),
         ])
         .split(layout[2]);
-    draw_podium_box(f, row_hsplit[1], entries.get(1), 2, Color::Gray);
-    draw_podium_box(f, row_hsplit[3], entries.get(2), 3, Color::Rgb(205, 127, 50));
+    draw_podium_box(f, row_hsplit[1], entries.get(1).copied(), 2, Color::Gray, cursor == 1, fastest);
+    draw_podium_box(f, row_hsplit[3], entries.get(2).copied(), 3, game.theme.bronze, cursor == 2, fastest);
 
     // Remaining list (4..=10), centered block
+    let lw: u16 = layout[4].width.clamp(40, 60);
     let mut lines: Vec<Line> = Vec::new();
     if entries.len() <= 3 {
         lines.push(Line::from("No more scores."));
     } else {
         for (i, entry) in entries.iter().enumerate().skip(3).take(7) {
             let pos = i + 1;
-            let emoji = if entry.won { "🏆" } else { "💀" };
-            lines.push(Line::from(format!("{:>2}. {} {}  {}", pos, emoji, entry.score, entry.name)));
+            let emoji = entry.result_emoji();
+            let style = if i == cursor { Style::default().add_modifier(Modifier::REVERSED) } else { Style::default() };
+            let text = format!("{:>2}. {} {}  {}", pos, emoji, leaderboard_value(entry, fastest), entry.name);
+            let mut spans = vec![Span::styled(text, style)];
+            // Tells a quick death from a long near-miss at the same score; dropped when it would wrap
+            if entry.run_summary != RunSummary::default() {
+                let brief = format!("  {}", entry.run_summary.brief());
+                if spans[0].width() + brief.chars().count() <= lw as usize {
+                    spans.push(Span::styled(brief, Style::default().fg(Color::DarkGray)));
+                }
+            }
+            lines.push(Line::from(spans));
         }
     }
-    let lw: u16 = layout[4].width.clamp(40, 60);
     let list_center = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
@@ -235,23 +471,246 @@ fn draw_leaderboard(f: &mut Frame, area: Rect, game: &Game) {
     let list_p = Paragraph::new(Text::from(lines)).wrap(Wrap { trim: true });
     f.render_widget(list_p, list_center[1]);
 
-    // Bottom-border right-aligned help hint
-    let border_hint_area = Rect {
-        x: area.x.saturating_add(1),
-        y: area.y.saturating_add(area.height.saturating_sub(1)),
-        width: area.width.saturating_sub(2),
-        height: 1,
+    // Name filter under the list, with a cursor while it has focus
+    let filter = &game.leaderboard_filter;
+    if filter.editing || !filter.name.is_empty() {
+        let cursor_mark = if filter.editing { "_" } else { "" };
+        let style = if filter.editing { Style::default().fg(Color::Cyan) } else { Style::default().fg(Color::Gray) };
+        let text = format!("Name: {}{}", filter.name, cursor_mark);
+        f.render_widget(Paragraph::new(Span::styled(text, style)).alignment(Alignment::Center), layout[5]);
+    }
+
+    let footer_w = draw_footer(f, area, game);
+    if let Some(warning) = &game.leaderboard_warning {
+        let warn_area = Rect {
+            x: area.x.saturating_add(1),
+            y: area.y.saturating_add(area.height.saturating_sub(1)),
+            width: area.width.saturating_sub(footer_w + 3),
+            height: 1,
+        };
+        let warn = Paragraph::new(Span::styled(format!("⚠ {}", warning), Style::default().fg(Color::Yellow)));
+        f.render_widget(warn, warn_area);
+    }
+
+    if game.leaderboard_detail && let Some(entry) = entries.get(cursor).copied() {
+        let rating = game.ratings.get(&entry.name);
+        let height = if entry.run_summary == RunSummary::default() { 11 } else { 17 } + entry.duration_secs.is_some() as u16 + rating.is_some() as u16;
+        draw_score_detail(f, centered_rect_fixed(48, height, inner), cursor, entry, rating);
+    }
+}
+
+const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+
+// CHANGELOG.md as styled lines: "## Version X.Y" headings in bold cyan, everything else gray
+fn changelog_lines() -> Vec<Line<'static>> {
+    CHANGELOG
+        .lines()
+        .filter(|l| !l.starts_with("# ")) // the file's own title; the screen has one
+        .map(|l| match l.strip_prefix("## ") {
+            Some(heading) => Line::from(Span::styled(heading, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))),
+            None => Line::from(Span::styled(l, Style::default().fg(Color::Gray))),
+        })
+        .skip_while(|l| l.width() == 0)
+        .collect()
+}
+
+fn draw_changelog(f: &mut Frame, area: Rect, game: &Game) {
+    let block = Block::default().title(format!("What's new — v{}", env!("CARGO_PKG_VERSION"))).borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    render_subtle_pattern(f, inner, game.theme.background_pattern, game.theme.background_dot);
+    let content = centered_rect(80, 90, inner);
+    let lines = changelog_lines();
+    // Same clamping as the Game Over history so End stops at the last line
+    let max_scroll = lines.len().saturating_sub(content.height as usize) as u16;
+    let p = Paragraph::new(Text::from(lines)).wrap(Wrap { trim: false }).scroll((game.changelog_scroll.min(max_scroll), 0));
+    f.render_widget(Clear, content);
+    f.render_widget(p, content);
+
+    draw_footer(f, area, game);
+}
+
+// The two shop choices, the trade naming what it costs
+fn shop_options(game: &Game) -> [String; 2] {
+    let trade = match game.shop_offer() {
+        Some(card) => format!("Trade the {} — monsters below lose 1", card),
+        None => "Trade (no card left over)".to_string(),
     };
-    let hint = Paragraph::new(Span::styled("? - help", Style::default().fg(Color::Gray))).alignment(Alignment::Right);
-    f.render_widget(hint, border_hint_area);
+    [trade, "Leave".to_string()]
+}
+
+fn draw_shop(f: &mut Frame, area: Rect, game: &Game) {
+    let block = Block::default().title(format!("Shop — after room {}", game.room_number)).borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    render_subtle_pattern(f, inner, game.theme.background_pattern, game.theme.background_dot);
+
+    let next = game.deck.top_n_cards(3);
+    let row_w = next.len() as u16 * MINI_W as u16;
+    let content = centered_rect_fixed(48.min(inner.width), (MINI_H as u16 + 5).min(inner.height), inner);
+    f.render_widget(Clear, content);
+    let v = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Length(MINI_H as u16), Constraint::Length(1), Constraint::Length(2)])
+        .split(content);
+    f.render_widget(Paragraph::new("The next cards from the deck:").alignment(Alignment::Center), v[0]);
+    let row = centered_rect_fixed(row_w, MINI_H as u16, v[1]);
+    for (i, card) in next.iter().enumerate() {
+        let cell = Rect { x: row.x + i as u16 * MINI_W as u16, width: MINI_W as u16, ..row };
+        draw_mini_card_box(f, cell, *card);
+    }
+    let lines: Vec<Line> = shop_options(game)
+        .into_iter()
+        .enumerate()
+        .map(|(i, text)| {
+            let style = if i == game.shop_cursor { Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD) } else { Style::default() };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    f.render_widget(Paragraph::new(Text::from(lines)).alignment(Alignment::Center), v[3]);
+
+    draw_footer(f, area, game);
+}
+
+fn save_slot_label(slot: usize, state: &SlotState) -> String {
+    match state {
+        SlotState::Empty => format!("Slot {}  (empty)", slot + 1),
+        SlotState::Corrupt => format!("Slot {}  (corrupt)", slot + 1),
+        SlotState::Saved(run) => format!(
+            "Slot {}  {} · Room {} · {}/{} HP · {}",
+            slot + 1, run.player_name, run.room_number, run.player.hp, run.player.max_hp, format_ts(run.ts)
+        ),
+    }
+}
+
+fn draw_save_slots(f: &mut Frame, area: Rect, game: &Game) {
+    let title = if game.choosing_slot { "All slots are full — pick one for the new run" } else { "Continue" };
+    let block = Block::default().title(title).borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    render_subtle_pattern(f, inner, game.theme.background_pattern, game.theme.background_dot);
+    let content = centered_rect(80, 90, inner);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, state) in game.slots.iter().enumerate() {
+        let style = if i == game.slot_cursor { Style::default().add_modifier(Modifier::REVERSED) } else { Style::default() };
+        lines.push(Line::from(Span::styled(save_slot_label(i, state), style)));
+    }
+    f.render_widget(Clear, content);
+    f.render_widget(Paragraph::new(Text::from(lines)), content);
+
+    draw_footer(f, area, game);
+}
+
+fn draw_run_history(f: &mut Frame, area: Rect, game: &Game) {
+    let block = Block::default().title(format!("Run History ({})", game.runs.len())).borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    render_subtle_pattern(f, inner, game.theme.background_pattern, game.theme.background_dot);
+    let content = centered_rect(80, 90, inner);
+
+    let mut lines: Vec<Line> = Vec::new();
+    if game.runs.is_empty() {
+        lines.push(Line::from("No finished runs yet."));
+    }
+    for (i, run) in game.runs.iter().enumerate() {
+        let emoji = if run.won { "🏆" } else { "💀" };
+        let text = format!("{}  {} {:>4}  {}  · seed {}", format_ts(run.ts), emoji, run.score, run.name, seed_codec::encode(run.seed));
+        let style = if i == game.runs_cursor { Style::default().add_modifier(Modifier::REVERSED) } else { Style::default() };
+        lines.push(Line::from(Span::styled(text, style)));
+    }
+    // Keep the cursor row on screen
+    let offset = (game.runs_cursor + 1).saturating_sub(content.height as usize) as u16;
+    f.render_widget(Paragraph::new(Text::from(lines)).scroll((offset, 0)), content);
+
+    draw_footer(f, area, game);
+
+    if game.run_open && let Some(run) = game.runs.get(game.runs_cursor) {
+        let rect = centered_rect(70, 80, inner);
+        let block = Block::default()
+            .title(format!("{} — {} — Score {}", format_ts(run.ts), run.name, run.score))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Cyan));
+        let body = block.inner(rect);
+        f.render_widget(Clear, rect);
+        f.render_widget(block, rect);
+        let lines = build_history_indented_lines(&run.events);
+        let max_scroll = lines.len().saturating_sub(body.height as usize) as u16;
+        let p = Paragraph::new(Text::from(lines)).alignment(Alignment::Center).scroll((game.run_scroll.min(max_scroll), 0));
+        f.render_widget(p, body);
+    }
+}
+
+// One header row of labels over one row of values, centered under the history
+fn draw_summary_table(f: &mut Frame, area: Rect, summary: &RunSummary) {
+    let rows = summary.rows();
+    let widths: Vec<u16> = rows.iter().map(|(k, v)| k.chars().count().max(v.chars().count()) as u16).collect();
+    let total = widths.iter().sum::<u16>() + 2 * (widths.len() as u16 - 1);
+    let rect = centered_rect_fixed(total.min(area.width), 2.min(area.height), area);
+    let header = Row::new(rows.iter().map(|(k, _)| *k)).style(Style::default().fg(Color::Gray));
+    let values = Row::new(rows.iter().map(|(_, v)| v.clone())).style(Style::default().add_modifier(Modifier::BOLD));
+    let table = Table::new([values], widths.iter().map(|w| Constraint::Length(*w))).header(header).column_spacing(2);
+    f.render_widget(table, rect);
+}
+
+fn draw_score_detail(f: &mut Frame, area: Rect, idx: usize, entry: &ScoreEntry, rating: Option<&PlayerRating>) {
+    let block = Block::default()
+        .title(format!("Run #{}", idx + 1))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+    let label = Style::default().fg(Color::Gray);
+    let row = |k: &'static str, v: String| Line::from(vec![Span::styled(format!("{:<8}", k), label), Span::raw(v)]);
+    let mut lines = vec![
+        row("Name", entry.name.clone()),
+    ];
+    // The player's standing now, not at the time of this run
+    if let Some(r) = rating {
+        lines.push(row("Rating", format!("{:.0} ({:+.0} last run, {} rated)", r.rating, r.last_delta, r.runs)));
+    }
+    lines.extend([
+        row("Score", entry.score.to_string()),
+        row("Result", format!("{} {}", entry.result_emoji(), if entry.won { "cleared" } else if entry.abandoned { "abandoned" } else { "fell" })),
+        row("Date", format_ts(entry.ts)),
+    ]);
+    if let Some(secs) = entry.duration_secs {
+        lines.push(row("Time", format_duration(secs)));
+    }
+    lines.extend([
+        row("Deck", entry.deck.clone().unwrap_or_else(|| "standard".into())),
+        row("Detail", entry.cause.clone().unwrap_or_else(|| "—".into())),
+    ]);
+    // Entries saved before summaries existed have nothing worth listing
+    if entry.run_summary != RunSummary::default() {
+        lines.extend(entry.run_summary.rows().into_iter().map(|(k, v)| row(k, v)));
+    }
+    lines.extend([
+        Line::from(""),
+        Line::from(Span::styled("Enter/Esc - close", label)),
+    ]);
+    f.render_widget(Paragraph::new(Text::from(lines)).wrap(Wrap { trim: true }), inner);
+}
+
+// The number a leaderboard row leads with: the score, or the run time under "fastest wins"
+fn leaderboard_value(entry: &ScoreEntry, fastest: bool) -> String {
+    match entry.duration_secs {
+        Some(secs) if fastest => format_duration(secs),
+        _ => entry.score.to_string(),
+    }
 }
 
 fn draw_podium_box(
     f: &mut Frame,
     area: Rect,
-    entry_opt: Option<&crate::game::ScoreEntry>,
+    entry_opt: Option<&ScoreEntry>,
     rank: usize,
     color: Color,
+    selected: bool,
+    fastest: bool,
 ) {
     let title = match rank {
         1 => "1st",
@@ -262,7 +721,7 @@ fn draw_podium_box(
     let b = Block::default()
         .title(Span::styled(title, Style::default().fg(color).add_modifier(Modifier::BOLD)))
         .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
+        .border_type(if selected { BorderType::Thick } else { BorderType::Rounded })
         .border_style(Style::default().fg(color));
     let inner = b.inner(area);
     f.render_widget(b, area);
@@ -273,52 +732,126 @@ fn draw_podium_box(
         lines.push(Line::from(vec![
             Span::styled(medal, Style::default().fg(color).add_modifier(Modifier::BOLD)),
             Span::raw(" "),
-            Span::styled(entry.score.to_string(), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::styled(leaderboard_value(entry, fastest), Style::default().fg(color).add_modifier(Modifier::BOLD)),
             Span::raw("  "),
             Span::raw(entry.name.clone()),
         ]));
     } else {
         lines.push(Line::from("—"));
     }
+    if selected {
+        lines = lines.into_iter().map(|l| l.patch_style(Modifier::REVERSED)).collect();
+    }
     let p = Paragraph::new(Text::from(lines)).alignment(Alignment::Center);
     f.render_widget(p, inner);
 }
 
+// "Seed best!" or "#3 on this seed" once the finished run is on the board
+fn seed_rank_text(game: &Game) -> Option<String> {
+    game.new_seed_rank.map(|pos| if pos == 0 { "Seed best!".to_string() } else { format!("#{} on this seed", pos + 1) })
+}
+
+// " (rating 1043, +12)" after the player's name once a Classic run is rated
+fn rating_suffix(game: &Game) -> String {
+    game.rating_change.map(|r| format!(" (rating {:.0}, {:+.0})", r.rating, r.last_delta)).unwrap_or_default()
+}
+
+// "Discard pile: 9 monsters, 3 weapons, 6 potions — total monster value 61"
+fn discard_summary(game: &Game) -> String {
+    let pile = game.discard_pile_composition();
+    format!(
+        "Discard pile: {} monsters, {} weapons, {} potions — total monster value {}",
+        pile.monsters,
+        pile.weapons,
+        pile.potions,
+        game.discard_pile_value()
+    )
+}
+
+const VICTORY_ART: [&str; 5] = [
+    "█   █  ███   ███  █████   ███   ████   █   █  █",
+    "█   █   █   █       █    █   █  █   █   █ █   █",
+    "█   █   █   █       █    █   █  ████     █    █",
+    " █ █    █   █       █    █   █  █  █     █     ",
+    "  █    ███   ███    █     ███   █   █    █    █",
+];
+
+// Stands in for the art when the screen is too short for it
+const VICTORY_LINE: [&str; 1] = ["★ ★ ★  V I C T O R Y  ★ ★ ★"];
+
 fn draw_game_over(f: &mut Frame, area: Rect, game: &Game) {
-    let block = Block::default().title("Game Over").borders(Borders::ALL);
+    // Victories get a gold double border, a banner and their score broken down
+    let victory = game.outcome == Some(Outcome::Victory);
+    let mut block = Block::default().title(if victory { "Victory" } else { "Game Over" }).borders(Borders::ALL);
+    if victory {
+        block = block.border_type(BorderType::Double).border_style(Style::default().fg(game.theme.gold).add_modifier(Modifier::BOLD));
+    }
     let inner = block.inner(area);
     f.render_widget(block, area);
     // subtle dotted background across the game over box
-    render_subtle_pattern(f, inner);
+    render_subtle_pattern(f, inner, game.theme.background_pattern, game.theme.background_dot);
+    // The big banner only where it leaves the history room to breathe
+    let banner: &[&str] = match (victory, inner.height >= 28) {
+        (false, _) => &[],
+        (true, true) => &VICTORY_ART,
+        (true, false) => &VICTORY_LINE,
+    };
+    // Header with result and score and rank
+    let score = game.score.unwrap_or(0);
+    let rank_text = if let Some(pos) = game.new_rank_pos {
+        let on_seed = seed_rank_text(game).map(|t| format!(" · {}", t)).unwrap_or_default();
+        format!("Global #{}{} · Seed {}", pos + 1, on_seed, game.seed_code())
+    } else {
+        format!("Seed {}", game.seed_code())
+    };
+    let mut header = vec![Line::from(vec![Span::styled(
+        format!(
+            "{} {}{} — Score {}",
+            if victory { "🏆" } else { "💀" },
+            game.player_name,
+            rating_suffix(game),
+            score
+        ),
+        Style::default().add_modifier(Modifier::BOLD),
+    )])];
+    if let Some(delta) = game.best_delta {
+        let color = if matches!(delta, BestDelta::NewBest { .. }) { Color::Green } else { Color::Gray };
+        header.push(Line::from(Span::styled(delta.describe(), Style::default().fg(color))));
+    }
+    header.extend(score_breakdown_line(game));
+    if victory && let Some(secs) = game.run_time_secs {
+        header.push(Line::from(Span::styled(format!("Cleared in {}", format_duration(secs)), Style::default().fg(game.theme.gold))));
+    }
+    // The played-card count only when the line still fits
+    let mut discard = discard_summary(game);
+    let played = format!(" · {} cards played", game.cards_seen_this_run());
+    if Span::raw(format!("{}{}", discard, played)).width() <= inner.width as usize { discard.push_str(&played); }
+    header.push(Line::from(Span::styled(discard, Style::default().fg(Color::Gray))));
+    header.push(Line::from(rank_text));
+    header.push(game_over_cause_line(game));
+    if game.max_kill_streak_this_run > 0 {
+        let mut text = format!("🔥 Longest clean-kill streak: {}", game.max_kill_streak_this_run);
+        if game.streak_bonus() > 0 { text = format!("{} (+{} score)", text, game.streak_bonus()); }
+        header.push(Line::from(Span::styled(text, Style::default().fg(Color::Yellow))));
+    }
+    if game.stats.healing_wasted > 0 {
+        let text = format!("⚕ Wasted {} HP in overhealing.", game.stats.healing_wasted);
+        header.push(Line::from(Span::styled(text, Style::default().fg(Color::Yellow))));
+    }
+    let outer = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(banner.len() as u16), Constraint::Min(0)])
+        .split(inner);
+    if !banner.is_empty() { draw_ascii_banner(f, outer[0], banner, game.theme.gold); }
     let v = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),
+            Constraint::Length(header.len() as u16),
             Constraint::Min(8),
             Constraint::Length(3),
         ])
-        .split(inner);
-
-    // Header with result and score and rank
-    let score = game.score.unwrap_or(0);
-    let rank_text = if let Some(pos) = game.new_rank_pos {
-        format!("New rank: #{}", pos + 1)
-    } else {
-        String::new()
-    };
-    let title = Paragraph::new(Text::from(vec![
-        Line::from(vec![Span::styled(
-            format!(
-                "{} {} — Score {}",
-                if score >= 0 { "🏆" } else { "💀" },
-                game.player_name,
-                score
-            ),
-            Style::default().add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(rank_text),
-    ]))
-    .alignment(Alignment::Center);
+        .split(outer[1]);
+    let title = Paragraph::new(Text::from(header)).alignment(Alignment::Center);
     f.render_widget(title, v[0]);
 
     // History list grouped by rooms, scrollable
@@ -343,39 +876,114 @@ fn draw_game_over(f: &mut Frame, area: Rect, game: &Game) {
         .scroll((scroll, 0));
     f.render_widget(hist, hsplit[1]);
 
-    // Bottom-border right-aligned help hint
-    let border_hint_area = Rect {
-        x: area.x.saturating_add(1),
-        y: area.y.saturating_add(area.height.saturating_sub(1)),
-        width: area.width.saturating_sub(2),
-        height: 1,
-    };
-    let hint = Paragraph::new(Span::styled("? - help", Style::default().fg(Color::Gray))).alignment(Alignment::Right);
-    f.render_widget(hint, border_hint_area);
+    draw_summary_table(f, v[2], &game.stats);
+    if let Some(AnimationState::Confetti { frame }) = game.animation {
+        draw_confetti(f.buffer_mut(), inner, frame, game.theme.background_pattern);
+    }
+
+    draw_footer(f, area, game);
+}
+
+// Pieces drift down a row every two frames and only land on the background pattern's marks,
+// which text has already covered; on a blank background any empty cell will do
+fn draw_confetti(buf: &mut Buffer, area: Rect, frame: u8, pattern: BackgroundPattern) {
+    const PIECES: [char; 4] = ['*', '✦', '•', '+'];
+    const COLORS: [Color; 5] = [Color::Yellow, Color::LightMagenta, Color::LightCyan, Color::LightGreen, Color::LightRed];
+    if area.is_empty() { return; }
+    let count = (area.width as usize * area.height as usize) / 30;
+    for k in 0..count {
+        let h = (k as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let x = area.x + (h % area.width as u64) as u16;
+        let y = area.y + ((h >> 16) % area.height as u64 + frame as u64 / 2) as u16 % area.height;
+        let cell = &mut buf[(x, y)];
+        let free = match pattern {
+            BackgroundPattern::Blank => cell.symbol() == " ",
+            _ => matches!(cell.symbol(), "·" | "░" | "/"),
+        };
+        if !free { continue; }
+        cell.set_char(PIECES[(h >> 32) as usize % PIECES.len()]).set_fg(COLORS[(h >> 40) as usize % COLORS.len()]);
+    }
+}
+
+// "HP 18 + potion 2 + rooms 5" under a Classic victory
+fn score_breakdown_line(game: &Game) -> Option<Line<'static>> {
+    if game.game_mode != GameMode::Classic || game.outcome != Some(Outcome::Victory) { return None; }
+    let b = game.victory_breakdown();
+    let mut text = format!("HP {} + potion {}", b.hp, b.potion_bonus);
+    if b.rooms_bonus != 0 { text = format!("{} + rooms {}", text, b.rooms_bonus); }
+    if b.streak_bonus != 0 { text = format!("{} + streak {}", text, b.streak_bonus); }
+    Some(Line::from(Span::styled(text, Style::default().fg(Color::Gray))))
+}
+
+fn game_over_cause_line(game: &Game) -> Line<'static> {
+    if let Some(cause) = &game.death_cause {
+        Line::from(Span::styled(cause.describe(), Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD)))
+    } else if let Some(card) = game.last_resolved {
+        Line::from(Span::styled(format!("Dungeon cleared — final card {}", card), Style::default().fg(Color::LightGreen)))
+    } else {
+        Line::from("")
+    }
+}
+
+// "Threat: 27 dmg if fought barehanded · 2 enemies · 1 weapon (best 7) · 1 potion"
+fn room_summary_text(game: &Game) -> Option<String> {
+    let s = game.room_summary();
+    let count = |n: usize, one: &str, many: &str| format!("{} {}", n, if n == 1 { one } else { many });
+    let mut parts = vec![format!("Threat: {} dmg if fought barehanded", s.monster_value)];
+    if s.monsters > 0 { parts.push(count(s.monsters, "enemy", "enemies")); }
+    if let Some(best) = s.best_weapon {
+        let weapons = count(s.weapons, "weapon", "weapons");
+        parts.push(if s.weapons > 1 { format!("{} (best {})", weapons, best) } else { weapons });
+    }
+    if s.potions > 0 { parts.push(count(s.potions, "potion", "potions")); }
+    if s.hidden > 0 { parts.push(format!("{} hidden", s.hidden)); }
+    (s.monsters + s.weapons + s.potions + s.hidden > 0).then(|| parts.join(" · "))
 }
 
+fn draw_room_summary(f: &mut Frame, area: Rect, game: &Game) {
+    let Some(text) = room_summary_text(game) else { return; };
+    f.render_widget(Paragraph::new(Span::styled(text, Style::default().fg(Color::Gray))).alignment(Alignment::Center), area);
+}
 
-fn draw_room(f: &mut Frame, area: Rect, game: &Game) {
+fn draw_room(f: &mut Frame, area: Rect, game: &Game, capture: &mut LayoutCapture) {
     // No enclosing room box; use provided area directly
     let inner = area;
 
-    // 1x4 horizontal layout
-    let cols = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-        ])
-        .split(inner);
-    for i in 0..4 {
-        let area = cols[i];
+    let grid = match game.config.room_layout {
+        RoomLayout::Row => false,
+        RoomLayout::Grid => true,
+        RoomLayout::Auto => inner.width < GRID_BELOW_WIDTH,
+    };
+    capture.layout.grid = grid;
+    let cols: Vec<Rect> = if grid {
+        // 2x2: slots 1-2 on the top row, 3-4 below, so quick-pick numbers read the same
+        let halves = |r: Rect, dir: Direction| {
+            Layout::default().direction(dir).constraints([Constraint::Percentage(50), Constraint::Percentage(50)]).split(r)
+        };
+        halves(inner, Direction::Vertical).iter().flat_map(|row| halves(*row, Direction::Horizontal).to_vec()).collect()
+    } else {
+        // 1x4 horizontal layout
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(25),
+                Constraint::Percentage(25),
+                Constraint::Percentage(25),
+                Constraint::Percentage(25),
+            ])
+            .split(inner)
+            .to_vec()
+    };
+    for (slot, rect) in capture.layout.card_rects.iter_mut().enumerate() {
+        *rect = Some(cols[slot]);
+    }
+    for (i, &area) in cols.iter().enumerate() {
         if let Some(card) = game.room[i] {
             let inner_block = if i == game.selected {
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Yellow))
+                    .border_type(game.theme.selected_border())
+                    .border_style(Style::default().fg(selected_border_color(game)))
             } else {
                 Block::default()
                     .borders(Borders::ALL)
@@ -384,19 +992,33 @@ fn draw_room(f: &mut Frame, area: Rect, game: &Game) {
             f.render_widget(inner_block.clone(), area);
             let inner = inner_block.inner(area);
             // subtle background in cell
-            render_subtle_pattern(f, inner);
+            render_subtle_pattern(f, inner, game.theme.background_pattern, game.theme.background_dot);
             let ca = centered_rect_fixed(CARD_W as u16, CARD_H as u16, inner);
-            draw_card_box(f, ca, card);
             // Caption label beneath card
             let label_y = (ca.y.saturating_add(ca.height)).min(inner.y.saturating_add(inner.height.saturating_sub(1)));
             let label_area = Rect { x: inner.x, y: label_y, width: inner.width, height: 1 };
-            let (label, col) = match card.suit {
-                Suit::Hearts => ("Potion", Color::LightRed),
-                Suit::Diamonds => ("Weapon", Color::LightBlue),
-                Suit::Clubs | Suit::Spades => ("Enemy", Color::LightMagenta),
-            };
-            let caption = Paragraph::new(Span::styled(label, Style::default().fg(col))).alignment(Alignment::Center);
-            f.render_widget(caption, label_area);
+            // Cards still being dealt or face down show their back, and no caption that would give them away
+            if !game.slot_revealed(i) || game.card_phase[i] == AnimationPhase::FaceDown {
+                draw_card_back(f, ca, &game.theme);
+            } else if let AnimationPhase::Flipping { since } = game.card_phase[i] {
+                // Narrow the back to a sliver, then widen the face back out
+                let t = game.frame_counter.saturating_sub(since).min(FLIP_FRAMES);
+                let half = FLIP_FRAMES / 2;
+                let w = (ca.width as u64 * t.abs_diff(half) / half).max(2) as u16;
+                let narrow = Rect { x: ca.x + ca.width.saturating_sub(w) / 2, width: w, ..ca };
+                if t < half { draw_card_back(f, narrow, &game.theme); } else { draw_card_box(f, narrow, card, &game.theme); }
+            } else if card.suit == Suit::Hearts && game.potion_used_this_turn {
+                draw_card_box(f, ca, card, &game.theme);
+                // Second potion this turn heals nothing; say so before it is taken
+                let warn = Paragraph::new(Span::styled("Potion (will be WASTED)", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)))
+                    .alignment(Alignment::Center);
+                f.render_widget(warn, label_area);
+            } else {
+                draw_card_box(f, ca, card, &game.theme);
+                // Struck out when the equipped weapon's binding means this fight would be barehanded
+                let out_of_reach = card.is_monster() && game.player.weapon.as_ref().is_some_and(|w| !w.can_use_on(card.monster_value()));
+                draw_card_type_badge(f, label_area, card.suit, &game.theme, out_of_reach);
+            }
             // Overlay selection numbers: top-left and bottom-right inside the cell
             let num = (i + 1).to_string();
             let top_left = Rect { x: inner.x, y: inner.y, width: 2, height: 1 };
@@ -415,11 +1037,14 @@ fn draw_room(f: &mut Frame, area: Rect, game: &Game) {
                 .title("Empty")
                 .border_style(Style::default().fg(Color::Gray));
             if i == game.selected {
-                b = b.border_style(Style::default().fg(Color::Red));
+                b = b.border_type(game.theme.selected_border()).border_style(Style::default().fg(Color::Red));
+            }
+            if game.flashing(FeedbackTarget::Slot(i)) {
+                b = b.border_style(Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD | Modifier::REVERSED));
             }
             let inner = b.inner(area);
             f.render_widget(b, area);
-            render_subtle_pattern(f, inner);
+            render_subtle_pattern(f, inner, game.theme.background_pattern, game.theme.background_dot);
             // Also render quick-pick numbers for empty cells
             let num = (i + 1).to_string();
             let top_left = Rect { x: inner.x, y: inner.y, width: 2, height: 1 };
@@ -433,81 +1058,223 @@ fn draw_room(f: &mut Frame, area: Rect, game: &Game) {
             f.render_widget(Paragraph::new(Span::styled(num.clone(), num_style)), top_left);
             f.render_widget(Paragraph::new(Span::styled(num, num_style)).alignment(Alignment::Right), bot_right);
         }
+        if let Some(color) = attack_flash_color(game, i) {
+            recolor_border(f.buffer_mut(), area, Style::default().fg(color).add_modifier(Modifier::BOLD));
+        }
+    }
+}
+
+// Yellow, pulsing through brighter shades while the selected card would be the room's last pick
+fn selected_border_color(game: &Game) -> Color {
+    const PULSE: [Color; 4] = [Color::Yellow, Color::LightYellow, Color::White, Color::LightYellow];
+    if game.choices_remaining_this_turn() != 1 || game.config.reduced_motion { return Color::Yellow; }
+    PULSE[(game.frame_counter / 4 % 4) as usize]
+}
+
+// The slot a weapon fight came from cycles white, yellow, orange while the strike animates
+fn attack_flash_color(game: &Game, slot: usize) -> Option<Color> {
+    match game.animation {
+        Some(AnimationState::WeaponAttack { frame, slot: s }) if s == slot => {
+            Some([Color::White, Color::Yellow, game.theme.hp_orange][(frame / 3 % 3) as usize])
+        }
+        _ => None,
+    }
+}
+
+// Restyle only the outline of `area`, keeping a title drawn on it
+fn recolor_border(buf: &mut Buffer, area: Rect, style: Style) {
+    let area = area.intersection(buf.area);
+    if area.is_empty() { return; }
+    for x in area.left()..area.right() {
+        buf[(x, area.top())].set_style(style);
+        buf[(x, area.bottom() - 1)].set_style(style);
+    }
+    for y in area.top()..area.bottom() {
+        buf[(area.left(), y)].set_style(style);
+        buf[(area.right() - 1, y)].set_style(style);
+    }
+}
+
+// Caption text and color for a card kind; the single place these labels live
+fn card_type_label(suit: Suit, theme: &Theme) -> (&'static str, Color) {
+    match suit {
+        Suit::Hearts => ("Potion", theme.potion),
+        Suit::Diamonds => ("Weapon", theme.weapon),
+        Suit::Clubs | Suit::Spades => ("Enemy", theme.enemy),
     }
 }
 
+fn draw_card_type_badge(f: &mut Frame, area: Rect, suit: Suit, theme: &Theme, struck: bool) {
+    let (label, col) = card_type_label(suit, theme);
+    let style = if struck { Style::default().fg(col).add_modifier(Modifier::DIM | Modifier::CROSSED_OUT) } else { Style::default().fg(col) };
+    let badge = Paragraph::new(Span::styled(label, style)).alignment(Alignment::Center);
+    f.render_widget(badge, area);
+}
+
+/// "[████░░░░░░] 17/44": `width` cells filled in proportion to the cards resolved.
+fn progress_gauge(resolved: usize, total: usize, width: usize) -> String {
+    let filled = (resolved * width).checked_div(total).unwrap_or(0).min(width);
+    format!("[{}{}] {}/{}", "█".repeat(filled), "░".repeat(width - filled), resolved, total)
+}
+
 fn draw_status(f: &mut Frame, area: Rect, game: &Game) {
     // Build status line: HP, Weapon, Deck. Include projected damage if selecting a monster.
     let player = &game.player;
     let mut hp_proj = String::new();
-    if let GamePhase::Running = game.phase
-        && let Some(card) = game.room[game.selected]
-        && matches!(card.suit, Suit::Clubs | Suit::Spades)
+    if let Some(dmg) = projected_damage(game) { hp_proj = format!(" (-{})", dmg); }
+    if let Some(card) = game.room[game.selected]
+        && card.suit == Suit::Hearts
+        && game.potion_used_this_turn
+        && !game.rules.hardcore
     {
-        let mval = card.monster_value() as i32;
-        let dmg = if let Some(w) = &player.weapon {
-            if w.can_use_on(card.monster_value()) { (mval - w.value as i32).max(0) } else { mval }
-        } else { mval };
-        if dmg > 0 { hp_proj = format!(" (-{})", dmg); }
+        hp_proj = " (-0 heal)".into();
     }
-    let weapon_str = if let Some(w) = &player.weapon {
-        format!("{} (≤ {})", w.value, w.last_monster.map(|v| v.to_string()).unwrap_or_else(|| "∞".into()))
-    } else { "-".into() };
+    let weapon_str = weapon_summary(game);
     // Determine HP color by percentage: 100% green, >=75% yellow, >=50% orange, else red
     let max_hp = player.max_hp.max(1) as f32;
-    let pct = (player.hp as f32 / max_hp).clamp(0.0, 1.0);
-    let hp_color = if (pct - 1.0).abs() < f32::EPSILON {
-        Color::LightGreen
-    } else if pct >= 0.75 {
-        Color::Yellow
-    } else if pct >= 0.5 {
-        Color::Rgb(255, 165, 0) // orange
-    } else {
-        Color::LightRed
-    };
+    let hp_color = game.theme.hp_color((player.hp as f32 / max_hp).clamp(0.0, 1.0));
     let mut status_spans: Vec<Span> = Vec::new();
     // HP value with colored status
-    status_spans.push(Span::styled(
-        format!("HP: {}/{}", player.hp, player.max_hp),
-        Style::default().fg(hp_color).add_modifier(Modifier::BOLD),
-    ));
+    // A potion just drunk flashes HP green, on and off every few frames
+    let hp_style = match game.animation {
+        Some(AnimationState::PotionHeal { frame }) if frame / 2 % 2 == 0 => Style::default().fg(Color::Black).bg(Color::LightGreen),
+        _ => Style::default().fg(hp_color),
+    };
+    status_spans.push(Span::styled(format!("HP: {}/{}", player.hp, player.max_hp), hp_style.add_modifier(Modifier::BOLD)));
     // Damage preview always in red (if present)
     if !hp_proj.is_empty() {
         status_spans.push(Span::styled(hp_proj.clone(), Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD)));
     }
     status_spans.push(Span::raw("  |  "));
     status_spans.push(Span::styled(format!("Weapon: {}", weapon_str), Style::default().fg(Color::LightBlue)));
+    if game.kill_streak() >= 2 {
+        status_spans.push(Span::styled(format!(" 🔥x{}", game.kill_streak()), Style::default().fg(Color::Yellow)));
+    }
     status_spans.push(Span::raw("  |  "));
     status_spans.push(Span::styled(format!("Deck: {}", game.deck.len()), Style::default().fg(Color::Gray)));
+    let deck_end = status_spans.len();
+    // Current HP is the score floor of a clean finish, so it gives a live rank estimate
+    let live_score = if game.game_mode == GameMode::Endless { game.endless_score() } else { player.hp };
+    let rank = game.leaderboard_rank_for_score(live_score) + 1;
+    status_spans.push(Span::styled(format!(" ~#{}", rank), Style::default().fg(Color::DarkGray)));
     status_spans.push(Span::raw("  |  "));
-    status_spans.push(Span::styled(format!("Room {}", game.room_number), Style::default().fg(Color::Gray)));
-    let line = Line::from(status_spans);
-    // Draw status block and background pattern, then center content inside
-    let block = Block::default()
-        .title("Status")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Gray));
-    let inner = block.inner(area);
-    f.render_widget(block, area);
-    render_subtle_pattern(f, inner);
+    let picks = match game.choices_remaining_this_turn() {
+        0 => Span::styled("Auto-ending turn…", Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD)),
+        1 => Span::styled("Picks: 1 left", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        n => Span::styled(format!("Picks: {} left", n), Style::default().fg(Color::Cyan)),
+    };
+    status_spans.push(picks);
+    status_spans.push(Span::raw("  |  "));
+    let avoid = game.avoid_status();
+    let avoid_color = if avoid == AvoidStatus::Ready { Color::LightGreen } else { Color::DarkGray };
+    status_spans.push(Span::styled(format!("Avoid: {}", avoid.label()), Style::default().fg(avoid_color)));
+    let room = area.width.saturating_sub(2) as usize;
+    // What is left to draw, right after the deck count; it takes the space before the projection does
+    if game.config.show_deck_breakdown {
+        let deck = &game.deck;
+        let text = format!(" [♠♣:{} ♦:{} ♥:{}]", deck.monsters_remaining(), deck.weapons_remaining(), deck.potions_remaining());
+        let span = Span::styled(text, Style::default().fg(Color::DarkGray));
+        let used: usize = status_spans.iter().map(|s| s.width()).sum();
+        if used + span.width() <= room { status_spans.insert(deck_end, span); }
+    }
+    // Cards out of play and the last one discarded, when there is room for them
+    if game.cards_out_of_play() > 0 {
+        let top = game.discard.last().map(|c| format!(" (top {})", c)).unwrap_or_default();
+        let span = Span::styled(format!("Gone: {}{}", game.cards_out_of_play(), top), Style::default().fg(Color::Gray));
+        let used: usize = status_spans.iter().map(|s| s.width()).sum();
+        if used + 5 + span.width() <= room {
+            status_spans.push(Span::raw("  |  "));
+            status_spans.push(span);
+        }
+    }
+    // Dungeon progress gauge, dropped first on narrow terminals
+    let (resolved, total) = game.progress();
+    let gauge = progress_gauge(resolved, total, 10);
+    let used: usize = status_spans.iter().map(|s| s.width()).sum();
+    if used + 5 + gauge.chars().count() <= room {
+        status_spans.push(Span::raw("  |  "));
+        status_spans.push(Span::styled(gauge, Style::default().fg(Color::Gray)));
+    }
+    // Scores if the run ended now, each only when it fits next to everything else
+    if game.config.show_score_projection {
+        let (win, death) = game.projected_score();
+        let risk_color = if death < 0 { Color::LightRed } else { Color::DarkGray };
+        let mut tail = vec![Span::styled(format!("  |  Risk: {:+}", death), Style::default().fg(risk_color))];
+        // Endless scores the same either way
+        if game.game_mode == GameMode::Classic {
+            tail.push(Span::styled(format!("  Win: {:+}", win), Style::default().fg(Color::DarkGray)));
+        }
+        for span in tail {
+            let used: usize = status_spans.iter().map(|s| s.width()).sum();
+            if used + span.width() > room { break; }
+            status_spans.push(span);
+        }
+    }
+    let line = Line::from(status_spans);
+    // Draw status block and background pattern, then center content inside; the seed sits on the border
+    let elapsed = format_duration(game.run_elapsed_secs());
+    let title = match game.game_mode {
+        GameMode::Classic => format!("Status · {}", elapsed),
+        GameMode::Endless => format!("Status · {} · Endless · Dungeon {}", elapsed, game.dungeons_cleared + 1),
+    };
+    let mut block = Block::default()
+        .title(title)
+        .title_bottom(Line::from(Span::styled(format!(" Seed {} ", game.seed_code()), Style::default().fg(Color::DarkGray))).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if game.flashing(FeedbackTarget::Status) { Color::LightRed } else { Color::Gray }));
+    if let Some(ghost) = game.ghost_comparison() {
+        block = block.title(Line::from(Span::styled(format!(" 👻 {} ", ghost), Style::default().fg(Color::Cyan))).right_aligned());
+    }
+    // Avoided cards the player has already seen, still waiting in the deck
+    if !game.cycled.is_empty() {
+        let seen: Vec<String> = game.cycled.iter().map(|c| c.to_string()).collect();
+        block = block.title_bottom(Line::from(Span::styled(format!(" Seen ahead: {} ", seen.join(" ")), Style::default().fg(Color::Gray))));
+    }
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    render_subtle_pattern(f, inner, game.theme.background_pattern, game.theme.background_dot);
     let p = Paragraph::new(Text::from(vec![line])).alignment(Alignment::Center);
     f.render_widget(p, inner);
 }
 
+// Damage the selected monster would deal with the default take, if any
+// Hardcore runs play without the preview
+fn projected_damage(game: &Game) -> Option<i32> {
+    if game.phase != GamePhase::Running || game.rules.hardcore { return None; }
+    game.preview_fight(game.selected, UseMode::Default).filter(|dmg| *dmg > 0)
+}
+
+fn weapon_summary(game: &Game) -> String {
+    if let Some(w) = &game.player.weapon {
+        format!("{} (≤ {})", w.value, w.last_monster.map(|v| v.to_string()).unwrap_or_else(|| "∞".into()))
+    } else { "-".into() }
+}
+
 fn draw_equipped(f: &mut Frame, area: Rect, game: &Game) {
-    let block = Block::default()
+    let mut block = Block::default()
         .title("Equipped & Slain")
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Gray));
+    // What taking the selected weapon would throw away
+    if let Some(trade) = game.weapon_trade(game.selected).filter(|_| game.slot_revealed(game.selected)) {
+        block = block.title(Line::from(Span::styled(format!(" Replaces {} ", trade), Style::default().fg(Color::Yellow))).right_aligned());
+    }
     f.render_widget(block.clone(), area);
     let inner = block.inner(area);
     // subtle background across the equipped box
-    render_subtle_pattern(f, inner);
+    render_subtle_pattern(f, inner, game.theme.background_pattern, game.theme.background_dot);
 
+    let next = game.next_room_preview();
+    let next_w = if next.is_empty() { 0 } else { 2 + next.len() as u16 * MINI_W as u16 };
     let cols = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Length(16), Constraint::Min(10)])
+        .constraints([Constraint::Length(16), Constraint::Min(10), Constraint::Length(next_w)])
         .split(inner);
+    if !next.is_empty() { draw_next_room(f, cols[2], game, &next); }
+    if game.config.show_minimap {
+        let right = Rect { y: inner.y, height: 1.min(inner.height), ..cols[1].union(cols[2]) };
+        draw_minimap(f, right, game);
+    }
 
     // Left: big equipped card centered
     if let Some(w) = &game.player.weapon {
@@ -515,8 +1282,23 @@ fn draw_equipped(f: &mut Frame, area: Rect, game: &Game) {
             suit: Suit::Diamonds,
             rank: Rank::new(w.value),
         };
-        let ca = centered_rect_fixed(CARD_W as u16, CARD_H as u16, cols[0]);
-        draw_card_box(f, ca, eq_card);
+        // Room for the durability bar under the card when weapons wear out
+        let bar_h = w.uses_remaining.is_some() as u16;
+        let ca = centered_rect_fixed(CARD_W as u16, CARD_H as u16 + bar_h, cols[0]);
+        let card_area = Rect { height: ca.height.min(CARD_H as u16), ..ca };
+        draw_card_box(f, card_area, eq_card, &game.theme);
+        // "≤8" in the top-right corner once the weapon is bound to its last kill
+        if let Some(bound) = w.last_monster {
+            let badge = format!("≤{}", bound);
+            let len = badge.chars().count() as u16;
+            let corner = Rect { x: card_area.right().saturating_sub(len + 1), y: card_area.y + 1, width: len, height: 1 }.intersection(card_area);
+            f.render_widget(Paragraph::new(Span::styled(badge, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))), corner);
+        }
+        if let (Some(left), Some(total)) = (w.uses_remaining, game.rules.weapon_breaks_after_uses) {
+            // On short boxes the bar sits on the card's bottom border
+            let y = (ca.y + CARD_H as u16).min(cols[0].bottom().saturating_sub(1));
+            draw_durability_bar(f, Rect { y, height: 1, ..ca }, left, total);
+        }
     } else {
         let ca = centered_rect_fixed(CARD_W as u16, CARD_H as u16, cols[0]);
         draw_empty_card_box(f, ca);
@@ -559,31 +1341,90 @@ fn draw_equipped(f: &mut Frame, area: Rect, game: &Game) {
     }
 }
 
-fn render_subtle_pattern(f: &mut Frame, area: Rect) {
-    if area.width == 0 || area.height == 0 { return; }
-    // Build a faint dot pattern (e.g., ". · . ·") with alternating rows
-    let mut lines: Vec<Line> = Vec::with_capacity(area.height as usize);
-    let w = area.width as usize;
-    for y in 0..area.height {
-        let offset = (y % 2) as usize;
-        let mut s = String::with_capacity(w);
-        for x in 0..w {
-            // pattern: place a dot every 2 columns, stagger by row
-            if (x + offset) % 2 == 0 { s.push('·'); } else { s.push(' '); }
+// One block per fight the weapon has left, green while most remain and red near the end
+fn draw_durability_bar(f: &mut Frame, area: Rect, left: u8, total: u8) {
+    let color = if left * 3 > total * 2 {
+        Color::Green
+    } else if left * 3 > total {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+    let bar = Span::styled("█".repeat(left as usize), Style::default().fg(color));
+    f.render_widget(Paragraph::new(Line::from(bar)).alignment(Alignment::Center), area);
+}
+
+/// Rooms shown on the minimap, most recent last.
+const MINIMAP_ROOMS: usize = 20;
+
+// "Rooms ▪▪▫▪": one mark per room, right-aligned; clean and damaged take the full and low HP colors
+fn draw_minimap(f: &mut Frame, area: Rect, game: &Game) {
+    if game.room_summary.is_empty() { return; }
+    let recent = &game.room_summary[game.room_summary.len().saturating_sub(MINIMAP_ROOMS)..];
+    let mut spans = vec![Span::styled("Rooms ", Style::default().fg(Color::Gray))];
+    spans.extend(recent.iter().map(|r| match r {
+        RoomResult::Clean => Span::styled("▪", Style::default().fg(game.theme.hp_color(1.0))),
+        RoomResult::Damaged => Span::styled("▪", Style::default().fg(game.theme.hp_color(0.0))),
+        RoomResult::Avoided => Span::styled("▫", Style::default().fg(Color::Gray)),
+    }));
+    f.render_widget(Paragraph::new(Line::from(spans)).alignment(Alignment::Right), area);
+}
+
+// "Next:" label over the cards the next room deals: backs, suits only, or full cards depending on the peek rules
+fn draw_next_room(f: &mut Frame, area: Rect, game: &Game, next: &[Card]) {
+    let h = MINI_H as u16 + 1;
+    let top = area.y + area.height.saturating_sub(h) / 2;
+    let label = Rect { x: area.x, y: top, width: area.width, height: 1.min(area.height) };
+    f.render_widget(Paragraph::new(Span::styled("Next:", Style::default().fg(Color::Gray))), label);
+    for (i, card) in next.iter().enumerate() {
+        let cell = Rect { x: area.x + i as u16 * MINI_W as u16, y: top + 1, width: MINI_W as u16, height: MINI_H as u16 }.intersection(area);
+        match (game.rules.allow_deck_peek, game.rules.show_full_next_room) {
+            (false, _) => draw_card_back(f, cell, &game.theme),
+            (true, false) => draw_mini_suit_box(f, cell, *card),
+            (true, true) => draw_mini_card_box(f, cell, *card),
         }
-        lines.push(Line::from(Span::styled(s, Style::default().fg(Color::DarkGray))));
     }
-    f.render_widget(Paragraph::new(Text::from(lines)), area);
 }
 
+fn render_subtle_pattern(f: &mut Frame, area: Rect, pattern: BackgroundPattern, color: Color) {
+    if area.width == 0 || area.height == 0 || pattern == BackgroundPattern::Blank { return; }
+    f.render_widget(SubtlePattern { pattern, color }, area);
+}
+
+/// Background texture behind every screen, one `pattern` cell per buffer cell.
+pub struct SubtlePattern {
+    pub pattern: BackgroundPattern,
+    pub color: Color,
+}
+
+impl Widget for SubtlePattern {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for y in 0..area.height {
+            for x in 0..area.width {
+                let (col, row) = (x as usize, y as usize);
+                let c = match self.pattern {
+                    // a dot every 2 columns, staggered by row
+                    BackgroundPattern::Dots => if (col + row).is_multiple_of(2) { '·' } else { ' ' },
+                    BackgroundPattern::Checkerboard => if (col + row).is_multiple_of(2) { '░' } else { ' ' },
+                    // the stripe moves one column left per row
+                    BackgroundPattern::DiagonalLines => if (col + row).is_multiple_of(4) { '/' } else { ' ' },
+                    BackgroundPattern::Blank => ' ',
+                };
+                buf[(area.x + x, area.y + y)].set_char(c).set_fg(self.color);
+            }
+        }
+    }
+}
 
 const CARD_W: usize = 11;
+// Below this room width four cells in a row get too cramped for a card; auto layout switches to 2x2
+const GRID_BELOW_WIDTH: u16 = 4 * (CARD_W as u16 + 4);
 const CARD_H: usize = 7; // top, rank_l, empty, suit, empty, rank_r, bottom
 const MINI_W: usize = 5;
 const MINI_H: usize = 4; // content box target height
 
-// Draw a rounded, white-bordered card with colored suit and ranks
-fn draw_card_box(f: &mut Frame, area: Rect, card: Card) {
+// Draw a rounded, white-bordered card with colored suit and ranks; colorblind mode adds the kind mark to the corners
+fn draw_card_box(f: &mut Frame, area: Rect, card: Card, theme: &Theme) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
@@ -598,15 +1439,33 @@ fn draw_card_box(f: &mut Frame, area: Rect, card: Card) {
     f.render_widget(suit_p, center_area);
     // Rank label (number/letter) in top-left and bottom-right
     let rank_style = Style::default().fg(card.suit.color()).add_modifier(Modifier::BOLD);
+    let mark = theme.kind_mark(card.suit);
     let tl = Rect { x: inner.x, y: inner.y, width: inner.width.min(4), height: 1 };
-    f.render_widget(Paragraph::new(Span::styled(card.rank.to_string(), rank_style)), tl);
+    f.render_widget(Paragraph::new(Span::styled(format!("{}{}", mark, card.rank), rank_style)), tl);
     let br = Rect {
         x: inner.x.saturating_add(inner.width.saturating_sub(4)),
         y: inner.y.saturating_add(inner.height.saturating_sub(1)),
         width: 4,
         height: 1,
     };
-    f.render_widget(Paragraph::new(Span::styled(card.rank.to_string(), rank_style)).alignment(Alignment::Right), br);
+    f.render_widget(Paragraph::new(Span::styled(format!("{}{}", card.rank, mark), rank_style)).alignment(Alignment::Right), br);
+}
+
+// Face-down card: the usual rounded outline filled with a woven back pattern
+fn draw_card_back(f: &mut Frame, area: Rect, theme: &Theme) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::White));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let lines: Vec<Line> = (0..inner.height)
+        .map(|y| {
+            let row: String = (0..inner.width).map(|x| if (x + y) % 2 == 0 { '░' } else { '▒' }).collect();
+            Line::from(Span::styled(row, Style::default().fg(theme.background_dot)))
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), inner);
 }
 
 fn draw_empty_card_box(f: &mut Frame, area: Rect) {
@@ -624,6 +1483,145 @@ fn draw_empty_card_box(f: &mut Frame, area: Rect) {
 
 // (no big room card rendering; use the same compact card style for room)
 
+// One key hint on a screen's bottom border; lower `priority` survives longer on narrow terminals
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Hint {
+    keys: &'static str,
+    action: &'static str,
+    priority: u8, // 0 is never dropped
+}
+
+impl Hint {
+    fn new(keys: &'static str, action: &'static str, priority: u8) -> Self {
+        Hint { keys, action, priority }
+    }
+    fn text(&self) -> String {
+        format!("{} {}", self.keys, self.action)
+    }
+}
+
+// The keys that do something right now, in display order
+fn footer_hints(game: &Game) -> Vec<Hint> {
+    if game.pending_confirm.is_some() {
+        return vec![Hint::new("y", "yes", 0), Hint::new("n", "no", 0)];
+    }
+    if game.show_help {
+        if game.help_full {
+            return vec![Hint::new("←→", "page", 1), Hint::new("↑↓", "scroll", 2), Hint::new("F1", "less", 3), Hint::new("?", "close", 0)];
+        }
+        return vec![Hint::new("↑↓", "scroll", 1), Hint::new("F1", "all", 2), Hint::new("?", "close", 0)];
+    }
+    let help = Hint::new("?", "help", 0);
+    match game.phase {
+        GamePhase::Menu => vec![
+            Hint::new("↑↓", "choose", 1),
+            Hint::new("Enter", "open", 1),
+            Hint::new("w", "weekly seed", 2),
+            Hint::new("V", "what's new", 3),
+            help,
+        ],
+        // Every character is typed here, so there is no `?`
+        GamePhase::NameEntry => {
+            let mut hints = vec![Hint::new("Enter", "start", 0)];
+            if !game.recent_names.is_empty() { hints.push(Hint::new("↑↓", "recent", 2)); }
+            hints.extend([Hint::new("Tab", "mode", 1), Hint::new("Shift+Tab", "hardcore", 2)]);
+            hints
+        }
+        GamePhase::SeedEntry => vec![Hint::new("Enter", "confirm", 0), Hint::new("Esc", "back", 1)],
+        GamePhase::Running => running_hints(game),
+        GamePhase::GameOver => vec![
+            Hint::new("↑↓", "scroll", 2),
+            Hint::new("n", "new", 1),
+            Hint::new("l", "leaderboard", 2),
+            Hint::new("h", "history", 3),
+            Hint::new("m", "menu", 3),
+            help,
+        ],
+        GamePhase::Leaderboard if game.leaderboard_filter.editing => {
+            vec![Hint::new("Enter", "keep", 0), Hint::new("Esc", "clear", 1)]
+        }
+        GamePhase::Leaderboard if game.leaderboard_detail => vec![Hint::new("Enter", "close", 1), help],
+        GamePhase::Leaderboard => vec![
+            Hint::new("↑↓", "select", 2),
+            Hint::new("Enter", "details", 2),
+            Hint::new("s", "sort", 3),
+            Hint::new("f", "fastest", 3),
+            Hint::new("g", "last seed", 3),
+            Hint::new("/", "filter", 3),
+            Hint::new("n", "new", 1),
+            help,
+        ],
+        GamePhase::RunHistory if game.run_open => vec![Hint::new("↑↓", "scroll", 2), Hint::new("Esc", "close", 1), help],
+        GamePhase::RunHistory => vec![
+            Hint::new("↑↓", "select", 2),
+            Hint::new("Enter", "open", 1),
+            Hint::new("n", "new", 2),
+            help,
+        ],
+        GamePhase::Changelog => vec![Hint::new("↑↓", "scroll", 2), Hint::new("Esc", "back", 1), help],
+        GamePhase::Shop => vec![Hint::new("↑↓", "choose", 2), Hint::new("Enter", "pick", 1), Hint::new("Esc", "leave", 1), help],
+        GamePhase::SaveSlots => vec![
+            Hint::new("↑↓", "select", 2),
+            Hint::new("Enter", if game.choosing_slot { "use slot" } else { "continue" }, 1),
+            Hint::new("Del", "delete", 3),
+            Hint::new("Esc", "menu", 1),
+            help,
+        ],
+    }
+}
+
+// During a run: take/weapon/bare only for what the selected card allows, avoid only when allowed
+fn running_hints(game: &Game) -> Vec<Hint> {
+    let mut hints = vec![Hint::new(if game.room_is_grid() { "←→↑↓" } else { "←→" }, "select", 2)];
+    let face_up = game.card_phase[game.selected] == AnimationPhase::FaceUp;
+    if let Some(card) = game.room[game.selected] {
+        hints.push(Hint::new("Enter", "take", 1));
+        if face_up && card.is_monster() {
+            let binds = game.player.weapon.as_ref().is_some_and(|w| w.can_use_on(card.monster_value()));
+            if binds { hints.push(Hint::new("w", "weapon", 2)); }
+            hints.push(Hint::new("b", "bare", 2));
+        }
+    }
+    if game.avoid_status() == AvoidStatus::Ready {
+        hints.push(Hint::new("v", "avoid", 1));
+    }
+    hints.push(Hint::new("?", "help", 0));
+    hints
+}
+
+// Drops the least important hints (the rightmost first among equals) until the rest fit `width`
+fn fit_hints(mut hints: Vec<Hint>, width: usize) -> String {
+    let line = |hints: &[Hint]| hints.iter().map(Hint::text).collect::<Vec<_>>().join(" · ");
+    while Span::raw(line(&hints)).width() > width {
+        let Some(drop) = hints
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| h.priority > 0)
+            .max_by_key(|(i, h)| (h.priority, *i))
+            .map(|(i, _)| i)
+        else {
+            break;
+        };
+        hints.remove(drop);
+    }
+    line(&hints)
+}
+
+// Right-aligned key hints on the bottom border of `area`; returns the width they take
+fn draw_footer(f: &mut Frame, area: Rect, game: &Game) -> u16 {
+    let border = Rect {
+        x: area.x.saturating_add(1),
+        y: area.y.saturating_add(area.height.saturating_sub(1)),
+        width: area.width.saturating_sub(2),
+        height: 1,
+    };
+    let text = fit_hints(footer_hints(game), border.width as usize);
+    let span = Span::styled(text, Style::default().fg(Color::Gray));
+    let width = span.width() as u16;
+    f.render_widget(Paragraph::new(span).alignment(Alignment::Right), border);
+    width
+}
+
 fn centered_rect(pct_x: u16, pct_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -644,6 +1642,17 @@ fn centered_rect(pct_x: u16, pct_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+// Clears a `w`×`h` box centered in `area` (shrunk to fit), draws its border and title, and
+// returns the space inside it for the popup's content
+fn centered_popup(f: &mut Frame, title: &str, w: u16, h: u16, area: Rect, border_color: Color) -> Rect {
+    let rect = centered_rect_fixed(w.min(area.width), h.min(area.height), area);
+    let block = Block::default().title(title).borders(Borders::ALL).border_style(Style::default().fg(border_color));
+    let inner = block.inner(rect);
+    f.render_widget(Clear, rect);
+    f.render_widget(block, rect);
+    inner
+}
+
 fn centered_rect_fixed(w: u16, h: u16, area: Rect) -> Rect {
     // Center a fixed-size rect within area
     let v = Layout::default()
@@ -672,8 +1681,8 @@ fn draw_mini_card_box(f: &mut Frame, area: Rect, card: Card) {
         .border_style(Style::default().fg(Color::White));
     let inner = block.inner(area);
     f.render_widget(block, area);
-    let sym = match card.suit { Suit::Clubs => '♣', Suit::Spades => '♠', Suit::Diamonds => '♦', Suit::Hearts => '♥' };
-    let col = match card.suit { Suit::Hearts | Suit::Diamonds => Color::Red, _ => Color::Gray };
+    let sym = card.suit.symbol();
+    let col = card.suit.color();
     // number/value top-left
     let num = if matches!(card.suit, Suit::Clubs | Suit::Spades) { card.monster_value().to_string() } else { card.rank.to_string() };
     let tl = Rect { x: inner.x, y: inner.y, width: inner.width.min(4), height: 1 };
@@ -683,72 +1692,369 @@ fn draw_mini_card_box(f: &mut Frame, area: Rect, card: Card) {
     f.render_widget(Paragraph::new(Span::styled(sym.to_string(), Style::default().fg(col).add_modifier(Modifier::BOLD))).alignment(Alignment::Right), br);
 }
 
+// Mini card with its suit but no value, for the suits-only deck peek
+fn draw_mini_suit_box(f: &mut Frame, area: Rect, card: Card) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::White));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let sym = Span::styled(card.suit.symbol().to_string(), Style::default().fg(card.suit.color()).add_modifier(Modifier::BOLD));
+    let mid = Rect { y: inner.y + inner.height / 2, height: 1.min(inner.height), ..inner };
+    f.render_widget(Paragraph::new(sym).alignment(Alignment::Center), mid);
+}
+
 // (header/footer helpers removed after layout refactor)
 
-fn draw_help(f: &mut Frame, area: Rect) {
-    let text = Text::from(vec![
-        Line::from(Span::styled("Scoundrel (terminal)", Style::default().add_modifier(Modifier::BOLD).fg(Color::White))),
-        Line::from(""),
-        Line::from(
-            "Setup: Remove red faces + red aces. Deck = 26 black monsters, 9 diamonds (weapons 2-10), 9 hearts (potions 2-10).",
-        ),
-        Line::from(
-            "Room: 4 face-up. Avoid with v (not twice). Take any 3; 1 carries to next room.",
-        ),
-        Line::from("Potions: use at most 1 per turn; extra potions are discarded. Max HP 20."),
-        Line::from("Weapons: binding. Damage = value; remaining monster damage hits you."),
-        Line::from("Rule: after weapon use, can only be used on monsters ≤ last monster’s value."),
-        Line::from(
-            "End: HP<=0 lose (score = hp - remaining monsters). Empty dungeon win (score = hp, or 20+potion if last was potion).",
-        ),
+// What `?` shows on each screen; F1 swaps it for the tabbed full help
+fn help_lines(game: &Game, context: GamePhase) -> Vec<Line<'static>> {
+    if game.help_full { return help_tab_lines(game, context, game.help_tab); }
+    let mut lines = phase_help_lines(context);
+    lines.push(Line::from(""));
+    lines.push(Line::from("F1 — full help: rules, controls, scoring and variants"));
+    lines
+}
+
+fn phase_help_lines(context: GamePhase) -> Vec<Line<'static>> {
+    match context {
+        GamePhase::Menu => help_menu_lines(),
+        GamePhase::NameEntry => help_name_entry_lines(),
+        GamePhase::SeedEntry => help_seed_entry_lines(),
+        GamePhase::Leaderboard => help_leaderboard_lines(),
+        GamePhase::RunHistory => help_run_history_lines(),
+        GamePhase::Running => help_running_lines(),
+        GamePhase::GameOver => help_game_over_lines(),
+        GamePhase::Changelog => help_changelog_lines(),
+        GamePhase::SaveSlots => help_save_slots_lines(),
+        GamePhase::Shop => help_shop_lines(),
+    }
+}
+
+fn help_heading(phase: GamePhase) -> &'static str {
+    match phase {
+        GamePhase::Menu => "Main menu",
+        GamePhase::NameEntry => "Name entry",
+        GamePhase::SeedEntry => "Custom seed",
+        GamePhase::Leaderboard => "Leaderboard",
+        GamePhase::RunHistory => "Run history",
+        GamePhase::Running => "In the dungeon",
+        GamePhase::GameOver => "Game over",
+        GamePhase::Changelog => "What's new",
+        GamePhase::SaveSlots => "Save slots",
+        GamePhase::Shop => "Shop",
+    }
+}
+
+// One page of the full help; each is kept short enough for an 80x24 terminal
+fn help_tab_lines(game: &Game, context: GamePhase, tab: HelpTab) -> Vec<Line<'static>> {
+    match tab {
+        HelpTab::Rules => help_rules_lines(&game.rules),
+        HelpTab::Controls => help_controls_lines(context),
+        HelpTab::Scoring => help_scoring_lines(&game.rules),
+        HelpTab::Variants => help_variants_lines(&game.rules),
+    }
+}
+
+// The current screen's keys, then the ones that work everywhere
+fn help_controls_lines(context: GamePhase) -> Vec<Line<'static>> {
+    let bold = Style::default().add_modifier(Modifier::BOLD).fg(Color::White);
+    let mut lines = vec![Line::from(Span::styled(help_heading(context), bold))];
+    lines.extend(phase_help_lines(context));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Anywhere", bold)));
+    lines.push(Line::from("  ? — this screen's keys, F1 — these pages, Left/Right/Tab — switch page"));
+    lines.push(Line::from("  V — what's new, Ctrl+R — back to the menu, Ctrl+D — debug dump, Ctrl+C — quit"));
+    lines
+}
+
+fn help_scoring_lines(rules: &Rules) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from("Death: HP left minus the value of every monster still in the dungeon."),
+        Line::from("Win: HP left; at full HP, plus the last card's value if it was a potion."),
+        Line::from("Abandoning (r) scores like a death in the current room."),
+        Line::from(format!("Endless: monsters slain, plus {} per dungeon cleared.", ENDLESS_DUNGEON_BONUS)),
         Line::from(""),
-        Line::from(Span::styled("Controls:", Style::default().fg(Color::Gray))),
-        Line::from("  Menu: Up/Down + Enter"),
-        Line::from("  Game: Left/Right select, Enter take, 1-4 quick pick, w weapon, b barehand, v avoid, ? help, q quit"),
+        Line::from("Every mix of variants has its own leaderboard."),
+        Line::from("Classic runs move your rating toward what the run was worth."),
+    ];
+    if rules.rooms_bonus_per_room != 0 {
+        lines.push(Line::from(format!("This run: a win adds {} per room survived.", rules.rooms_bonus_per_room)));
+    }
+    if rules.kill_streak_bonus {
+        lines.push(Line::from("This run: half the longest clean-kill streak is added."));
+    }
+    lines
+}
+
+// Every variant with its flag; the ones this run plays with are marked
+fn help_variants_lines(rules: &Rules) -> Vec<Line<'static>> {
+    let on = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let row = |active: bool, flag: &str, text: &str| {
+        let mark = if active { Span::styled("● ", on) } else { Span::raw("○ ") };
+        Line::from(vec![mark, Span::raw(format!("{:<18}{}", flag, text))])
+    };
+    let mut lines = vec![Line::from("Start a run with these flags; ● marks the ones in play."), Line::from("")];
+    lines.extend([
+        row(rules.no_weapons, "--no-weapons", "diamonds are discarded"),
+        row(rules.no_potions, "--no-potions", "hearts are discarded"),
+        row(rules.hardcore, "--hardcore", "no avoiding, no damage preview"),
+        row(rules.hidden_cards > 0, "--hidden-cards N", "N new cards per room face down"),
+        row(rules.allow_deck_peek && !rules.show_full_next_room, "--peek", "suits of the next room"),
+        row(rules.show_full_next_room, "--full-peek", "the next room in full"),
+        row(rules.rooms_bonus_per_room != 0, "--room-bonus N", "a win adds N per room"),
+        row(rules.weapon_breaks_after_uses.is_some(), "--weapon-uses N", "weapons break after N fights"),
+        row(rules.enable_shop, "--shop", "a shop every third room"),
+        row(rules.shuffle_avoided_cards, "--shuffle-avoided", "avoided rooms are shuffled"),
+        row(rules.kill_streak_bonus, "--streak-bonus", "clean-kill streaks score"),
     ]);
-    let block = Block::default()
-        .title("Help")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Gray));
-    let area_inner = block.inner(area);
-    f.render_widget(Clear, area);
-    f.render_widget(block, area);
+    lines
+}
+
+// Screen-specific help fits a smaller popup than the full reference
+fn help_rect(game: &Game, area: Rect) -> Rect {
+    if game.help_full { centered_rect(90, 85, area) } else { centered_rect(60, 50, area) }
+}
+
+fn draw_help(f: &mut Frame, area: Rect, game: &Game, context: GamePhase) {
+    let lines = help_lines(game, context);
+    let title = if game.help_full { String::new() } else { format!("Help — {}", help_heading(context)) };
+    // `area` is already the popup's rect (see `help_rect`), so this only draws it
+    let area_inner = centered_popup(f, &title, area.width, area.height, area, Color::Gray);
+    // The full help's pages sit in the top border, where a title would go
+    if game.help_full {
+        let tabs_area = Rect { x: area.x.saturating_add(1), y: area.y, width: area.width.saturating_sub(2), height: 1.min(area.height) };
+        let tabs = Tabs::new(HelpTab::ALL.iter().map(|t| t.label()))
+            .select(game.help_tab.index())
+            .style(Style::default().fg(Color::Gray))
+            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD | Modifier::REVERSED));
+        f.render_widget(tabs, tabs_area);
+    }
     // Dimmer help text for readability across screens
-    let help_para = Paragraph::new(text)
+    let help_para = Paragraph::new(Text::from(lines))
         .style(Style::default().fg(Color::Gray))
         .wrap(Wrap { trim: true });
-    f.render_widget(help_para, area_inner);
+    // Clamped on the wrapped rows, so End and long scrolls stop at the last line on narrow popups too
+    let max_scroll = help_para.line_count(area_inner.width).saturating_sub(area_inner.height as usize) as u16;
+    let scroll = game.help_scroll.min(max_scroll);
+    f.render_widget(help_para.scroll((scroll, 0)), area_inner);
+    // Scroll hint on the bottom border when the content does not fit
+    if max_scroll > 0 {
+        let hint_area = Rect {
+            x: area.x.saturating_add(1),
+            y: area.y.saturating_add(area.height.saturating_sub(1)),
+            width: area.width.saturating_sub(2),
+            height: 1,
+        };
+        let hint = Paragraph::new(Span::styled("↑↓ scroll", Style::default().fg(Color::Gray))).alignment(Alignment::Right);
+        f.render_widget(hint, hint_area);
+    }
+}
+
+fn help_controls_header() -> Line<'static> {
+    Line::from(Span::styled("Controls:", Style::default().fg(Color::Gray)))
+}
+
+fn help_menu_lines() -> Vec<Line<'static>> {
+    vec![
+        help_controls_header(),
+        Line::from("  Up/Down — move selection"),
+        Line::from("  Enter — confirm (New Game / Continue / Custom Seed / Leaderboard / Run History / Quit)"),
+        Line::from("  n — new run (asks for a name)"),
+        Line::from("  w — play the seed of the week (everyone gets the same dungeon until Monday)"),
+        Line::from("  l — leaderboard, h — run history"),
+        Line::from("  V — what's new in this version (from any screen)"),
+        Line::from("  ? — toggle help, q/Esc — quit"),
+    ]
 }
 
-fn game_event_line(ev: &GameEvent) -> String {
+fn help_name_entry_lines() -> Vec<Line<'static>> {
+    vec![
+        help_controls_header(),
+        Line::from("  Type your run name (max 20 characters)"),
+        Line::from("  Your last name is pre-filled: Enter keeps it, typing replaces it"),
+        Line::from("  Backspace — delete, Enter — start the run"),
+        Line::from("  Up/Down — recent names from the leaderboard"),
+        Line::from("  Tab — switch between Classic and Endless"),
+        Line::from("  Shift+Tab — hardcore on/off (no avoiding, no damage preview)"),
+        Line::from("  Esc — quit"),
+        Line::from(""),
+        Line::from("Endless: a cleared dungeon reshuffles its discards (weapon kept) and play goes on."),
+        Line::from("Score is monsters slain plus a bonus per dungeon cleared; only death ends the run."),
+    ]
+}
+
+fn help_seed_entry_lines() -> Vec<Line<'static>> {
+    vec![
+        Line::from("Runs with the same seed deal the same dungeon. Share the code shown in the status bar."),
+        Line::from(""),
+        help_controls_header(),
+        Line::from("  Type a decimal number or a letter/digit code"),
+        Line::from("  Enter — continue to name entry, Esc — back to menu"),
+    ]
+}
+
+fn help_leaderboard_lines() -> Vec<Line<'static>> {
+    vec![
+        Line::from("Top 10 runs. The podium is always the top three by score; the sort orders the list below. 🏆 = cleared dungeon, 💀 = fell, 🏳 = abandoned."),
+        Line::from("Each row: rank, result, score (time with f), name, and on wide terminals rooms, kills, potions and avoids."),
+        Line::from("The title names the sort: score, lowest score, name, date, or win rate per player."),
+        Line::from(""),
+        help_controls_header(),
+        Line::from("  Up/Down — select a run (wraps), Enter — details"),
+        Line::from("  Delete/Backspace — remove the selected run (asks first)"),
+        Line::from("  Tab — Classic / Endless board"),
+        Line::from("  w — wins only on/off"),
+        Line::from("  f — fastest wins on/off (timed wins, quickest first)"),
+        Line::from("  g — only runs on the seed played most recently, on/off"),
+        Line::from("  s — sort by score, lowest score, name, date or win rate; r — reverse it"),
+        Line::from("  / — filter by name (Enter keeps it, Esc clears it)"),
+        Line::from("  e — export every score to a file in the working directory"),
+        Line::from("  n — new run, m — menu"),
+        Line::from("  ? — toggle help, q/Esc — quit"),
+    ]
+}
+
+fn help_rules_lines(rules: &Rules) -> Vec<Line<'static>> {
+    // Rules switched off for this run are starred and explained underneath
+    let star = |off: bool| if off { "* " } else { "" };
+    let mut lines = vec![
+        Line::from("Setup: Remove red faces + red aces. Deck = 26 black monsters,"),
+        Line::from("9 diamonds (weapons 2-10), 9 hearts (potions 2-10)."),
+        Line::from(format!("{}Room: 4 face-up. Avoid with v (not twice). Take any 3; 1 carries to next room.", star(rules.hardcore))),
+        Line::from(format!("{}Potions: use at most 1 per turn; extra potions are discarded. Max HP 20.", star(rules.no_potions))),
+        Line::from(format!("{}Weapons: binding. Damage = value; remaining monster damage hits you.", star(rules.no_weapons))),
+        Line::from("Rule: after weapon use, can only be used on monsters ≤ last monster’s value."),
+        Line::from("End: HP<=0 loses. An empty dungeon wins (see Scoring)."),
+    ];
+    if rules.no_weapons || rules.no_potions {
+        lines.push(Line::from("* Challenge run: starred cards are discarded unused."));
+    }
+    if rules.hardcore {
+        lines.push(Line::from("* Hardcore: rooms can't be avoided and fight damage isn't previewed."));
+    }
+    if rules.shuffle_avoided_cards {
+        lines.push(Line::from("Avoided rooms are shuffled before going under the deck."));
+    }
+    if let Some(uses) = rules.weapon_breaks_after_uses {
+        lines.push(Line::from(format!("Durability: a weapon breaks after {} fights; the bar under it shows what's left.", uses)));
+    }
+    lines
+}
+
+fn help_running_lines() -> Vec<Line<'static>> {
+    vec![
+        help_controls_header(),
+        Line::from("  Left/Right — select card (Up/Down too in the 2x2 layout), Enter/Space — take"),
+        Line::from("  1-4 — quick pick, w — weapon, b — barehand"),
+        Line::from("  v — avoid room, n/r — abandon run (asks first; scored as a loss)"),
+        Line::from("  ? — toggle help, q/Esc — quit"),
+    ]
+}
+
+fn help_run_history_lines() -> Vec<Line<'static>> {
+    vec![
+        Line::from("Every finished run is kept with its full event log (newest first)."),
+        Line::from(""),
+        help_controls_header(),
+        Line::from("  Up/Down — select run, Enter — open/close its history"),
+        Line::from("  Up/Down, PageUp/PageDown, mouse wheel — scroll an open history"),
+        Line::from("  n — new run, l — leaderboard, h — run history, m — menu"),
+        Line::from("  ? — toggle help, q/Esc — quit (Esc closes an open history)"),
+    ]
+}
+
+fn help_game_over_lines() -> Vec<Line<'static>> {
+    vec![
+        help_controls_header(),
+        Line::from("  Up/Down, PageUp/PageDown, Home/End, mouse wheel — scroll history"),
+        Line::from("  n — new run, l — leaderboard, h — run history, m — menu"),
+        Line::from("  ? — toggle help, q/Esc — quit"),
+    ]
+}
+
+fn help_shop_lines() -> Vec<Line<'static>> {
+    vec![
+        Line::from("Every third room ends here. Trading gives up the card left over from the room;"),
+        Line::from("in return each monster among the next three cards loses 1 (an ace becomes a king, twos stay)."),
+        Line::from(""),
+        help_controls_header(),
+        Line::from("  Up/Down — choose, Enter — trade or leave"),
+        Line::from("  Esc — leave without trading, r — abandon run"),
+        Line::from("  ? — toggle help, q — quit"),
+    ]
+}
+
+fn help_save_slots_lines() -> Vec<Line<'static>> {
+    vec![
+        Line::from("Runs are saved after every move; a finished run frees its slot."),
+        Line::from(""),
+        help_controls_header(),
+        Line::from("  Up/Down — select slot, Enter — continue the run (or use the slot for a new one)"),
+        Line::from("  Delete/Backspace — delete the saved run"),
+        Line::from("  Esc — back to the menu"),
+        Line::from("  ? — toggle help, q — quit"),
+    ]
+}
+
+fn help_changelog_lines() -> Vec<Line<'static>> {
+    vec![
+        Line::from("What changed in each version of the game."),
+        Line::from(""),
+        help_controls_header(),
+        Line::from("  Up/Down, PageUp/PageDown, Home/End, mouse wheel — scroll"),
+        Line::from("  Esc — back to the previous screen"),
+        Line::from("  n — new run, l — leaderboard, h — run history, m — menu"),
+        Line::from("  ? — toggle help, q — quit"),
+    ]
+}
+
+// "Ace of Spades", the way a screen reader should say a card
+fn card_spoken(card: &Card) -> String {
+    let rank = match card.rank.value() {
+        1 => "Ace".to_string(),
+        11 => "Jack".to_string(),
+        12 => "Queen".to_string(),
+        13 => "King".to_string(),
+        n => n.to_string(),
+    };
+    let suit = match card.suit {
+        Suit::Clubs => "Clubs",
+        Suit::Diamonds => "Diamonds",
+        Suit::Hearts => "Hearts",
+        Suit::Spades => "Spades",
+    };
+    format!("{} of {}", rank, suit)
+}
+
+/// One spoken sentence per event for `--plain`; the words stand in for the emoji of `GameEvent`'s `Display`.
+pub fn narration_line(ev: &GameEvent) -> String {
     match ev {
-        GameEvent::RoomStart { number } => format!("Room {}", number),
-        GameEvent::Potion {
-            value,
-            hp_before,
-            hp_after,
-        } => format!("🧪 +{} HP {}→{}", value, hp_before, hp_after),
-        GameEvent::PotionDiscarded { value } => format!("🧪 discarded {}", value),
-        GameEvent::Weapon { value } => format!("🗡️ equip {}", value),
-        GameEvent::Fight {
-            monster,
-            with_weapon,
-            damage_taken,
-        } => {
-            let w = with_weapon
-                .map(|v| format!(" with {}", v))
-                .unwrap_or_else(|| " barehand".into());
-            let skulls = if *damage_taken > 0 { " 💥" } else { " ✅" };
-            format!("👾 {}{} → dmg {}{}", monster, w, damage_taken, skulls)
-        }
-        GameEvent::Avoid => "🌀 avoid room".into(),
-    }
-}
-
-fn build_history_indented_lines(history: &Vec<GameEvent>) -> Vec<Line<'static>> {
-    if history.is_empty() {
-        return vec![Line::from("No battles happened.")];
+        GameEvent::RoomStart { number } => format!("Room {} begins.", number),
+        GameEvent::Potion { value, hp_before, hp_after } => {
+            format!("Drank a {} potion, HP {} to {}.", value, hp_before, hp_after)
+        }
+        GameEvent::PotionDiscarded { value } => format!("Discarded a {} potion.", value),
+        GameEvent::PotionOverheal { value, capped_at } => format!("{} HP of healing lost above {}.", value, capped_at),
+        GameEvent::Weapon { value } => format!("Equipped a {} weapon.", value),
+        GameEvent::WeaponReplaced { old, new } => format!("Replaced the {} weapon with a {}.", old, new),
+        GameEvent::WeaponDiscarded { value } => format!("Discarded a {} weapon.", value),
+        GameEvent::Fight { monster, with_weapon, damage_taken } => {
+            let how = with_weapon.map(|v| format!("with the {} weapon", v)).unwrap_or_else(|| "barehanded".into());
+            format!("Fought a {} {}, took {} damage.", monster, how, damage_taken)
+        }
+        GameEvent::Avoid { cards } if cards.is_empty() => "Avoided the room.".into(),
+        GameEvent::Avoid { cards } => {
+            let cards: Vec<String> = cards.iter().map(card_spoken).collect();
+            format!("Avoided the room: {}.", cards.join(", "))
+        }
+        GameEvent::Abandoned { room, hp } => format!("Abandoned the run in room {} with {} HP.", room, hp),
+        GameEvent::WeaponDestroyed { value, kills } => format!("The {} weapon broke after {} kill{}.", value, kills, if *kills == 1 { "" } else { "s" }),
     }
+}
+
+/// Event descriptions grouped under the room they happened in, shared by every history view.
+pub fn history_groups(history: &[GameEvent]) -> Vec<(u32, Vec<String>)> {
     let mut groups: Vec<(u32, Vec<String>)> = Vec::new();
     let mut current_room: Option<u32> = None;
     for ev in history {
@@ -758,7 +2064,7 @@ fn build_history_indented_lines(history: &Vec<GameEvent>) -> Vec<Line<'static>>
                 groups.push((*number, Vec::new()));
             }
             _ => {
-                let s = game_event_line(ev);
+                let s = ev.to_string();
                 if let Some((_, vec)) = groups.last_mut() {
                     vec.push(s);
                 } else {
@@ -768,8 +2074,15 @@ fn build_history_indented_lines(history: &Vec<GameEvent>) -> Vec<Line<'static>>
             }
         }
     }
+    groups
+}
+
+fn build_history_indented_lines(history: &[GameEvent]) -> Vec<Line<'static>> {
+    if history.is_empty() {
+        return vec![Line::from("No battles happened.")];
+    }
     let mut lines: Vec<Line> = Vec::new();
-    for (room, evs) in groups {
+    for (room, evs) in history_groups(history) {
         lines.push(Line::from(Span::styled(format!("Room {}", room), Style::default().add_modifier(Modifier::BOLD))));
         if evs.is_empty() {
             lines.push(Line::from("  (no actions)"));
@@ -782,3 +2095,667 @@ fn build_history_indented_lines(history: &Vec<GameEvent>) -> Vec<Line<'static>>
     }
     lines
 }
+
+/// Rows used by the `--inline` viewport.
+pub const INLINE_HEIGHT: u16 = 12;
+
+/// Compact, borderless rendering for the inline viewport: one text line per room card.
+pub fn draw_inline(f: &mut Frame, game: &Game) {
+    let area = f.area();
+    if game.show_help {
+        draw_help(f, area, game, game.phase);
+        return;
+    }
+    let dim = Style::default().fg(Color::Gray);
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines: Vec<Line> = Vec::new();
+    match game.phase {
+        GamePhase::Menu => {
+            lines.push(Line::from(Span::styled("Scoundrel", bold)));
+            for (i, label) in MENU_ITEMS.iter().enumerate() {
+                let marker = if game.menu_selected == i { "> " } else { "  " };
+                lines.push(Line::from(format!("{}{}", marker, label)));
+            }
+            lines.push(Line::from(Span::styled("↑↓ move · Enter select · ? help · q quit", dim)));
+        }
+        GamePhase::NameEntry => {
+            lines.push(Line::from(Span::styled("Enter your name", bold)));
+            lines.push(Line::from(Span::styled(format!("> {}", game.name_input), Style::default().fg(Color::Cyan))));
+            let hardcore = if game.rules.hardcore { " · hardcore" } else { "" };
+            lines.push(Line::from(Span::styled(format!("Mode: {}{} (Tab, Shift+Tab)", game.game_mode.label(), hardcore), dim)));
+            lines.push(Line::from(Span::styled("Enter start · Backspace delete", dim)));
+        }
+        GamePhase::SeedEntry => {
+            lines.push(Line::from(Span::styled("Custom seed", bold)));
+            lines.push(Line::from(Span::styled(format!("> {}", game.seed_input), Style::default().fg(Color::Cyan))));
+            if let Some(err) = &game.seed_error {
+                lines.push(Line::from(Span::styled(err.clone(), Style::default().fg(Color::LightRed))));
+            }
+            lines.push(Line::from(Span::styled("Enter continue · Esc menu", dim)));
+        }
+        GamePhase::Leaderboard => {
+            lines.push(Line::from(Span::styled("Leaderboard", bold)));
+            let shown = INLINE_HEIGHT.saturating_sub(2) as usize;
+            let view = game.leaderboard_view();
+            for (i, (_, entry)) in view.iter().take(shown).enumerate() {
+                let emoji = entry.result_emoji();
+                lines.push(Line::from(format!("{:>2}. {} {}  {}", i + 1, emoji, entry.score, entry.name)));
+            }
+            if view.is_empty() { lines.push(Line::from("No scores yet.")); }
+            lines.push(Line::from(Span::styled("n new run · m menu · q quit", dim)));
+        }
+        GamePhase::RunHistory if game.run_open => {
+            let shown = INLINE_HEIGHT.saturating_sub(1) as usize;
+            let events = game.runs.get(game.runs_cursor).map(|r| history_groups(&r.events)).unwrap_or_default();
+            let text: Vec<String> = events
+                .into_iter()
+                .flat_map(|(room, evs)| std::iter::once(format!("Room {}", room)).chain(evs.into_iter().map(|e| format!("  {}", e))))
+                .collect();
+            let start = (game.run_scroll as usize).min(text.len().saturating_sub(shown));
+            for t in text.into_iter().skip(start).take(shown) { lines.push(Line::from(t)); }
+            lines.push(Line::from(Span::styled("↑↓ scroll · Enter/Esc back", dim)));
+        }
+        GamePhase::Shop => {
+            lines.push(Line::from(Span::styled(format!("Shop — after room {}", game.room_number), bold)));
+            let next: Vec<String> = game.deck.top_n_cards(3).iter().map(Card::to_string).collect();
+            lines.push(Line::from(format!("Next: {}", next.join(" "))));
+            for (i, text) in shop_options(game).into_iter().enumerate() {
+                let marker = if i == game.shop_cursor { "> " } else { "  " };
+                lines.push(Line::from(format!("{}{}", marker, text)));
+            }
+            lines.push(Line::from(Span::styled("↑↓ choose · Enter pick · Esc leave", dim)));
+        }
+        GamePhase::SaveSlots => {
+            lines.push(Line::from(Span::styled("Continue", bold)));
+            for (i, state) in game.slots.iter().enumerate() {
+                let style = if i == game.slot_cursor { Style::default().add_modifier(Modifier::REVERSED) } else { Style::default() };
+                lines.push(Line::from(Span::styled(save_slot_label(i, state), style)));
+            }
+            lines.push(Line::from(Span::styled("↑↓ select · Enter continue · Del delete · Esc menu", dim)));
+        }
+        GamePhase::Changelog => {
+            let shown = INLINE_HEIGHT.saturating_sub(1) as usize;
+            let text = changelog_lines();
+            let start = (game.changelog_scroll as usize).min(text.len().saturating_sub(shown));
+            lines.extend(text.into_iter().skip(start).take(shown));
+            lines.push(Line::from(Span::styled("↑↓ scroll · Esc back", dim)));
+        }
+        GamePhase::RunHistory => {
+            lines.push(Line::from(Span::styled("Run History", bold)));
+            let shown = INLINE_HEIGHT.saturating_sub(2) as usize;
+            let start = (game.runs_cursor + 1).saturating_sub(shown);
+            for (i, run) in game.runs.iter().enumerate().skip(start).take(shown) {
+                let marker = if i == game.runs_cursor { ">" } else { " " };
+                lines.push(Line::from(format!("{} {}  {:>4}  {}", marker, format_ts(run.ts), run.score, run.name)));
+            }
+            if game.runs.is_empty() { lines.push(Line::from("No finished runs yet.")); }
+            lines.push(Line::from(Span::styled("↑↓ select · n new run · m menu · q quit", dim)));
+        }
+        GamePhase::Running => {
+            lines.push(Line::from(Span::styled(
+                format!("Scoundrel · Room {} · Deck {}", game.room_number, game.deck.len()),
+                bold,
+            )));
+            for (i, slot) in game.room.iter().enumerate() {
+                let marker = if i == game.selected { ">" } else { " " };
+                let line = match slot {
+                    Some(_) if game.card_phase[i] == AnimationPhase::FaceDown => {
+                        Line::from(vec![Span::raw(format!("{} {}. ", marker, i + 1)), Span::styled("??  face down", dim)])
+                    }
+                    Some(card) => {
+                        let (label, col) = card_type_label(card.suit, &game.theme);
+                        Line::from(vec![
+                            Span::raw(format!("{} {}. ", marker, i + 1)),
+                            Span::styled(format!("{:<4}", card.to_string()), Style::default().fg(card.suit.color()).add_modifier(Modifier::BOLD)),
+                            Span::styled(label, Style::default().fg(col)),
+                        ])
+                    }
+                    None => Line::from(Span::styled(format!("{} {}. —", marker, i + 1), dim)),
+                };
+                lines.push(line);
+            }
+            let mut status = vec![Span::styled(format!("HP {}/{}", game.player.hp, game.player.max_hp), bold)];
+            if let Some(dmg) = projected_damage(game) {
+                status.push(Span::styled(format!(" (-{})", dmg), Style::default().fg(Color::LightRed)));
+            }
+            status.push(Span::raw(format!("  |  Weapon {}", weapon_summary(game))));
+            lines.push(Line::from(status));
+            let last = game.notice.as_ref().map(|(t, _)| t.clone()).or_else(|| game.log.last().cloned());
+            lines.push(Line::from(Span::styled(last.unwrap_or_default(), dim)));
+            lines.push(Line::from(Span::styled("←→ select · Enter take · 1-4 pick · w/b · v avoid · ? help · q quit", dim)));
+        }
+        GamePhase::GameOver => {
+            for text in inline_summary(game) { lines.push(Line::from(text)); }
+            lines.push(Line::from(Span::styled("n new run · l leaderboard · q quit", dim)));
+        }
+    }
+    f.render_widget(Paragraph::new(Text::from(lines)), area);
+}
+
+/// Plain-text run summary, also printed to the scrollback when leaving inline mode.
+pub fn inline_summary(game: &Game) -> Vec<String> {
+    let score = game.score.unwrap_or(0);
+    let mut out = vec![format!(
+        "{} {}{} — Score {}",
+        if game.outcome == Some(Outcome::Victory) { "🏆" } else { "💀" },
+        game.player_name,
+        rating_suffix(game),
+        score
+    )];
+    if let Some(delta) = game.best_delta { out.push(delta.describe()); }
+    if let Some(pos) = game.new_rank_pos { out.push(format!("Rank #{}", pos + 1)); }
+    out.extend(seed_rank_text(game));
+    out.push(format!("Seed {}", game.seed_code()));
+    if let Some(cause) = &game.death_cause {
+        out.push(cause.describe());
+    } else if let Some(card) = game.last_resolved {
+        out.push(format!("Dungeon cleared — final card {}", card));
+    }
+    out
+}
+
+/// `--no-color`: every drawn cell back to the terminal's own colors. Bold, dim and reverse stay,
+/// so the selection and highlights still show.
+pub fn strip_colors(buf: &mut Buffer) {
+    for cell in buf.content.iter_mut() {
+        cell.set_fg(Color::Reset);
+        cell.set_bg(Color::Reset);
+    }
+}
+
+/// Narration rows kept at the bottom of the `--plain` screen, above the key hints.
+const NARRATION_ROWS: u16 = 5;
+
+/// Screen-reader rendering for `--plain`: left-aligned sentences with no borders, colors or
+/// background, the latest events at a fixed position near the bottom and the keys on the last row.
+pub fn draw_plain(f: &mut Frame, game: &Game) {
+    let area = f.area();
+    let narration_rows = NARRATION_ROWS.min(area.height.saturating_sub(2));
+    let body_rows = area.height.saturating_sub(narration_rows + 1);
+    let body = Rect { height: body_rows, ..area };
+    let narration = Rect { y: area.y + body_rows, height: narration_rows, ..area };
+    let keys = Rect { y: area.y + body_rows + narration_rows, height: 1.min(area.height), ..area };
+
+    let mut lines = plain_screen_lines(game);
+    if let Some(confirm) = game.pending_confirm {
+        lines.push(String::new());
+        lines.push(format!("Question: {} Press y for yes or n for no.", confirm.prompt(game)));
+    }
+    let lines: Vec<Line> = lines.into_iter().map(Line::from).collect();
+    f.render_widget(Paragraph::new(Text::from(lines)).wrap(Wrap { trim: false }), body);
+
+    let mut events: Vec<String> = game.history.iter().map(narration_line).collect();
+    if let Some((text, _)) = &game.notice { events.push(text.clone()); }
+    let start = events.len().saturating_sub(narration_rows as usize);
+    let events: Vec<Line> = events.into_iter().skip(start).map(Line::from).collect();
+    f.render_widget(Paragraph::new(Text::from(events)), narration);
+
+    let hints: Vec<String> = footer_hints(game).iter().map(Hint::text).collect();
+    f.render_widget(Paragraph::new(format!("Keys: {}", hints.join(", "))), keys);
+}
+
+fn plain_screen_lines(game: &Game) -> Vec<String> {
+    if game.show_welcome {
+        return vec![
+            "Welcome to Scoundrel. Clear the dungeon four cards at a time.".into(),
+            "Monsters are clubs and spades and hit you for their value. Weapons are diamonds and soften the blows they block. Potions are hearts and heal, one per room.".into(),
+            "Press any key to start. Question mark shows the full rules at any time.".into(),
+        ];
+    }
+    if game.show_help {
+        let mut text: Vec<String> = help_lines(game, game.phase).iter().map(Line::to_string).collect();
+        if game.help_full {
+            let page = format!("Help page {}, {} of {}. Left and Right change page.", game.help_tab.label(), game.help_tab.index() + 1, HelpTab::ALL.len());
+            text.insert(0, page);
+        }
+        return text.into_iter().skip(game.help_scroll as usize).collect();
+    }
+    let mut out: Vec<String> = Vec::new();
+    match game.phase {
+        GamePhase::Menu => {
+            out.push(format!("Scoundrel menu. Selected: {}.", MENU_ITEMS[game.menu_selected]));
+            for (i, label) in MENU_ITEMS.iter().enumerate() { out.push(format!("{}. {}", i + 1, label)); }
+        }
+        GamePhase::NameEntry => {
+            out.push(format!("Enter your name: {}", game.name_input));
+            out.push(format!("Mode: {}{}.", game.game_mode.label(), if game.rules.hardcore { ", hardcore" } else { "" }));
+        }
+        GamePhase::SeedEntry => {
+            out.push(format!("Custom seed: {}", game.seed_input));
+            if let Some(err) = &game.seed_error { out.push(format!("Error: {}", err)); }
+        }
+        GamePhase::Leaderboard => {
+            let view = game.leaderboard_view();
+            out.push(format!("Leaderboard, {} runs.", view.len()));
+            for (i, (_, entry)) in view.iter().take(10).enumerate() {
+                let outcome = if entry.won { "won" } else if entry.abandoned { "abandoned" } else { "died" };
+                out.push(format!("{}. {}, {} points, {}.", i + 1, entry.name, entry.score, outcome));
+            }
+        }
+        GamePhase::RunHistory if game.run_open => {
+            if let Some(run) = game.runs.get(game.runs_cursor) {
+                out.push(format!("Run by {} on {}, score {}.", run.name, format_ts(run.ts), run.score));
+                out.extend(run.events.iter().map(narration_line).skip(game.run_scroll as usize));
+            }
+        }
+        GamePhase::RunHistory => {
+            out.push(format!("Run history, {} runs.", game.runs.len()));
+            if let Some(run) = game.runs.get(game.runs_cursor) {
+                let outcome = if run.won { "won" } else { "died" };
+                out.push(format!("Selected: {} on {}, {} points, {}.", run.name, format_ts(run.ts), run.score, outcome));
+            }
+        }
+        GamePhase::Shop => {
+            let next: Vec<String> = game.deck.top_n_cards(3).iter().map(card_spoken).collect();
+            out.push(format!("Shop after room {}. The next cards are: {}.", game.room_number, next.join(", ")));
+            let options = shop_options(game);
+            out.push(format!("Selected: {}.", options[game.shop_cursor]));
+            out.extend(options.iter().enumerate().map(|(i, text)| format!("{}. {}", i + 1, text)));
+        }
+        GamePhase::SaveSlots => {
+            out.push(format!("Continue. Selected: slot {}.", game.slot_cursor + 1));
+            out.extend(game.slots.iter().enumerate().map(|(i, state)| save_slot_label(i, state)));
+        }
+        GamePhase::Changelog => {
+            out.extend(changelog_lines().iter().map(Line::to_string).skip(game.changelog_scroll as usize));
+        }
+        GamePhase::Running => {
+            let (resolved, total) = game.progress();
+            out.push(format!("Room {}. {} cards left in the deck, {} of {} resolved.", game.room_number, game.deck.len(), resolved, total));
+            for (i, slot) in game.room.iter().enumerate() {
+                let selected = if i == game.selected { " Selected." } else { "" };
+                let text = match slot {
+                    Some(_) if game.card_phase[i] == AnimationPhase::FaceDown => "face down.".to_string(),
+                    Some(card) => format!("{}, {}.", card_spoken(card), card_type_label(card.suit, &game.theme).0.to_lowercase()),
+                    None => "taken.".to_string(),
+                };
+                out.push(format!("Card {}: {}{}", i + 1, text, selected));
+            }
+            if !game.rules.hardcore && let Some(text) = room_summary_text(game) { out.push(format!("{}.", text)); }
+            let weapon = match &game.player.weapon {
+                Some(w) => {
+                    let binds = match w.last_monster {
+                        Some(last) => format!("binds to {} or less", last),
+                        None => "binds to any monster".to_string(),
+                    };
+                    let uses = w.uses_remaining.map(|n| format!(", {} uses left", n)).unwrap_or_default();
+                    format!("weapon {} {}{}", w.value, binds, uses)
+                }
+                None => "no weapon".to_string(),
+            };
+            out.push(format!("HP {} of {}, {}.", game.player.hp, game.player.max_hp, weapon));
+            if let Some(dmg) = projected_damage(game) { out.push(format!("Taking the selected card costs {} HP.", dmg)); }
+        }
+        GamePhase::GameOver => {
+            let score = game.score.unwrap_or(0);
+            let result = if game.outcome == Some(Outcome::Victory) { "won" } else { "died" };
+            out.push(format!("Game over. {} {} with a score of {}.", game.player_name, result, score));
+            if let Some(delta) = game.best_delta { out.push(delta.describe()); }
+            if let Some(r) = game.rating_change { out.push(format!("Rating {:.0}, {:+.0}.", r.rating, r.last_delta)); }
+            out.push(format!("{}, {} cards played.", discard_summary(game), game.cards_seen_this_run()));
+            if let Some(pos) = game.new_rank_pos { out.push(format!("Rank {} on the leaderboard.", pos + 1)); }
+            if let Some(text) = seed_rank_text(game) { out.push(format!("{}.", text.trim_end_matches('!'))); }
+            if let Some(cause) = &game.death_cause { out.push(cause.describe()); }
+            out.extend(game.stats.rows().iter().map(|(k, v)| format!("{}: {}.", k, v)));
+            out.extend(game.history.iter().map(narration_line).skip(game.game_over_scroll as usize));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use crate::game::PlayerAction;
+    use crate::game::fixtures::{deal, dealt};
+
+    fn menu_game() -> Game {
+        Game::sandboxed(1, Rules::default())
+    }
+
+    // A game dealt `text` in reading order and played slot by slot until it ends
+    fn finished(text: &str) -> Game {
+        let mut game = deal(text);
+        for slot in 0..4 {
+            if game.phase != GamePhase::Running { break; }
+            game.apply_action(PlayerAction::TakeCard { slot, mode: UseMode::Default }).expect("slot holds a card");
+        }
+        assert_eq!(game.phase, GamePhase::GameOver);
+        game
+    }
+
+    fn find(rows: &[String], text: &str) -> Option<(u16, u16)> {
+        rows.iter().enumerate().find_map(|(y, r)| r.find(text).map(|i| (y as u16, r[..i].chars().count() as u16)))
+    }
+
+    #[test]
+    fn help_scrolls_down_to_its_last_wrapped_line() {
+        let mut game = menu_game();
+        game.show_help = true;
+        game.help_scroll = u16::MAX;
+        // The popup is 22 columns wide inside, so the help wraps to far more rows than it has lines
+        let rows = TestRenderer::new(40, 20).render(&game);
+        assert!(rows.iter().any(|r| r.contains("variants")), "last help line not reachable:\n{}", rows.join("\n"));
+    }
+
+    #[test]
+    fn tall_victory_screen_draws_the_art_banner() {
+        let game = finished("2c 3c 4c 5c");
+        let mut renderer = TestRenderer::new(100, 40);
+        let rows = renderer.render(&game);
+        let (y, x) = find(&rows, VICTORY_ART[0]).expect("art banner drawn");
+        for (i, line) in VICTORY_ART.iter().enumerate() {
+            assert!(rows[y as usize + i].contains(line), "art line {} missing", i);
+        }
+        assert!(find(&rows, VICTORY_LINE[0]).is_none());
+        let cell = renderer.cell_at(y, x).expect("inside the screen");
+        assert_eq!(cell.fg, game.theme.gold);
+        assert!(cell.modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn short_victory_screen_draws_the_one_line_banner() {
+        let game = finished("2c 3c 4c 5c");
+        let mut renderer = TestRenderer::new(100, 24);
+        let rows = renderer.render(&game);
+        let (y, x) = find(&rows, VICTORY_LINE[0]).expect("line banner drawn");
+        assert!(find(&rows, VICTORY_ART[0]).is_none());
+        let cell = renderer.cell_at(y, x).expect("inside the screen");
+        assert_eq!(cell.fg, game.theme.gold);
+        assert!(cell.modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn death_screen_has_no_banner() {
+        let game = finished("Ks Qs 2c 3c");
+        let rows = TestRenderer::new(100, 40).render(&game);
+        assert!(find(&rows, VICTORY_ART[0]).is_none() && find(&rows, VICTORY_LINE[0]).is_none());
+    }
+
+    // A run on its first room, dealt face up with nothing left animating
+    fn running(text: &str) -> Game {
+        let mut game = dealt(text);
+        game.apply_action(PlayerAction::SelectSlot(0)).expect("run in progress");
+        game.theme.background_pattern = BackgroundPattern::Blank;
+        game
+    }
+
+    // The room rows of an 80x30 frame; the status timer below them moves with the clock
+    fn room_rows(game: &Game) -> String {
+        TestRenderer::new(80, 30).render(game)[1..15].join("\n")
+    }
+
+    #[test]
+    fn row_layout_snapshot() {
+        let mut game = running("Kc 7d 5h 2s 9c");
+        game.config.room_layout = RoomLayout::Row;
+        let expected = [
+            "│┌──────────────────┐┌─────────────────┐┌──────────────────┐┌─────────────────┐│",
+            "││1                 ││2                ││3                 ││4                ││",
+            "││                  ││                 ││                  ││                 ││",
+            "││                  ││                 ││                  ││                 ││",
+            "││    ╭─────────╮   ││   ╭─────────╮   ││    ╭─────────╮   ││   ╭─────────╮   ││",
+            "││    │K        │   ││   │7        │   ││    │5        │   ││   │2        │   ││",
+            "││    │         │   ││   │         │   ││    │         │   ││   │         │   ││",
+            "││    │    ♣    │   ││   │    ♦    │   ││    │    ♥    │   ││   │    ♠    │   ││",
+            "││    │         │   ││   │         │   ││    │         │   ││   │         │   ││",
+            "││    │        K│   ││   │        7│   ││    │        5│   ││   │        2│   ││",
+            "││    ╰─────────╯   ││   ╰─────────╯   ││    ╰─────────╯   ││   ╰─────────╯   ││",
+            "││       Enemy      ││     Weapon      ││      Potion      ││      Enemy      ││",
+            "││                 1││                2││                 3││                4││",
+            "│└──────────────────┘└─────────────────┘└──────────────────┘└─────────────────┘│",
+        ];
+        assert_eq!(room_rows(&game), expected.join("\n"));
+    }
+
+    #[test]
+    fn colorblind_row_snapshot() {
+        let mut game = running("Kc 7d 5h 2s 9c");
+        game.config.room_layout = RoomLayout::Row;
+        game.theme.colorblind = true;
+        let expected = [
+            "│╔══════════════════╗┌─────────────────┐┌──────────────────┐┌─────────────────┐│",
+            "│║1                 ║│2                ││3                 ││4                ││",
+            "│║                  ║│                 ││                  ││                 ││",
+            "│║                  ║│                 ││                  ││                 ││",
+            "│║    ╭─────────╮   ║│   ╭─────────╮   ││    ╭─────────╮   ││   ╭─────────╮   ││",
+            "│║    │⚔K       │   ║│   │†7       │   ││    │+5       │   ││   │⚔2       │   ││",
+            "│║    │         │   ║│   │         │   ││    │         │   ││   │         │   ││",
+            "│║    │    ♣    │   ║│   │    ♦    │   ││    │    ♥    │   ││   │    ♠    │   ││",
+            "│║    │         │   ║│   │         │   ││    │         │   ││   │         │   ││",
+            "│║    │       K⚔│   ║│   │       7†│   ││    │       5+│   ││   │       2⚔│   ││",
+            "│║    ╰─────────╯   ║│   ╰─────────╯   ││    ╰─────────╯   ││   ╰─────────╯   ││",
+            "│║       Enemy      ║│     Weapon      ││      Potion      ││      Enemy      ││",
+            "│║                 1║│                2││                 3││                4││",
+            "│╚══════════════════╝└─────────────────┘└──────────────────┘└─────────────────┘│",
+        ];
+        assert_eq!(room_rows(&game), expected.join("\n"));
+    }
+
+    #[test]
+    fn binding_snapshot() {
+        let mut game = running("4c 9s 3h 2d 8c");
+        game.config.room_layout = RoomLayout::Row;
+        game.set_weapon(7);
+        if let Some(w) = game.player.weapon.as_mut() { w.last_monster = Some(5); }
+        let mut renderer = TestRenderer::new(80, 30);
+        let rows = renderer.render(&game);
+        let expected = [
+            "││   ╭─────────╮                                                       Next:  ││",
+            "││   │7      ≤5│                                                       ╭───╮  ││",
+            "││   │         │                                                       │░▒░│  ││",
+            "││   │    ♦    │                                                       │▒░▒│  ││",
+        ];
+        assert_eq!(rows[21..25].join("\n"), expected.join("\n"));
+        // Only the 9 is out of the weapon's reach; the text is the same, the style is not
+        let captions: Vec<(u16, u16)> = rows.iter().enumerate()
+            .flat_map(|(y, r)| r.match_indices("Enemy").map(move |(i, _)| (y as u16, r[..i].chars().count() as u16)))
+            .collect();
+        assert_eq!(captions.len(), 2);
+        let struck: Vec<bool> = captions.iter()
+            .map(|&(y, x)| renderer.cell_at(y, x).expect("caption on screen").modifier.contains(Modifier::CROSSED_OUT))
+            .collect();
+        assert_eq!(struck, vec![false, true]);
+    }
+
+    #[test]
+    fn grid_layout_snapshot() {
+        let mut game = running("Kc 7d 5h 2s 9c");
+        game.config.room_layout = RoomLayout::Grid;
+        let expected = [
+            "│┌─────────────────────────────────────┐┌─────────────────────────────────────┐│",
+            "││1            ╭─────────╮             ││2            ╭─────────╮             ││",
+            "││             │K        │             ││             │7        │             ││",
+            "││             │    ♣    │             ││             │    ♦    │             ││",
+            "││             │        K│             ││             │        7│             ││",
+            "││             ╰──Enemy──╯            1││             ╰─Weapon──╯            2││",
+            "│└─────────────────────────────────────┘└─────────────────────────────────────┘│",
+            "│┌─────────────────────────────────────┐┌─────────────────────────────────────┐│",
+            "││3            ╭─────────╮             ││4            ╭─────────╮             ││",
+            "││             │5        │             ││             │2        │             ││",
+            "││             │    ♥    │             ││             │    ♠    │             ││",
+            "││             │        5│             ││             │        2│             ││",
+            "││             ╰─Potion──╯            3││             ╰──Enemy──╯            4││",
+            "│└─────────────────────────────────────┘└─────────────────────────────────────┘│",
+        ];
+        assert_eq!(room_rows(&game), expected.join("\n"));
+    }
+
+    #[test]
+    fn auto_layout_switches_to_the_grid_on_narrow_screens() {
+        let game = running("Kc 7d 5h 2s 9c");
+        assert_eq!(game.config.room_layout, RoomLayout::Auto);
+        let wide = TestRenderer::new(80, 30).render(&game);
+        let (weapon, potion) = (find(&wide, "Weapon").expect("badge"), find(&wide, "Potion").expect("badge"));
+        assert!(weapon.0 == potion.0 && weapon.1 < potion.1, "one row on 80 columns");
+        let narrow = TestRenderer::new(50, 30).render(&game);
+        let (weapon, potion) = (find(&narrow, "Weapon").expect("badge"), find(&narrow, "Potion").expect("badge"));
+        assert!(weapon.0 < potion.0 && weapon.1 > potion.1, "slot 3 starts the second row on 50 columns");
+    }
+
+    #[test]
+    fn hints_drop_lowest_priority_rightmost_first() {
+        let hints = vec![Hint::new("a", "one", 2), Hint::new("b", "two", 3), Hint::new("c", "three", 3), Hint::new("d", "four", 1), Hint::new("?", "help", 0)];
+        let full = fit_hints(hints.clone(), 80);
+        assert_eq!(full, "a one · b two · c three · d four · ? help");
+        // Each expected line is exactly as wide as the width that first lets it through
+        let expected = ["a one · b two · d four · ? help", "a one · d four · ? help", "d four · ? help", "? help"];
+        let mut width = full.chars().count() - 1;
+        for line in expected {
+            assert_eq!(fit_hints(hints.clone(), width), line, "at width {width}");
+            width = line.chars().count() - 1;
+        }
+        assert_eq!(fit_hints(hints, 0), "? help", "priority 0 is never dropped");
+    }
+
+    fn keys(hints: &[Hint]) -> Vec<&str> {
+        hints.iter().map(|h| h.keys).collect()
+    }
+
+    #[test]
+    fn weapon_hint_only_when_the_weapon_can_bind() {
+        let mut game = running("9c 2c 3h 4d 5c");
+        assert_eq!(keys(&running_hints(&game)), vec!["←→", "Enter", "b", "v", "?"]);
+        game.set_weapon(7);
+        assert_eq!(keys(&running_hints(&game)), vec!["←→", "Enter", "w", "b", "v", "?"]);
+        if let Some(w) = game.player.weapon.as_mut() { w.last_monster = Some(5); }
+        assert_eq!(keys(&running_hints(&game)), vec!["←→", "Enter", "b", "v", "?"], "9 is above the binding");
+    }
+
+    #[test]
+    fn avoid_hint_goes_once_a_card_is_taken() {
+        let mut game = running("9c 2c 3h 4d 5c");
+        game.config.confirm_lethal_fight = false;
+        game.apply_action(PlayerAction::TakeCard { slot: 1, mode: UseMode::Default }).expect("slot holds a card");
+        assert!(!keys(&running_hints(&game)).contains(&"v"));
+    }
+
+    #[test]
+    fn rule_discards_are_narrated() {
+        assert_eq!(narration_line(&GameEvent::PotionDiscarded { value: 5 }), "Discarded a 5 potion.");
+        assert_eq!(narration_line(&GameEvent::WeaponDiscarded { value: 9 }), "Discarded a 9 weapon.");
+    }
+
+    #[test]
+    fn fights_are_narrated_with_the_weapon_or_barehanded() {
+        let armed = GameEvent::Fight { monster: 9, with_weapon: Some(7), damage_taken: 2 };
+        assert_eq!(narration_line(&armed), "Fought a 9 with the 7 weapon, took 2 damage.");
+        let bare = GameEvent::Fight { monster: 4, with_weapon: None, damage_taken: 4 };
+        assert_eq!(narration_line(&bare), "Fought a 4 barehanded, took 4 damage.");
+    }
+
+    #[test]
+    fn avoided_cards_are_spoken_in_words() {
+        let cards = ["As", "Tc", "Qd"].iter().map(|c| c.parse().expect("test card")).collect();
+        assert_eq!(narration_line(&GameEvent::Avoid { cards }), "Avoided the room: Ace of Spades, 10 of Clubs, Queen of Diamonds.");
+        assert_eq!(narration_line(&GameEvent::Avoid { cards: Vec::new() }), "Avoided the room.");
+    }
+
+    #[test]
+    fn broken_weapon_counts_kills_in_words() {
+        assert_eq!(narration_line(&GameEvent::WeaponDestroyed { value: 5, kills: 1 }), "The 5 weapon broke after 1 kill.");
+        assert_eq!(narration_line(&GameEvent::WeaponDestroyed { value: 5, kills: 3 }), "The 5 weapon broke after 3 kills.");
+    }
+
+    #[test]
+    fn plain_room_reads_each_card_then_the_player() {
+        let game = running("Kc 7d 5h 2s 9c");
+        let lines = plain_screen_lines(&game);
+        assert_eq!(lines[0], "Room 1. 1 cards left in the deck, 0 of 5 resolved.");
+        assert_eq!(lines[1], "Card 1: King of Clubs, enemy. Selected.");
+        assert_eq!(lines[2], "Card 2: 7 of Diamonds, weapon.");
+        assert!(lines.iter().any(|l| l == "HP 20 of 20, no weapon."), "{lines:#?}");
+    }
+
+    #[test]
+    fn subtle_pattern_matches_the_paragraph_render_cell_for_cell() {
+        let patterns = [BackgroundPattern::Dots, BackgroundPattern::Checkerboard, BackgroundPattern::DiagonalLines];
+        for pattern in patterns {
+            let mut old = Terminal::new(TestBackend::new(80, 24)).expect("test backend");
+            let mut new = Terminal::new(TestBackend::new(80, 24)).expect("test backend");
+            for (w, h) in [(80, 24), (37, 11)] {
+                old.backend_mut().resize(w, h);
+                new.backend_mut().resize(w, h);
+                // an offset area catches a widget that ignores area.x / area.y
+                let area = Rect::new(3, 2, w - 5, h - 3);
+                old.draw(|f| paragraph_pattern(f, area, pattern, Color::DarkGray)).expect("draw");
+                new.draw(|f| f.render_widget(SubtlePattern { pattern, color: Color::DarkGray }, area)).expect("draw");
+                assert_eq!(old.backend().buffer(), new.backend().buffer(), "{pattern:?} at {w}x{h}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod history_tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn text(history: &[GameEvent]) -> Vec<String> {
+        build_history_indented_lines(history).iter().map(|l| l.to_string()).collect()
+    }
+
+    fn fight(monster: u8, with_weapon: Option<u8>, damage_taken: u8) -> GameEvent {
+        GameEvent::Fight { monster, with_weapon, damage_taken }
+    }
+
+    #[test]
+    fn empty_history_says_no_battles_happened() {
+        assert_eq!(text(&[]), vec!["No battles happened."]);
+    }
+
+    #[test]
+    fn events_before_any_room_start_belong_to_room_one() {
+        let history = [GameEvent::Weapon { value: 5 }, GameEvent::RoomStart { number: 2 }, fight(3, Some(5), 0)];
+        assert_eq!(text(&history), vec!["Room 1", "  🗡️ equip 5", "", "Room 2", "  👾 3 with 5 → dmg 0 ✅", ""]);
+    }
+
+    #[test]
+    fn room_without_events_says_so() {
+        let history = [GameEvent::RoomStart { number: 1 }, GameEvent::RoomStart { number: 2 }];
+        assert_eq!(text(&history), vec!["Room 1", "  (no actions)", "", "Room 2", "  (no actions)", ""]);
+    }
+
+    #[test]
+    fn avoided_rooms_list_their_cards() {
+        let cards: Vec<Card> = ["Kc", "7d"].iter().map(|c| c.parse().expect("test card")).collect();
+        let history = [GameEvent::RoomStart { number: 1 }, GameEvent::Avoid { cards }, GameEvent::Avoid { cards: Vec::new() }];
+        assert_eq!(text(&history)[1..3], ["  🌀 avoided: K♣ 7♦", "  🌀 avoid room"]);
+    }
+
+    #[test]
+    fn potions_show_the_hp_change() {
+        let history = [GameEvent::RoomStart { number: 1 }, GameEvent::Potion { value: 7, hp_before: 9, hp_after: 16 }];
+        assert_eq!(text(&history)[1], "  🧪 +7 HP 9→16");
+    }
+
+    #[test]
+    fn weapons_show_their_value() {
+        let history = [GameEvent::RoomStart { number: 1 }, GameEvent::Weapon { value: 8 }, GameEvent::WeaponReplaced { old: 8, new: 10 }];
+        assert_eq!(text(&history)[1..3], ["  🗡️ equip 8", "  🗡️ swap 8 for 10"]);
+    }
+
+    #[test]
+    fn fights_show_the_weapon_or_barehand() {
+        let history = [GameEvent::RoomStart { number: 1 }, fight(9, Some(6), 3), fight(4, None, 4)];
+        assert_eq!(text(&history)[1..3], ["  👾 9 with 6 → dmg 3 💥", "  👾 4 barehand → dmg 4 💥"]);
+    }
+
+    #[test]
+    fn history_renders_in_a_small_terminal() {
+        let mut history = vec![GameEvent::Weapon { value: 5 }];
+        for n in 1..=30 {
+            history.push(GameEvent::RoomStart { number: n });
+            history.push(fight(n as u8 % 13 + 1, Some(5), 0));
+        }
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).expect("test backend never fails");
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new(build_history_indented_lines(&history)), f.area()))
+            .expect("test backend never fails");
+        let first: String = (0..6).map(|x| terminal.backend().buffer()[(x, 0)].symbol().to_string()).collect();
+        assert_eq!(first, "Room 1");
+    }
+}
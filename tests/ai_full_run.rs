@@ -0,0 +1,42 @@
+//! Whole runs played by `GreedyAi`, as a regression net under every change to the rules.
+
+use scoundrel::ai::GreedyAi;
+use scoundrel::game::{death_score, Game, GamePhase, Outcome, VictoryScore};
+use scoundrel::rules::Rules;
+use scoundrel::simulate;
+
+// A whole run never needs anywhere near this many moves
+const MAX_MOVES: u32 = 500;
+
+#[test]
+fn test_greedy_ai_completes_run_without_panic() {
+    for seed in 0..100 {
+        let mut game = Game::sandboxed(seed, Rules::default());
+        simulate::make_unattended(&mut game);
+        game.new_run();
+        let mut moves = 0;
+        while game.phase != GamePhase::GameOver {
+            assert!(game.apply_ai_action(&GreedyAi).expect("greedy moves are legal"), "seed {seed}: stuck");
+            moves += 1;
+            assert!(moves < MAX_MOVES, "seed {seed}: run never ended");
+        }
+        let score = game.score.expect("a finished run is scored");
+        assert!(!game.history.is_empty(), "seed {seed}: no history");
+        match game.outcome {
+            Some(Outcome::Victory) => {
+                // finish_victory: everything was dealt and played, and the HP left is the score
+                assert!(game.player.hp > 0 && game.player.hp <= game.player.max_hp, "seed {seed}");
+                assert!(game.deck.is_empty() && game.room.iter().all(Option::is_none), "seed {seed}");
+                let expected = VictoryScore::new(game.player.hp, game.player.max_hp, game.last_card_potion_value, game.rooms_survived(), game.rules.rooms_bonus_per_room);
+                assert_eq!(score, expected.total(), "seed {seed}");
+            }
+            Some(Outcome::Death) => {
+                // finish_death: what is left of the dungeon counts against the score
+                assert!(game.player.hp <= 0, "seed {seed}");
+                let left: i32 = game.deck.iter().chain(game.room.iter().flatten()).filter(|c| c.is_monster()).map(|c| c.point_value()).sum();
+                assert_eq!(score, death_score(game.player.hp, left), "seed {seed}");
+            }
+            None => panic!("seed {seed}: Game Over without an outcome"),
+        }
+    }
+}
@@ -0,0 +1,318 @@
+//! Rules reached through the `testing` state setters: weapon binding, potions, avoiding,
+//! the pick limit, HP bounds and how each way of ending a run is scored.
+
+use scoundrel::game::fixtures::{cards, deal, last_move, room, take, take_with};
+use scoundrel::game::{death_score, ActionError, AvoidStatus, GameEvent, GameMode, GamePhase, Outcome, PlayerAction, UseMode};
+
+#[test]
+fn set_weapon_fights_like_an_equipped_weapon() {
+    let mut game = deal("6c 2h 3h 4h");
+    game.set_weapon(4);
+    take(&mut game, 0);
+    assert_eq!(game.player.hp, 18);
+    assert_eq!(last_move(&game), Some(&GameEvent::Fight { monster: 6, with_weapon: Some(4), damage_taken: 2 }));
+}
+
+#[test]
+fn weapon_cannot_fight_above_its_last_kill() {
+    let mut game = deal("5c 7c 2h 3h");
+    game.set_weapon(9);
+    take(&mut game, 0);
+    assert_eq!(game.preview_fight(1, UseMode::Default), Some(7));
+    take(&mut game, 1);
+    assert_eq!(game.player.hp, 13);
+    assert_eq!(last_move(&game), Some(&GameEvent::Fight { monster: 7, with_weapon: None, damage_taken: 7 }));
+}
+
+#[test]
+fn weapon_mode_on_a_bound_weapon_fights_barehanded() {
+    let mut game = deal("5c 7c 2h 3h");
+    game.set_weapon(9);
+    take(&mut game, 0);
+    take_with(&mut game, 1, UseMode::Weapon);
+    assert_eq!(game.player.hp, 13);
+    assert_eq!(game.player.weapon.as_ref().and_then(|w| w.last_monster), Some(5));
+}
+
+#[test]
+fn weapon_can_fight_a_monster_equal_to_its_last_kill() {
+    let mut game = deal("5c 5s 2h 3h");
+    game.set_weapon(3);
+    take(&mut game, 0);
+    take(&mut game, 1);
+    assert_eq!(game.player.hp, 16);
+    assert_eq!(game.player.weapon.as_ref().map(|w| w.stack.len()), Some(2));
+}
+
+#[test]
+fn clear_weapon_leaves_every_fight_barehanded() {
+    let mut game = deal("5c 2h 3h 4h");
+    game.set_weapon(8);
+    game.clear_weapon();
+    assert_eq!(game.preview_fight(0, UseMode::Weapon), Some(5));
+    take(&mut game, 0);
+    assert_eq!(game.player.hp, 15);
+    assert!(game.discard.contains(&cards("5c")[0]));
+}
+
+#[test]
+fn weapon_at_least_as_strong_as_the_monster_takes_no_damage() {
+    let mut game = deal("9c 2h 3h 4h");
+    game.set_weapon(10);
+    take(&mut game, 0);
+    assert_eq!(game.player.hp, 20);
+    assert_eq!(game.player.weapon.as_ref().map(|w| w.stack.clone()), Some(cards("9c")));
+}
+
+#[test]
+fn new_weapon_lifts_the_old_binding() {
+    let mut game = deal("5c 8d 7c 2h");
+    game.set_weapon(9);
+    take(&mut game, 0);
+    take(&mut game, 1);
+    take(&mut game, 2);
+    // The 9 was bound to 5 and over; the fresh 8 fights the 7 outright
+    assert_eq!(game.player.hp, 20);
+    assert_eq!(last_move(&game), Some(&GameEvent::Fight { monster: 7, with_weapon: Some(8), damage_taken: 0 }));
+}
+
+#[test]
+fn potion_heals_up_to_max_hp() {
+    let mut game = deal("9h 2c 3c 4c");
+    game.set_hp(15);
+    take(&mut game, 0);
+    assert_eq!(game.player.hp, 20);
+}
+
+#[test]
+fn hp_never_exceeds_max_whatever_the_potion() {
+    for value in 2..=10 {
+        let mut game = deal(&format!("{}h 2c 3c 4c", if value == 10 { "T".to_string() } else { value.to_string() }));
+        game.set_hp(19);
+        take(&mut game, 0);
+        assert_eq!(game.player.hp, game.player.max_hp, "potion {}", value);
+    }
+}
+
+#[test]
+fn potion_taken_twice_in_a_room_heals_once() {
+    let mut game = deal("4h 6h 2c 3c");
+    game.set_hp(5);
+    take(&mut game, 0);
+    take(&mut game, 1);
+    assert_eq!(game.player.hp, 9);
+    assert!(game.potion_used_this_turn);
+    assert_eq!(last_move(&game), Some(&GameEvent::PotionDiscarded { value: 6 }));
+}
+
+#[test]
+fn room_cannot_be_avoided_twice_in_a_row() {
+    let mut game = deal("2c 3c 4c 5c 6c 7c 8c 9c");
+    game.apply_action(PlayerAction::AvoidRoom).expect("first avoid");
+    assert_eq!(game.avoid_status(), AvoidStatus::UsedLastRoom);
+    assert_eq!(game.apply_action(PlayerAction::AvoidRoom), Err(ActionError::AvoidNotAllowed));
+    assert_eq!(game.room_number, 2);
+}
+
+#[test]
+fn avoiding_is_allowed_again_after_a_room_is_played() {
+    let mut game = deal("2c 3c 4c 5c 2s 3s 4s 5s 6s 7s 8s 9s");
+    game.apply_action(PlayerAction::AvoidRoom).expect("first avoid");
+    take(&mut game, 0);
+    take(&mut game, 1);
+    take(&mut game, 2);
+    assert_eq!(game.avoid_status(), AvoidStatus::Ready);
+    assert_eq!(game.apply_action(PlayerAction::AvoidRoom), Ok(()));
+}
+
+#[test]
+fn room_cannot_be_avoided_once_a_card_is_taken() {
+    let mut game = deal("2c 3c 4c 5c 6c");
+    take(&mut game, 0);
+    assert_eq!(game.avoid_status(), AvoidStatus::NeedFourCards);
+    assert_eq!(game.apply_action(PlayerAction::AvoidRoom), Err(ActionError::AvoidNotAllowed));
+}
+
+#[test]
+fn hardcore_never_allows_avoiding() {
+    let mut game = deal("2c 3c 4c 5c 6c");
+    game.rules.hardcore = true;
+    assert_eq!(game.avoid_status(), AvoidStatus::Hardcore);
+    assert_eq!(game.apply_action(PlayerAction::AvoidRoom), Err(ActionError::AvoidNotAllowed));
+}
+
+#[test]
+fn turn_ends_after_three_picks_keeping_the_fourth_card() {
+    let mut game = deal("2c 3c 4c 5c 6c 7c 8c");
+    take(&mut game, 0);
+    take(&mut game, 1);
+    take(&mut game, 2);
+    assert_eq!(game.choices_this_turn, 0);
+    assert_eq!(game.room_number, 2);
+    assert_eq!(game.stats.rooms_completed, 1);
+    assert_eq!(room(&game), cards("6c 7c 8c 5c").into_iter().map(Some).collect::<Vec<_>>());
+}
+
+#[test]
+fn a_take_after_three_choices_ends_the_turn_instead() {
+    let mut game = deal("2c 3c 4c 5c 6c");
+    game.set_choices_this_turn(3);
+    take(&mut game, 0);
+    assert_eq!(game.player.hp, 20, "no card was taken");
+    assert_eq!(room(&game)[0], Some(cards("2c")[0]));
+    assert_eq!((game.choices_this_turn, game.room_number), (0, 2));
+}
+
+#[test]
+fn two_choices_made_leave_a_single_pick() {
+    let mut game = deal("2c 3c 4c 5c 6c 7c 8c");
+    game.set_choices_this_turn(2);
+    take(&mut game, 0);
+    assert_eq!(game.room_number, 2);
+    assert_eq!(room(&game), cards("6c 3c 4c 5c").into_iter().map(Some).collect::<Vec<_>>());
+}
+
+#[test]
+fn choices_remaining_follows_the_picks() {
+    let mut game = deal("2c 3c 4c 5c 6c");
+    assert_eq!(game.choices_remaining_this_turn(), 3);
+    game.set_choices_this_turn(2);
+    assert_eq!(game.choices_remaining_this_turn(), 1);
+    game.set_choices_this_turn(5);
+    assert_eq!(game.choices_remaining_this_turn(), 0);
+}
+
+#[test]
+fn empty_and_missing_slots_are_rejected() {
+    let mut game = deal("2c 3c 4c 5c 6c");
+    take(&mut game, 0);
+    assert_eq!(game.apply_action(PlayerAction::TakeCard { slot: 0, mode: UseMode::Default }), Err(ActionError::SlotEmpty(0)));
+    assert_eq!(game.apply_action(PlayerAction::TakeCard { slot: 4, mode: UseMode::Default }), Err(ActionError::InvalidSlot(4)));
+}
+
+#[test]
+fn no_cards_can_be_taken_after_the_run_ends() {
+    let mut game = deal("Ks 2c 3c 4c");
+    game.set_hp(1);
+    take(&mut game, 0);
+    assert_eq!(game.phase, GamePhase::GameOver);
+    assert_eq!(game.apply_action(PlayerAction::TakeCard { slot: 1, mode: UseMode::Default }), Err(ActionError::GameNotRunning));
+    assert_eq!(game.apply_action(PlayerAction::AvoidRoom), Err(ActionError::GameNotRunning));
+}
+
+#[test]
+fn death_comes_at_exactly_zero_hp() {
+    let mut game = deal("5c 2c 3c 4c");
+    game.set_hp(5);
+    take(&mut game, 0);
+    assert_eq!(game.player.hp, 0);
+    assert_eq!(game.outcome, Some(Outcome::Death));
+}
+
+#[test]
+fn one_hp_left_keeps_the_run_going() {
+    let mut game = deal("5c 2h 3h 4h");
+    game.set_hp(6);
+    take(&mut game, 0);
+    assert_eq!(game.player.hp, 1);
+    assert_eq!(game.phase, GamePhase::Running);
+    assert_eq!(game.score, None);
+}
+
+#[test]
+fn victory_score_is_the_hp_left() {
+    let mut game = deal("2c 3c 4c 5c");
+    take(&mut game, 0);
+    take(&mut game, 1);
+    take(&mut game, 2);
+    assert_eq!(game.phase, GamePhase::Running, "one card is left over");
+    take(&mut game, 3);
+    assert_eq!(game.outcome, Some(Outcome::Victory));
+    assert_eq!(game.score, Some(20 - 14));
+}
+
+#[test]
+fn last_potion_below_max_hp_earns_no_bonus() {
+    let mut game = deal("9c 8c 2c 3h");
+    take(&mut game, 0);
+    take(&mut game, 1);
+    take(&mut game, 2);
+    take(&mut game, 3);
+    assert_eq!(game.last_card_potion_value, Some(3));
+    assert_eq!(game.score, Some(1 + 3));
+}
+
+#[test]
+fn rooms_bonus_rule_pays_per_room_survived() {
+    let mut game = deal("2c 3c 4c 5c");
+    game.rules.rooms_bonus_per_room = 2;
+    for slot in 0..4 { take(&mut game, slot); }
+    assert_eq!(game.rooms_survived(), 1);
+    assert_eq!(game.score, Some(6 + 2));
+}
+
+#[test]
+fn streak_bonus_rule_adds_half_the_longest_clean_streak() {
+    let mut game = deal("5c 4c 3c 2c");
+    game.rules.kill_streak_bonus = true;
+    game.set_weapon(10);
+    for slot in 0..4 { take(&mut game, slot); }
+    assert_eq!(game.max_kill_streak_this_run, 4);
+    assert_eq!(game.score, Some(20 + 2));
+}
+
+#[test]
+fn death_score_goes_below_zero_with_the_overkill() {
+    let mut game = deal("Ks 9c 2h 3h");
+    game.set_hp(2);
+    take(&mut game, 0);
+    assert_eq!(game.score, Some(-11 - 9));
+}
+
+#[test]
+fn abandoning_scores_the_run_like_a_death() {
+    let mut game = deal("9c 2c 3h 4h 5c");
+    take(&mut game, 0);
+    let expected = game.projected_score().1;
+    game.apply_action(PlayerAction::AbandonRun).expect("run in progress");
+    game.apply_action(PlayerAction::ConfirmAccept).expect("abandon asked");
+    assert_eq!(game.phase, GamePhase::Menu);
+    assert_eq!(game.score, Some(expected));
+    assert_eq!(expected, death_score(11, 2 + 5));
+    assert!(game.leaderboard.first().is_some_and(|e| e.abandoned && !e.won));
+}
+
+#[test]
+fn endless_mode_deals_the_discards_as_the_next_dungeon() {
+    let mut game = deal("2c 3c 4c 5h");
+    game.game_mode = GameMode::Endless;
+    for slot in 0..4 { take(&mut game, slot); }
+    assert_eq!(game.phase, GamePhase::Running);
+    assert_eq!(game.dungeons_cleared, 1);
+    assert_eq!(game.room.iter().flatten().count(), 4);
+    assert!(game.discard.is_empty());
+}
+
+#[test]
+fn endless_mode_keeps_benched_suits_out_of_the_next_dungeon() {
+    let mut game = deal("2c 8d 3c 4h");
+    game.game_mode = GameMode::Endless;
+    game.rules.no_weapons = true;
+    game.rules.no_potions = true;
+    for slot in 0..4 { take(&mut game, slot); }
+    assert_eq!(game.dungeons_cleared, 1);
+    assert_eq!(game.room.iter().flatten().filter(|c| c.is_monster()).count(), 2);
+    assert_eq!(game.room.iter().flatten().count(), 2);
+    assert_eq!(game.discard, cards("8d 4h"));
+}
+
+#[test]
+fn endless_mode_ends_when_only_benched_cards_are_left() {
+    let mut game = deal("5h 6h 7h 8h");
+    game.game_mode = GameMode::Endless;
+    game.rules.no_potions = true;
+    for slot in 0..4 { take(&mut game, slot); }
+    assert_eq!(game.phase, GamePhase::GameOver);
+    assert_eq!(game.outcome, Some(Outcome::Victory));
+    assert_eq!(game.dungeons_cleared, 0);
+}
@@ -0,0 +1,179 @@
+//! Rules suite: decks staged card by card through every branch of `resolve_card`.
+
+use scoundrel::game::fixtures::{cards, deal, last_move, room, take, take_with};
+use scoundrel::game::{death_score, GameEvent, GamePhase, Outcome, PlayerAction, UseMode};
+
+#[test]
+fn weapon_binds_across_decreasing_then_increasing_fights() {
+    let mut game = deal("7d 6c 4s 2h 5c 3c 9h 8c");
+    take(&mut game, 0);
+    take(&mut game, 1);
+    take(&mut game, 2);
+    assert_eq!(game.player.hp, 20, "both monsters were under the weapon");
+    assert_eq!(room(&game), vec![Some(cards("5c")[0]), Some(cards("3c")[0]), Some(cards("9h")[0]), Some(cards("2h")[0])]);
+    // 5 is above the last kill (4): the weapon can't be used, so it is a barehand fight
+    take(&mut game, 0);
+    assert_eq!(last_move(&game), Some(&GameEvent::Fight { monster: 5, with_weapon: None, damage_taken: 5 }));
+    assert_eq!(game.player.hp, 15);
+    // 3 is below it again
+    take(&mut game, 1);
+    assert_eq!(last_move(&game), Some(&GameEvent::Fight { monster: 3, with_weapon: Some(7), damage_taken: 0 }));
+    let weapon = game.player.weapon.as_ref().expect("weapon kept");
+    assert_eq!(weapon.last_monster, Some(3));
+    assert_eq!(weapon.stack, cards("6c 4s 3c"));
+    assert!(game.discard.contains(&cards("5c")[0]), "the barehand kill is discarded");
+}
+
+#[test]
+fn weapon_fight_takes_what_the_weapon_does_not_absorb() {
+    let mut game = deal("3d Ks 2c 2s");
+    take(&mut game, 0);
+    take(&mut game, 1);
+    assert_eq!(last_move(&game), Some(&GameEvent::Fight { monster: 13, with_weapon: Some(3), damage_taken: 10 }));
+    assert_eq!(game.player.hp, 10);
+    assert_eq!(game.stats.damage_taken, 10);
+}
+
+#[test]
+fn second_potion_in_a_turn_is_discarded() {
+    let mut game = deal("9c 5h 4h 2s");
+    take(&mut game, 0);
+    take(&mut game, 1);
+    assert_eq!(game.player.hp, 16);
+    take(&mut game, 2);
+    assert_eq!(game.player.hp, 16);
+    assert_eq!(last_move(&game), Some(&GameEvent::PotionDiscarded { value: 4 }));
+    assert_eq!((game.stats.potions_used, game.stats.potions_wasted), (1, 1));
+    assert!(game.discard.contains(&cards("4h")[0]));
+}
+
+#[test]
+fn potion_limit_resets_with_the_next_room() {
+    let mut game = deal("9c 5h 2s 3s 4h 2c 3c 4c");
+    take(&mut game, 0);
+    take(&mut game, 1);
+    take(&mut game, 2);
+    assert_eq!(room(&game)[0], Some(cards("4h")[0]));
+    assert!(!game.potion_used_this_turn);
+    take(&mut game, 0);
+    assert_eq!(game.player.hp, 18);
+    assert_eq!(game.stats.potions_used, 2);
+}
+
+#[test]
+fn potion_above_max_hp_records_the_overheal() {
+    let mut game = deal("3c 9h 2c 2s");
+    take(&mut game, 0);
+    take(&mut game, 1);
+    assert_eq!(game.player.hp, 20);
+    let tail = &game.history[game.history.len() - 2..];
+    assert_eq!(tail, [
+        GameEvent::Potion { value: 9, hp_before: 17, hp_after: 20 },
+        GameEvent::PotionOverheal { value: 6, capped_at: 20 },
+    ]);
+    assert_eq!(game.stats.healing_wasted, 6);
+}
+
+#[test]
+fn avoided_room_returns_under_the_deck_in_the_order_seen() {
+    let mut game = deal("2c 3c 4c 5c 6c 7c 8c 9c");
+    game.apply_action(PlayerAction::AvoidRoom).expect("a full room can be avoided");
+    assert_eq!(room(&game), cards("6c 7c 8c 9c").into_iter().map(Some).collect::<Vec<_>>());
+    assert_eq!(game.deck.top_n_cards(4), cards("2c 3c 4c 5c"));
+    assert!(game.history.contains(&GameEvent::Avoid { cards: cards("2c 3c 4c 5c") }));
+    assert_eq!(game.room_number, 2);
+}
+
+#[test]
+fn death_score_counts_the_monsters_left_face_up_and_in_the_deck() {
+    let mut game = deal("Ks Qs 2h 3d 5c 4s 7h");
+    game.set_hp(10);
+    take(&mut game, 0);
+    assert_eq!(game.phase, GamePhase::GameOver);
+    assert_eq!(game.outcome, Some(Outcome::Death));
+    // Qs in the room, 5c and 4s in the deck
+    assert_eq!(game.score, Some(death_score(-3, 12 + 5 + 4)));
+    let cause = game.death_cause.as_ref().expect("death recorded");
+    assert_eq!((cause.card, cause.damage, cause.hp_before, cause.with_weapon), (cards("Ks")[0], 13, 10, None));
+}
+
+#[test]
+fn last_card_potion_at_full_hp_adds_its_value_to_a_victory() {
+    let mut game = deal("9h 2c 3c 8h");
+    take(&mut game, 1);
+    take(&mut game, 2);
+    take(&mut game, 0);
+    assert_eq!(game.player.hp, 20);
+    take(&mut game, 3);
+    assert_eq!(game.outcome, Some(Outcome::Victory));
+    assert_eq!(game.last_card_potion_value, Some(8));
+    assert_eq!(game.score, Some(20 + 8));
+}
+
+#[test]
+fn no_potions_rule_discards_hearts_untouched() {
+    let mut game = deal("5h 9c 2c 3c");
+    game.rules.no_potions = true;
+    game.set_hp(10);
+    take(&mut game, 0);
+    assert_eq!(game.player.hp, 10);
+    assert_eq!(last_move(&game), Some(&GameEvent::PotionDiscarded { value: 5 }));
+    assert_eq!(game.discard, cards("5h"));
+    assert_eq!(game.stats.potions_used, 0);
+}
+
+#[test]
+fn rule_discards_have_their_own_history_lines() {
+    assert_eq!(GameEvent::PotionDiscarded { value: 5 }.to_string(), "🧪 discarded 5");
+    assert_eq!(GameEvent::WeaponDiscarded { value: 8 }.to_string(), "🗡️ discarded 8");
+}
+
+#[test]
+fn no_weapons_rule_discards_diamonds_unequipped() {
+    let mut game = deal("8d 9c 2c 3c");
+    game.rules.no_weapons = true;
+    take(&mut game, 0);
+    assert!(game.player.weapon.is_none());
+    assert_eq!(last_move(&game), Some(&GameEvent::WeaponDiscarded { value: 8 }));
+    assert_eq!(game.discard, cards("8d"));
+    assert_eq!(game.stats.weapons_equipped, 0);
+}
+
+#[test]
+fn new_weapon_discards_the_old_one_with_its_kills() {
+    let mut game = deal("5d 3c 8d 2h");
+    take(&mut game, 0);
+    take(&mut game, 1);
+    take(&mut game, 2);
+    assert_eq!(last_move(&game), Some(&GameEvent::WeaponReplaced { old: 5, new: 8 }));
+    let weapon = game.player.weapon.as_ref().expect("new weapon equipped");
+    assert_eq!((weapon.value, weapon.last_monster, weapon.stack.len()), (8, None, 0));
+    assert_eq!(game.discard, cards("5d 3c"));
+}
+
+#[test]
+fn worn_out_weapon_breaks_after_the_fight() {
+    let mut game = deal("5d 3c 2c 4h");
+    game.rules.weapon_breaks_after_uses = Some(1);
+    take(&mut game, 0);
+    take(&mut game, 1);
+    assert!(game.player.weapon.is_none());
+    let tail = &game.history[game.history.len() - 2..];
+    assert_eq!(tail, [
+        GameEvent::Fight { monster: 3, with_weapon: Some(5), damage_taken: 0 },
+        GameEvent::WeaponDestroyed { value: 5, kills: 1 },
+    ]);
+    assert_eq!(game.discard, cards("5d 3c"));
+}
+
+#[test]
+fn barehand_mode_leaves_the_weapon_unbound() {
+    let mut game = deal("9d 4c 8c 2h");
+    take(&mut game, 0);
+    take_with(&mut game, 1, UseMode::Barehand);
+    assert_eq!(game.player.hp, 16);
+    assert_eq!(game.player.weapon.as_ref().map(|w| w.last_monster), Some(None));
+    take_with(&mut game, 2, UseMode::Weapon);
+    assert_eq!(game.player.hp, 16);
+    assert_eq!(game.current_kill_streak, 1);
+}
@@ -0,0 +1,85 @@
+//! `kill` on a running session: the binary gets a pseudo-terminal, is sent SIGTERM, and must
+//! leave raw mode and the alternate screen before it exits.
+#![cfg(unix)]
+
+use std::fs::File;
+use std::io::Read;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const ENTER_ALT_SCREEN: &[u8] = b"\x1b[?1049h";
+const LEAVE_ALT_SCREEN: &[u8] = b"\x1b[?1049l";
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+// (controller, terminal) ends of a fresh 80x24 pty
+fn open_pty() -> (File, OwnedFd) {
+    let (mut controller, mut terminal) = (0, 0);
+    let size = libc::winsize { ws_row: 24, ws_col: 80, ws_xpixel: 0, ws_ypixel: 0 };
+    let rc = unsafe { libc::openpty(&mut controller, &mut terminal, std::ptr::null_mut(), std::ptr::null(), &size) };
+    assert_eq!(rc, 0, "openpty: {}", std::io::Error::last_os_error());
+    unsafe { (File::from_raw_fd(controller), OwnedFd::from_raw_fd(terminal)) }
+}
+
+fn termios(fd: &OwnedFd) -> libc::termios {
+    let mut t = unsafe { std::mem::zeroed() };
+    assert_eq!(unsafe { libc::tcgetattr(fd.as_raw_fd(), &mut t) }, 0, "tcgetattr");
+    t
+}
+
+#[test]
+fn sigterm_restores_the_terminal() {
+    let (mut controller, terminal) = open_pty();
+    let cooked = termios(&terminal);
+    assert_ne!(cooked.c_lflag & libc::ICANON, 0, "a fresh pty starts in cooked mode");
+
+    // Score and settings files land next to the binary's working directory
+    let dir = std::env::temp_dir().join(format!("scoundrel-signal-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("temp dir is writable");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_scoundrel"))
+        .arg("--quiet")
+        .current_dir(&dir)
+        .env("TERM", "xterm-256color")
+        .stdin(Stdio::from(terminal.try_clone().expect("dup pty")))
+        .stdout(Stdio::from(terminal.try_clone().expect("dup pty")))
+        .stderr(Stdio::from(terminal.try_clone().expect("dup pty")))
+        .spawn()
+        .expect("spawn the binary");
+
+    // Keep the pty drained so the child never blocks on a full buffer
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&output);
+    thread::spawn(move || {
+        let mut buf = [0u8; 4096];
+        while let Ok(n @ 1..) = controller.read(&mut buf) {
+            sink.lock().unwrap().extend_from_slice(&buf[..n]);
+        }
+    });
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !contains(&output.lock().unwrap(), ENTER_ALT_SCREEN) {
+        assert!(Instant::now() < deadline, "the UI never started");
+        thread::sleep(Duration::from_millis(10));
+    }
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(termios(&terminal).c_lflag & libc::ICANON, 0, "the running UI is in raw mode");
+    assert_eq!(unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGTERM) }, 0, "kill");
+
+    let status = child.wait().expect("wait for the binary");
+    assert_eq!(status.code(), Some(128 + libc::SIGTERM), "exits with the shell's status for SIGTERM");
+    let restored = termios(&terminal);
+    assert_eq!(restored.c_lflag & (libc::ICANON | libc::ECHO), cooked.c_lflag & (libc::ICANON | libc::ECHO), "raw mode is off");
+    // The reader may still be catching up with the last bytes written
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !contains(&output.lock().unwrap(), LEAVE_ALT_SCREEN) {
+        assert!(Instant::now() < deadline, "the alternate screen was never left");
+        thread::sleep(Duration::from_millis(10));
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+}
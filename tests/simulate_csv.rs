@@ -0,0 +1,47 @@
+//! `--simulate N --csv`: the per-game rows a spreadsheet gets.
+
+use std::collections::HashSet;
+use std::process::Command;
+
+use scoundrel::seed_codec;
+
+const GAMES: u64 = 50;
+const BASE_SEED: u64 = 1000;
+const COLUMNS: [&str; 8] = ["seed", "strategy", "won", "score", "rooms", "monsters_slain", "potions_used", "actions"];
+
+#[test]
+fn fifty_games_write_a_row_per_game_and_strategy() {
+    let dir = std::env::temp_dir().join(format!("scoundrel-simulate-csv-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("temp dir is writable");
+    let out = dir.join("out.csv");
+    let status = Command::new(env!("CARGO_BIN_EXE_scoundrel"))
+        .args(["--simulate", &GAMES.to_string(), "--strategies", "first,cautious", "--seed", &BASE_SEED.to_string(), "--csv"])
+        .arg(&out)
+        .current_dir(&dir)
+        .status()
+        .expect("run the binary");
+    assert!(status.success(), "--simulate failed: {status}");
+
+    let mut reader = csv::Reader::from_path(&out).expect("the CSV was written");
+    let header: Vec<String> = reader.headers().expect("header row").iter().map(str::to_string).collect();
+    assert_eq!(header, COLUMNS);
+    let rows: Vec<csv::StringRecord> = reader.records().map(|r| r.expect("well-formed row")).collect();
+    assert_eq!(rows.len() as u64, GAMES * 2, "one row per game and strategy");
+
+    let mut pairs = HashSet::new();
+    for row in &rows {
+        assert_eq!(row.len(), COLUMNS.len(), "{row:?}");
+        assert!(row.iter().all(|field| !field.eq_ignore_ascii_case("nan") && !field.is_empty()), "{row:?}");
+        let seed = seed_codec::decode(&row[0]).expect("seed code");
+        assert!((BASE_SEED..BASE_SEED + GAMES).contains(&seed), "{row:?}");
+        assert!(matches!(&row[1], "first" | "cautious"), "{row:?}");
+        assert!(pairs.insert((seed, row[1].to_string())), "duplicate row {row:?}");
+        assert!(matches!(&row[2], "true" | "false"), "{row:?}");
+        row[3].parse::<i32>().expect("score is an integer");
+        for field in row.iter().skip(4) { field.parse::<u32>().expect("counts are whole numbers"); }
+    }
+    // Both strategies played the same seeds
+    assert_eq!(pairs.len() as u64, GAMES * 2);
+    let _ = std::fs::remove_dir_all(&dir);
+}
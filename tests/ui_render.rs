@@ -0,0 +1,86 @@
+//! The screens as `TestRenderer` draws them, checked row by row.
+
+use ratatui::style::{Color, Modifier};
+use scoundrel::config::HelpTab;
+use scoundrel::game::{Game, GamePhase};
+use scoundrel::rules::Rules;
+use scoundrel::simulate;
+use scoundrel::ui::TestRenderer;
+
+const TITLE_ART_TOP: &str = "____                            _          _";
+
+fn menu() -> Game {
+    Game::sandboxed(7, Rules::default())
+}
+
+fn running() -> Game {
+    let mut game = menu();
+    simulate::make_unattended(&mut game);
+    game.new_run();
+    assert_eq!(game.phase, GamePhase::Running);
+    game
+}
+
+fn find(rows: &[String], text: &str) -> Option<(u16, u16)> {
+    rows.iter().enumerate().find_map(|(y, r)| r.find(text).map(|i| (y as u16, r[..i].chars().count() as u16)))
+}
+
+#[test]
+fn menu_draws_the_ascii_art_title() {
+    let rows = TestRenderer::new(80, 24).render(&menu());
+    let (top, _) = find(&rows, TITLE_ART_TOP).expect("first art line");
+    assert!(rows[top as usize + 4].contains("|____/ \\___\\___/ \\__,_|_| |_|\\__,_|_|  \\___|_|"), "{rows:#?}");
+    assert!(find(&rows, "New Game").is_some_and(|(y, _)| y > top + 4), "menu entries sit below the title");
+}
+
+#[test]
+fn status_bar_shows_the_hp() {
+    let mut game = running();
+    game.set_hp(13);
+    let mut renderer = TestRenderer::new(100, 30);
+    let rows = renderer.render(&game);
+    let (y, x) = find(&rows, "HP: 13/20").expect("HP in the status bar");
+    // The value carries the theme's HP color
+    let cell = renderer.cell_at(y, x + 4).expect("cell on screen");
+    assert_eq!(cell.symbol(), "1");
+    assert_ne!(cell.fg, Color::Reset);
+}
+
+#[test]
+fn help_overlay_appears_with_show_help() {
+    let mut game = menu();
+    let mut renderer = TestRenderer::new(80, 24);
+    assert!(find(&renderer.render(&game), "Help — ").is_none());
+    game.show_help = true;
+    let rows = renderer.render(&game);
+    assert!(find(&rows, "Help — ").is_some(), "{rows:#?}");
+    assert!(find(&rows, "Controls:").is_some(), "{rows:#?}");
+}
+
+// Compares `rows` with tests/snapshots/<name>.txt; UPDATE_SNAPSHOTS=1 rewrites the file instead
+fn assert_snapshot(name: &str, rows: &[String]) {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots").join(format!("{name}.txt"));
+    let actual = rows.join("\n") + "\n";
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, &actual).expect("snapshot dir is writable");
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {e} (run with UPDATE_SNAPSHOTS=1)", path.display()));
+    assert!(actual == expected, "{name} changed; expected:\n{expected}\nactual:\n{actual}");
+}
+
+#[test]
+fn each_help_tab_matches_its_snapshot() {
+    let mut game = menu();
+    game.toggle_full_help();
+    for tab in HelpTab::ALL {
+        game.help_tab = tab;
+        let mut renderer = TestRenderer::new(80, 24);
+        let rows = renderer.render(&game);
+        assert_snapshot(&format!("help_{}", tab.label().to_lowercase()), &rows);
+        // The text alone does not show which page is open
+        let (y, x) = find(&rows, &format!(" {} ", tab.label())).expect("tab label");
+        let cell = renderer.cell_at(y, x + 1).expect("cell on screen");
+        assert!(cell.modifier.contains(Modifier::REVERSED), "{} is highlighted", tab.label());
+    }
+}